@@ -1,26 +1,48 @@
 
 use axum::{
     extract::{Path, Request, State},
-    http::HeaderMap,
-    response::Response,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use serde_json::json;
+use shared::models::ApiResponse;
 
 use crate::proxy::forward_request;
 use crate::AppState;
 
 // citanje body-ja
 
-async fn read_body(req: Request) -> (HeaderMap, String) {
+// body ostaje u bajtovima - multipart upload slika nije validan UTF-8.
+// Preveliki body se odbija ovde, umesto da se servisu posalje prazan.
+async fn read_body(state: &AppState, req: Request) -> Result<(HeaderMap, Vec<u8>), Response> {
     let headers = req.headers().clone();
-    let body_bytes = axum::body::to_bytes(req.into_body(), 5 * 1024 * 1024)
-        .await
-        .unwrap_or_default();
-    let body = String::from_utf8_lossy(&body_bytes).to_string();
-    (headers, body)
+    match axum::body::to_bytes(req.into_body(), state.max_body_bytes).await {
+        Ok(bytes) => Ok((headers, bytes.to_vec())),
+        Err(_) => Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            Json(ApiResponse::<()>::error("Zahtev je prevelik")),
+        )
+            .into_response()),
+    }
 }
 
+/// Prosledjuje zahtev servisu na istu putanju (bez /api prefiksa), zajedno sa query stringom i body-jem
+async fn passthrough(state: &AppState, base_url: &str, req: Request) -> Response {
+    let method = req.method().as_str().to_string();
+    let path = req
+        .uri()
+        .path_and_query()
+        .map(|pq| pq.as_str().to_string())
+        .unwrap_or_default();
+    let url = format!("{}{}", base_url, path.strip_prefix("/api").unwrap_or(&path));
+    let (headers, body) = match read_body(state, req).await {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let body = if body.is_empty() { None } else { Some(body) };
+    forward_request(&state.client, &method, &url, &headers, body).await
+}
 
 /// GET health - proverava sve servise
 pub async fn health_check(State(state): State<AppState>) -> Json<serde_json::Value> {
@@ -44,13 +66,19 @@ pub async fn health_check(State(state): State<AppState>) -> Json<serde_json::Val
 //  Auth rute 
 
 pub async fn auth_register(State(state): State<AppState>, req: Request) -> Response {
-    let (headers, body) = read_body(req).await;
+    let (headers, body) = match read_body(&state, req).await {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
     let url = format!("{}/register", state.auth_url);
     forward_request(&state.client, "POST", &url, &headers, Some(body)).await
 }
 
 pub async fn auth_login(State(state): State<AppState>, req: Request) -> Response {
-    let (headers, body) = read_body(req).await;
+    let (headers, body) = match read_body(&state, req).await {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
     let url = format!("{}/login", state.auth_url);
     forward_request(&state.client, "POST", &url, &headers, Some(body)).await
 }
@@ -61,10 +89,24 @@ pub async fn auth_me(State(state): State<AppState>, req: Request) -> Response {
     forward_request(&state.client, "GET", &url, &headers, None).await
 }
 
+pub async fn auth_profile(State(state): State<AppState>, req: Request) -> Response {
+    let method = req.method().as_str().to_string();
+    let (headers, body) = match read_body(&state, req).await {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let url = format!("{}/profile", state.auth_url);
+    let body = (method != "GET").then_some(body);
+    forward_request(&state.client, &method, &url, &headers, body).await
+}
+
 //  Event rute 
 
 pub async fn event_create(State(state): State<AppState>, req: Request) -> Response {
-    let (headers, body) = read_body(req).await;
+    let (headers, body) = match read_body(&state, req).await {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
     let url = format!("{}/events", state.event_url);
     forward_request(&state.client, "POST", &url, &headers, Some(body)).await
 }
@@ -83,7 +125,9 @@ pub async fn event_get(
     req: Request,
 ) -> Response {
     let headers = req.headers().clone();
-    let url = format!("{}/events/{}", state.event_url, id);
+    // ?lang= za prevod
+    let query = req.uri().query().map(|q| format!("?{}", q)).unwrap_or_default();
+    let url = format!("{}/events/{}{}", state.event_url, id, query);
     forward_request(&state.client, "GET", &url, &headers, None).await
 }
 
@@ -92,7 +136,10 @@ pub async fn event_update(
     Path(id): Path<String>,
     req: Request,
 ) -> Response {
-    let (headers, body) = read_body(req).await;
+    let (headers, body) = match read_body(&state, req).await {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
     let url = format!("{}/events/{}", state.event_url, id);
     forward_request(&state.client, "PUT", &url, &headers, Some(body)).await
 }
@@ -107,10 +154,18 @@ pub async fn event_delete(
     forward_request(&state.client, "DELETE", &url, &headers, None).await
 }
 
+/// Ostale event rute (serije, slike, ...) - prosledjuju se bez izmena
+pub async fn event_passthrough(State(state): State<AppState>, req: Request) -> Response {
+    passthrough(&state, &state.event_url, req).await
+}
+
 //  Registration rute 
 
 pub async fn reg_create(State(state): State<AppState>, req: Request) -> Response {
-    let (headers, body) = read_body(req).await;
+    let (headers, body) = match read_body(&state, req).await {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
     let url = format!("{}/registrations", state.registration_url);
     forward_request(&state.client, "POST", &url, &headers, Some(body)).await
 }
@@ -141,3 +196,8 @@ pub async fn reg_qr(
     let url = format!("{}/registrations/{}/qr", state.registration_url, id);
     forward_request(&state.client, "GET", &url, &headers, None).await
 }
+
+/// Ostale registration rute (licni kalendar, ...) - prosledjuju se bez izmena
+pub async fn reg_passthrough(State(state): State<AppState>, req: Request) -> Response {
+    passthrough(&state, &state.registration_url, req).await
+}
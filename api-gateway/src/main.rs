@@ -12,6 +12,8 @@ pub struct AppState {
     pub auth_url: String,
     pub event_url: String,
     pub registration_url: String,
+    /// najveci body koji se prosledjuje - prati limit za upload slika u event-service-u
+    pub max_body_bytes: usize,
 }
 
 #[tokio::main]
@@ -27,13 +29,21 @@ async fn main() {
             .unwrap_or_else(|_| "http://localhost:3003".to_string()),
         registration_url: std::env::var("REGISTRATION_SERVICE_URL")
             .unwrap_or_else(|_| "http://localhost:3004".to_string()),
+        // isti MAX_IMAGE_BYTES kao event-service, plus rezerva za multipart
+        max_body_bytes: std::env::var("MAX_IMAGE_BYTES")
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(4 * 1024 * 1024)
+            + 64 * 1024,
     };
 
     // CORS - dozvoljava frontend-u da pristupa API-ju
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods(Any)
-        .allow_headers(Any);
+        .allow_headers(Any)
+        // frontend cita verziju dogadjaja za If-Match
+        .expose_headers([axum::http::header::ETAG]);
 
     let app = Router::new()
         // Health
@@ -42,12 +52,85 @@ async fn main() {
         .route("/api/auth/register", post(handlers::auth_register))
         .route("/api/auth/login", post(handlers::auth_login))
         .route("/api/auth/me", get(handlers::auth_me))
+        .route("/api/auth/profile", get(handlers::auth_profile).put(handlers::auth_profile))
         // Events
         .route("/api/events", get(handlers::event_list).post(handlers::event_create))
+        .route("/api/events/feed.ics", get(handlers::event_passthrough))
+        .route("/api/events/feed.atom", get(handlers::event_passthrough))
+        .route("/api/events/feed.rss", get(handlers::event_passthrough))
+        .route("/api/events/import", post(handlers::event_passthrough))
+        .route("/api/events/trash", get(handlers::event_passthrough))
         .route("/api/events/:id", get(handlers::event_get).put(handlers::event_update).delete(handlers::event_delete))
+        // Sesije, predavaci, agenda
+        .route("/api/events/:id/jsonld", get(handlers::event_passthrough))
+        .route("/api/events/:id/page", get(handlers::event_passthrough))
+        .route("/api/events/:id/clone", post(handlers::event_passthrough))
+        .route("/api/events/:id/restore", post(handlers::event_passthrough))
+        .route("/api/events/:id/revisions", get(handlers::event_passthrough))
+        .route("/api/events/:id/revisions/:version/restore", post(handlers::event_passthrough))
+        .route("/api/events/link/:slug", get(handlers::event_passthrough))
+        .route("/api/events/bookmarked", get(handlers::event_passthrough))
+        .route("/api/events/recommended", get(handlers::event_passthrough))
+        .route("/api/recommendations/refresh", post(handlers::event_passthrough))
+        .route("/api/events/:id/access", get(handlers::event_passthrough))
+        .route("/api/events/:id/access/slug", post(handlers::event_passthrough))
+        .route("/api/events/:id/invitations", post(handlers::event_passthrough))
+        .route("/api/events/:id/invitations/:email", delete(handlers::event_passthrough))
+        .route("/api/events/:id/staff", get(handlers::event_passthrough).post(handlers::event_passthrough))
+        .route("/api/events/:id/staff/:user_id", delete(handlers::event_passthrough))
+        .route("/api/events/:id/attendees", get(handlers::reg_passthrough))
+        .route("/api/events/:id/check-in", post(handlers::reg_passthrough))
+        .route("/api/events/:id/analytics", get(handlers::reg_passthrough))
+        .route("/api/analytics/organizer", get(handlers::reg_passthrough))
+        .route("/api/events/:id/image", post(handlers::event_passthrough).delete(handlers::event_passthrough))
+        .route("/api/uploads/*key", get(handlers::event_passthrough))
+        .route("/api/events/:id/sessions", get(handlers::event_passthrough).post(handlers::event_passthrough))
+        .route("/api/events/:id/sessions/:session_id", put(handlers::event_passthrough).delete(handlers::event_passthrough))
+        .route("/api/events/:id/sessions/:session_id/attend", post(handlers::event_passthrough).delete(handlers::event_passthrough))
+        .route("/api/events/:id/speakers", get(handlers::event_passthrough).post(handlers::event_passthrough))
+        .route("/api/events/:id/speakers/:speaker_id", put(handlers::event_passthrough).delete(handlers::event_passthrough))
+        .route("/api/events/:id/agenda", get(handlers::event_passthrough))
+        // Tipovi karata
+        .route("/api/events/:id/ticket-types", get(handlers::event_passthrough).post(handlers::event_passthrough))
+        .route("/api/events/:id/ticket-types/:type_id", get(handlers::event_passthrough).put(handlers::event_passthrough).delete(handlers::event_passthrough))
+        .route("/api/events/:id/reviews", get(handlers::event_passthrough).post(handlers::event_passthrough))
+        .route("/api/events/:id/reviews/:review_id", put(handlers::event_passthrough).delete(handlers::event_passthrough))
+        .route("/api/events/:id/reviews/:review_id/reply", put(handlers::event_passthrough).delete(handlers::event_passthrough))
+        .route("/api/events/:id/reviews/:review_id/report", post(handlers::event_passthrough))
+        .route("/api/events/:id/reviews/:review_id/moderation", put(handlers::event_passthrough))
+        .route("/api/reviews/reported", get(handlers::event_passthrough))
+        .route("/api/events/:id/questions", get(handlers::event_passthrough).post(handlers::event_passthrough))
+        .route("/api/events/:id/questions/:question_id", put(handlers::event_passthrough).delete(handlers::event_passthrough))
+        .route("/api/events/:id/questions/:question_id/vote", post(handlers::event_passthrough).delete(handlers::event_passthrough))
+        .route("/api/events/:id/questions/:question_id/moderation", put(handlers::event_passthrough))
+        .route("/api/events/:id/translations", get(handlers::event_passthrough))
+        .route("/api/events/:id/translations/:locale", put(handlers::event_passthrough).delete(handlers::event_passthrough))
+        .route("/api/events/:id/featured", put(handlers::event_passthrough))
+        .route("/api/events/:id/eligibility", get(handlers::event_passthrough).put(handlers::event_passthrough).delete(handlers::event_passthrough))
+        .route("/api/events/:id/bookmark", post(handlers::event_passthrough).delete(handlers::event_passthrough))
+        .route("/api/follows", get(handlers::event_passthrough))
+        .route("/api/organizers/:id/follow", post(handlers::event_passthrough).delete(handlers::event_passthrough))
+        .route("/api/categories/:slug/follow", post(handlers::event_passthrough).delete(handlers::event_passthrough))
+        // Kategorije
+        .route("/api/categories", get(handlers::event_passthrough).post(handlers::event_passthrough))
+        .route("/api/categories/:slug", put(handlers::event_passthrough).delete(handlers::event_passthrough))
+        // Kolekcije
+        .route("/api/collections", get(handlers::event_passthrough).post(handlers::event_passthrough))
+        .route("/api/collections/:slug", get(handlers::event_passthrough).put(handlers::event_passthrough).delete(handlers::event_passthrough))
+        // Sabloni dogadjaja
+        .route("/api/templates", get(handlers::event_passthrough).post(handlers::event_passthrough))
+        .route("/api/templates/:id", get(handlers::event_passthrough).put(handlers::event_passthrough).delete(handlers::event_passthrough))
+        .route("/api/templates/:id/prefill", get(handlers::event_passthrough))
+        // Serije dogadjaja
+        .route("/api/series", post(handlers::event_passthrough))
+        .route("/api/series/:id", get(handlers::event_passthrough).delete(handlers::event_passthrough))
+        .route("/api/series/:id/occurrences/:event_id", put(handlers::event_passthrough))
         // Registrations
         .route("/api/registrations", post(handlers::reg_create))
         .route("/api/registrations/my", get(handlers::reg_my))
+        .route("/api/registrations/eligibility", get(handlers::reg_passthrough))
+        .route("/api/registrations/calendar-token", get(handlers::reg_passthrough).post(handlers::reg_passthrough).delete(handlers::reg_passthrough))
+        .route("/api/calendar/:token", get(handlers::reg_passthrough))
         .route("/api/registrations/:id", delete(handlers::reg_cancel))
         .route("/api/registrations/:id/qr", get(handlers::reg_qr))
         .layer(cors)
@@ -13,7 +13,7 @@ pub async fn forward_request(
     method: &str,
     url: &str,
     headers: &HeaderMap,
-    body: Option<String>,
+    body: Option<Vec<u8>>,
 ) -> Response {
     let mut req = match method {
         "GET" => client.get(url),
@@ -39,6 +39,20 @@ pub async fn forward_request(
         }
     }
 
+    // jezik prikaza dogadjaja
+    if let Some(lang) = headers.get("accept-language") {
+        if let Ok(val) = lang.to_str() {
+            req = req.header("accept-language", val);
+        }
+    }
+
+    // verzija dogadjaja za optimisticko zakljucavanje
+    if let Some(im) = headers.get("if-match") {
+        if let Ok(val) = im.to_str() {
+            req = req.header("if-match", val);
+        }
+    }
+
     if let Some(b) = body {
         req = req.body(b);
     }
@@ -54,8 +68,33 @@ pub async fn forward_request(
                 .and_then(|v| v.to_str().ok())
                 .unwrap_or("application/json")
                 .to_string();
+            // slike se kesiraju - prosledjujemo cache-control ako ga servis posalje
+            let cache_control = resp.headers().get("cache-control").cloned();
+            let etag = resp.headers().get("etag").cloned();
+            // 429 kaze klijentu koliko da saceka
+            let retry_after = resp.headers().get("retry-after").cloned();
+            // CSV izvoz se preuzima kao fajl
+            let disposition = resp.headers().get("content-disposition").cloned();
+            let language = resp.headers().get("content-language").cloned();
             let body_bytes = resp.bytes().await.unwrap_or_default();
-            (status, [(axum::http::header::CONTENT_TYPE, content_type)], Body::from(body_bytes)).into_response()
+            let mut response =
+                (status, [(axum::http::header::CONTENT_TYPE, content_type)], Body::from(body_bytes)).into_response();
+            if let Some(cc) = cache_control.and_then(|v| v.to_str().ok().and_then(|v| v.parse().ok())) {
+                response.headers_mut().insert(axum::http::header::CACHE_CONTROL, cc);
+            }
+            if let Some(tag) = etag.and_then(|v| v.to_str().ok().and_then(|v| v.parse().ok())) {
+                response.headers_mut().insert(axum::http::header::ETAG, tag);
+            }
+            if let Some(secs) = retry_after.and_then(|v| v.to_str().ok().and_then(|v| v.parse().ok())) {
+                response.headers_mut().insert(axum::http::header::RETRY_AFTER, secs);
+            }
+            if let Some(d) = disposition.and_then(|v| v.to_str().ok().and_then(|v| v.parse().ok())) {
+                response.headers_mut().insert(axum::http::header::CONTENT_DISPOSITION, d);
+            }
+            if let Some(lang) = language.and_then(|v| v.to_str().ok().and_then(|v| v.parse().ok())) {
+                response.headers_mut().insert(axum::http::header::CONTENT_LANGUAGE, lang);
+            }
+            response
         }
         Err(_) => {
             (StatusCode::SERVICE_UNAVAILABLE, "Servis nije dostupan").into_response()
@@ -0,0 +1,7 @@
+-- profil korisnika - koristi se za uslove prijave na dogadjaje (godine, organizacija...)
+ALTER TABLE users ADD COLUMN IF NOT EXISTS full_name VARCHAR(255);
+ALTER TABLE users ADD COLUMN IF NOT EXISTS date_of_birth DATE;
+ALTER TABLE users ADD COLUMN IF NOT EXISTS organization VARCHAR(255);
+ALTER TABLE users ADD COLUMN IF NOT EXISTS phone VARCHAR(50);
+-- dodatni atributi po izboru organizatora, npr. {"membership_id": "A-123"}
+ALTER TABLE users ADD COLUMN IF NOT EXISTS attributes JSONB NOT NULL DEFAULT '{}';
@@ -1,13 +1,20 @@
 // Handleri za auth rute
 
-use axum::{extract::State, http::HeaderMap, http::StatusCode, Json};
+use std::collections::BTreeMap;
+
+use axum::{extract::{Path, Query, State}, http::HeaderMap, http::StatusCode, Json};
 use bcrypt::{hash, verify, DEFAULT_COST};
+use chrono::Datelike;
 use serde_json::json;
 use uuid::Uuid;
 
-use crate::models::{AuthResponse, LoginRequest, RegisterRequest, User};
+use crate::models::{
+    AuthResponse, LoginRequest, RegisterRequest, UpdateProfileRequest, User, UserIdsRequest, UserLookupQuery,
+    UserProfile, UserSummary,
+};
 use crate::AppState;
-use shared::auth::{create_token, extract_claims};
+use shared::auth::{check_internal_token, create_token, extract_claims};
+use shared::eligibility::{valid_attribute_key, PROFILE_FIELDS};
 use shared::models::ApiResponse;
 
 /// GET /health
@@ -19,7 +26,6 @@ pub async fn health_check() -> Json<serde_json::Value> {
 }
 
 /// me - returna podatke o logovanom korisniku
-
 pub async fn me(
     headers: HeaderMap,
     State(state): State<AppState>,
@@ -154,3 +160,176 @@ pub async fn login(
         }
     }
 }
+
+// ---- Profil ----
+
+const PROFILE_SQL: &str =
+    "SELECT id, email, full_name, date_of_birth, organization, phone, attributes FROM users WHERE id = $1";
+
+fn validate_profile(req: &UpdateProfileRequest) -> Result<(), String> {
+    let too_long = |v: &Option<String>, max: usize| v.as_deref().is_some_and(|s| s.trim().chars().count() > max);
+    if too_long(&req.full_name, 255) || too_long(&req.organization, 255) {
+        return Err("Ime i organizacija mogu imati najvise 255 karaktera".to_string());
+    }
+    if too_long(&req.phone, 50) {
+        return Err("Telefon moze imati najvise 50 karaktera".to_string());
+    }
+    if let Some(dob) = req.date_of_birth {
+        let today = chrono::Utc::now().date_naive();
+        if dob > today || dob.year() < 1900 {
+            return Err("Neispravan datum rodjenja".to_string());
+        }
+    }
+    if let Some(attributes) = &req.attributes {
+        if attributes.len() > 20 {
+            return Err("Najvise 20 dodatnih atributa".to_string());
+        }
+        if let Some(key) = attributes.keys().find(|k| !valid_attribute_key(k) || PROFILE_FIELDS.contains(&k.as_str())) {
+            return Err(format!("Neispravan naziv atributa: {} (mala slova, brojevi i _)", key));
+        }
+        if attributes.values().any(|v| v.chars().count() > 255) {
+            return Err("Vrednost atributa moze imati najvise 255 karaktera".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// GET /profile - profil ulogovanog korisnika
+pub async fn get_profile(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+) -> (StatusCode, Json<ApiResponse<UserProfile>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+
+    let result = sqlx::query_as::<_, UserProfile>(PROFILE_SQL)
+        .bind(Uuid::parse_str(&claims.sub).unwrap_or_default())
+        .fetch_optional(&state.db)
+        .await;
+
+    match result {
+        Ok(Some(profile)) => (StatusCode::OK, Json(ApiResponse::success("Profil", profile))),
+        Ok(None) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Korisnik ne postoji u bazi"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// PUT /profile - izmena profila
+pub async fn update_profile(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Json(req): Json<UpdateProfileRequest>,
+) -> (StatusCode, Json<ApiResponse<UserProfile>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    if let Err(msg) = validate_profile(&req) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(&msg)));
+    }
+
+    let attributes = req.attributes.as_ref().map(|a| {
+        sqlx::types::Json(
+            a.iter()
+                .map(|(k, v)| (k.clone(), v.trim().to_string()))
+                .filter(|(_, v)| !v.is_empty())
+                .collect::<BTreeMap<_, _>>(),
+        )
+    });
+
+    let result = sqlx::query_as::<_, UserProfile>(
+        "UPDATE users SET
+            full_name = CASE WHEN $2::text IS NULL THEN full_name ELSE NULLIF(TRIM($2), '') END,
+            date_of_birth = COALESCE($3, date_of_birth),
+            organization = CASE WHEN $4::text IS NULL THEN organization ELSE NULLIF(TRIM($4), '') END,
+            phone = CASE WHEN $5::text IS NULL THEN phone ELSE NULLIF(TRIM($5), '') END,
+            attributes = COALESCE($6, attributes)
+         WHERE id = $1
+         RETURNING id, email, full_name, date_of_birth, organization, phone, attributes",
+    )
+    .bind(Uuid::parse_str(&claims.sub).unwrap_or_default())
+    .bind(&req.full_name)
+    .bind(req.date_of_birth)
+    .bind(&req.organization)
+    .bind(&req.phone)
+    .bind(attributes)
+    .fetch_optional(&state.db)
+    .await;
+
+    match result {
+        Ok(Some(profile)) => (StatusCode::OK, Json(ApiResponse::success("Profil sacuvan", profile))),
+        Ok(None) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Korisnik ne postoji u bazi"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+// ---- Interno (drugi servisi) ----
+
+/// GET /internal/users/lookup?email= - korisnik po email-u (npr. dodavanje osoblja na dogadjaj)
+pub async fn lookup_user(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<UserLookupQuery>,
+) -> (StatusCode, Json<ApiResponse<UserSummary>>) {
+    if let Err((status, msg)) = check_internal_token(&headers, &state.internal_token) {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+
+    let result = sqlx::query_as::<_, UserSummary>(
+        "SELECT id, email, role FROM users WHERE LOWER(email) = LOWER($1)",
+    )
+    .bind(query.email.trim())
+    .fetch_optional(&state.db)
+    .await;
+
+    match result {
+        Ok(Some(user)) => (StatusCode::OK, Json(ApiResponse::success("Korisnik pronadjen", user))),
+        Ok(None) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Korisnik sa tim emailom ne postoji"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// POST /internal/users/batch - korisnici po id-evima (nepostojeci se preskacu)
+pub async fn users_batch(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<UserIdsRequest>,
+) -> (StatusCode, Json<ApiResponse<Vec<UserSummary>>>) {
+    if let Err((status, msg)) = check_internal_token(&headers, &state.internal_token) {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+
+    let result = sqlx::query_as::<_, UserSummary>("SELECT id, email, role FROM users WHERE id = ANY($1)")
+        .bind(&req.ids)
+        .fetch_all(&state.db)
+        .await;
+
+    match result {
+        Ok(users) => (StatusCode::OK, Json(ApiResponse::success("Korisnici", users))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// GET /internal/users/:id/profile - profil za proveru uslova prijave (registration-service)
+pub async fn internal_profile(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<UserProfile>>) {
+    if let Err((status, msg)) = check_internal_token(&headers, &state.internal_token) {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+
+    let result = sqlx::query_as::<_, UserProfile>(PROFILE_SQL)
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await;
+
+    match result {
+        Ok(Some(profile)) => (StatusCode::OK, Json(ApiResponse::success("Profil", profile))),
+        Ok(None) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Korisnik ne postoji"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
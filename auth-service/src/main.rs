@@ -11,6 +11,7 @@ use sqlx::PgPool;
 pub struct AppState {
     pub db: PgPool,
     pub jwt_secret: String,
+    pub internal_token: String,
 }
 
 ///Seeduj admina ako ne postoji 
@@ -50,6 +51,9 @@ async fn main() {
     let jwt_secret = std::env::var("JWT_SECRET")
         .expect("JWT_SECRET mora biti postavljen u .env");
 
+    let internal_token = std::env::var("INTERNAL_API_TOKEN")
+        .expect("INTERNAL_API_TOKEN mora biti postavljen u .env");
+
     let pool = db::create_pool(&database_url).await;
 
     // seeduj admina
@@ -58,6 +62,7 @@ async fn main() {
     let state = AppState {
         db: pool,
         jwt_secret,
+        internal_token,
     };
 
     let app = Router::new()
@@ -65,6 +70,10 @@ async fn main() {
         .route("/register", post(handlers::register))
         .route("/login", post(handlers::login))
         .route("/me", get(handlers::me))
+        .route("/profile", get(handlers::get_profile).put(handlers::update_profile))
+        .route("/internal/users/lookup", get(handlers::lookup_user))
+        .route("/internal/users/batch", post(handlers::users_batch))
+        .route("/internal/users/:id/profile", get(handlers::internal_profile))
         .with_state(state);
 
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3001")
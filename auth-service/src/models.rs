@@ -1,9 +1,12 @@
 // Modeli za auth servis
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
 use sqlx::FromRow;
 use uuid::Uuid;
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime};
 
 /// User kako izgleda u bazi
 #[derive(Debug, FromRow, Serialize)]
@@ -37,3 +40,45 @@ pub struct AuthResponse {
     pub email: String,
     pub role: String,
 }
+
+/// Javni podaci o korisniku za druge servise (bez lozinke)
+#[derive(Debug, FromRow, Serialize)]
+pub struct UserSummary {
+    pub id: Uuid,
+    pub email: String,
+    pub role: String,
+}
+
+/// ?email= za pretragu korisnika
+#[derive(Debug, Deserialize)]
+pub struct UserLookupQuery {
+    pub email: String,
+}
+
+/// Vise korisnika odjednom (npr. email-ovi za spisak prijavljenih)
+#[derive(Debug, Deserialize)]
+pub struct UserIdsRequest {
+    pub ids: Vec<Uuid>,
+}
+
+/// Profil korisnika (uslovi za prijavu na dogadjaje)
+#[derive(Debug, FromRow, Serialize)]
+pub struct UserProfile {
+    pub id: Uuid,
+    pub email: String,
+    pub full_name: Option<String>,
+    pub date_of_birth: Option<NaiveDate>,
+    pub organization: Option<String>,
+    pub phone: Option<String>,
+    pub attributes: Json<BTreeMap<String, String>>,
+}
+
+/// PUT /profile - menja se samo poslato; prazan string brise polje, attributes se zamenjuju cele
+#[derive(Debug, Deserialize)]
+pub struct UpdateProfileRequest {
+    pub full_name: Option<String>,
+    pub date_of_birth: Option<NaiveDate>,
+    pub organization: Option<String>,
+    pub phone: Option<String>,
+    pub attributes: Option<BTreeMap<String, String>>,
+}
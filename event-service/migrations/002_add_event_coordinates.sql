@@ -0,0 +1,6 @@
+
+ALTER TABLE events ADD COLUMN IF NOT EXISTS latitude DOUBLE PRECISION;
+ALTER TABLE events ADD COLUMN IF NOT EXISTS longitude DOUBLE PRECISION;
+
+-- index za bounding box pretragu (near=lat,lng)
+CREATE INDEX IF NOT EXISTS idx_events_coordinates ON events(latitude, longitude);
@@ -0,0 +1,32 @@
+
+-- serije dogadjaja (ponavljajuci dogadjaji po RRULE pravilu)
+CREATE TABLE IF NOT EXISTS event_series (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organizer_id UUID NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    description TEXT NOT NULL,
+    location VARCHAR(255) NOT NULL,
+    capacity INT NOT NULL DEFAULT 100,
+    category VARCHAR(100) NOT NULL DEFAULT 'Ostalo',
+    image_url TEXT,
+    latitude DOUBLE PRECISION,
+    longitude DOUBLE PRECISION,
+    dtstart TIMESTAMP NOT NULL,
+    rrule TEXT NOT NULL,
+    exdates TIMESTAMP[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE events ADD COLUMN IF NOT EXISTS series_id UUID REFERENCES event_series(id) ON DELETE SET NULL;
+ALTER TABLE events ADD COLUMN IF NOT EXISTS occurrence_date TIMESTAMP;
+ALTER TABLE events ADD COLUMN IF NOT EXISTS is_exception BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- jedan termin serije = jedan red; DEFERRABLE da bismo mogli da pomerimo sve termine odjednom
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_constraint WHERE conname = 'events_series_occurrence_key') THEN
+        ALTER TABLE events ADD CONSTRAINT events_series_occurrence_key
+            UNIQUE (series_id, occurrence_date) DEFERRABLE INITIALLY IMMEDIATE;
+    END IF;
+END $$;
@@ -0,0 +1,43 @@
+
+-- predavaci na dogadjaju
+CREATE TABLE IF NOT EXISTS speakers (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    bio TEXT NOT NULL DEFAULT '',
+    photo_url TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- sesije (predavanja) unutar dogadjaja
+CREATE TABLE IF NOT EXISTS sessions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    title VARCHAR(255) NOT NULL,
+    abstract TEXT NOT NULL DEFAULT '',
+    room VARCHAR(100),
+    track VARCHAR(100),
+    start_time TIMESTAMP NOT NULL,
+    end_time TIMESTAMP NOT NULL,
+    capacity INT,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CHECK (end_time > start_time)
+);
+
+CREATE TABLE IF NOT EXISTS session_speakers (
+    session_id UUID NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    speaker_id UUID NOT NULL REFERENCES speakers(id) ON DELETE CASCADE,
+    PRIMARY KEY (session_id, speaker_id)
+);
+
+-- ko od prijavljenih prisustvuje kojoj sesiji (kapacitet po sesiji)
+CREATE TABLE IF NOT EXISTS session_attendees (
+    session_id UUID NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (session_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_sessions_event ON sessions(event_id, start_time);
+CREATE INDEX IF NOT EXISTS idx_speakers_event ON speakers(event_id);
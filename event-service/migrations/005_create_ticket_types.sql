@@ -0,0 +1,21 @@
+
+-- tipovi karata po dogadjaju (Early Bird, Regular, VIP, Student...)
+CREATE TABLE IF NOT EXISTS ticket_types (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    price_cents BIGINT NOT NULL DEFAULT 0 CHECK (price_cents >= 0),
+    currency VARCHAR(3) NOT NULL DEFAULT 'RSD',
+    quota INT NOT NULL CHECK (quota > 0),
+    sales_start TIMESTAMP,
+    sales_end TIMESTAMP,
+    -- public = vidljiv u listi, hidden = samo preko direktnog linka
+    visibility VARCHAR(20) NOT NULL DEFAULT 'public',
+    sort_order INT NOT NULL DEFAULT 0,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    UNIQUE (event_id, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_ticket_types_event ON ticket_types(event_id);
@@ -0,0 +1,3 @@
+-- Slike se cuvaju u skladistu (disk/S3), u bazi je samo URL + thumbnail-ovi
+ALTER TABLE events ADD COLUMN IF NOT EXISTS image_thumbnails JSONB;
+ALTER TABLE events ADD COLUMN IF NOT EXISTS image_keys TEXT[] NOT NULL DEFAULT '{}';
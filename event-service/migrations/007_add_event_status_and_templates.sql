@@ -0,0 +1,23 @@
+-- draft dogadjaji (npr. klonirani) se ne vide javno dok se ne objave
+ALTER TABLE events ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'published';
+CREATE INDEX IF NOT EXISTS idx_events_status ON events(status);
+
+-- sacuvani sabloni organizatora za nove dogadjaje
+CREATE TABLE IF NOT EXISTS event_templates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organizer_id UUID NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    title VARCHAR(255),
+    description TEXT,
+    location VARCHAR(255),
+    capacity INT,
+    category VARCHAR(100),
+    image_url TEXT,
+    image_thumbnails JSONB,
+    image_keys TEXT[] NOT NULL DEFAULT '{}',
+    latitude DOUBLE PRECISION,
+    longitude DOUBLE PRECISION,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    UNIQUE (organizer_id, name)
+);
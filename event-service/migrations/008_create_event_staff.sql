@@ -0,0 +1,12 @@
+-- osoblje dogadjaja (pored organizatora): co_organizer, editor, checkin, viewer
+CREATE TABLE IF NOT EXISTS event_staff (
+    event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    email VARCHAR(255) NOT NULL,
+    role VARCHAR(20) NOT NULL,
+    added_by UUID NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (event_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_staff_user ON event_staff(user_id);
@@ -0,0 +1,14 @@
+-- vidljivost: public (u listi), unlisted (samo preko tajnog linka), private (link + pozivnica ili pristupni kod)
+ALTER TABLE events ADD COLUMN IF NOT EXISTS visibility VARCHAR(20) NOT NULL DEFAULT 'public';
+ALTER TABLE events ADD COLUMN IF NOT EXISTS slug VARCHAR(64) NOT NULL DEFAULT replace(gen_random_uuid()::text, '-', '');
+ALTER TABLE events ADD COLUMN IF NOT EXISTS access_code VARCHAR(64);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_events_slug ON events(slug);
+
+-- pozivnice za privatne dogadjaje (po email-u, korisnik ne mora jos da ima nalog)
+CREATE TABLE IF NOT EXISTS event_invitations (
+    event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    email VARCHAR(255) NOT NULL,
+    invited_by UUID NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (event_id, email)
+);
@@ -0,0 +1,15 @@
+-- verzija za optimisticko zakljucavanje (ETag / If-Match)
+ALTER TABLE events ADD COLUMN IF NOT EXISTS version INT NOT NULL DEFAULT 1;
+
+-- istorija izmena: stanje dogadjaja posle svake verzije i sta se promenilo
+CREATE TABLE IF NOT EXISTS event_revisions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    version INT NOT NULL,
+    -- NULL za pocetno stanje (zabelezeno pri prvoj izmeni)
+    author_id UUID,
+    changes JSONB,
+    snapshot JSONB NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    UNIQUE (event_id, version)
+);
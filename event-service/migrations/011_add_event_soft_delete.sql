@@ -0,0 +1,5 @@
+-- obrisani dogadjaji idu u kantu; trajno se brisu posle EVENT_TRASH_RETENTION_DAYS
+ALTER TABLE events ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMP;
+ALTER TABLE events ADD COLUMN IF NOT EXISTS deleted_by UUID;
+
+CREATE INDEX IF NOT EXISTS idx_events_deleted ON events(deleted_at) WHERE deleted_at IS NOT NULL;
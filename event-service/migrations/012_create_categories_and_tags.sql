@@ -0,0 +1,44 @@
+-- kategorije kojima upravlja admin (events.category cuva naziv kategorije)
+CREATE TABLE IF NOT EXISTS categories (
+    slug VARCHAR(100) PRIMARY KEY,
+    name VARCHAR(100) NOT NULL UNIQUE,
+    -- prevodi naziva: {"en": "Conference"}
+    translations JSONB NOT NULL DEFAULT '{}',
+    sort_order INT NOT NULL DEFAULT 0,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO categories (slug, name, translations, sort_order) VALUES
+    ('konferencija', 'Konferencija', '{"en": "Conference"}', 10),
+    ('radionica', 'Radionica', '{"en": "Workshop"}', 20),
+    ('koncert', 'Koncert', '{"en": "Concert"}', 30),
+    ('meetup', 'Meetup', '{"en": "Meetup"}', 40),
+    ('ostalo', 'Ostalo', '{"en": "Other"}', 1000)
+ON CONFLICT DO NOTHING;
+
+-- kategorije koje vec postoje u dogadjajima, serijama i sablonima, da stari podaci ostanu ispravni
+INSERT INTO categories (slug, name, sort_order)
+SELECT
+    COALESCE(NULLIF(trim(both '-' from regexp_replace(lower(category), '[^a-z0-9]+', '-', 'g')), ''), md5(category)),
+    category, 500
+FROM (
+    SELECT category FROM events
+    UNION SELECT category FROM event_series
+    UNION SELECT category FROM event_templates WHERE category IS NOT NULL
+) existing
+ON CONFLICT DO NOTHING;
+
+-- slobodni tagovi
+CREATE TABLE IF NOT EXISTS tags (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    slug VARCHAR(50) NOT NULL UNIQUE,
+    name VARCHAR(50) NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS event_tags (
+    event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    tag_id UUID NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (event_id, tag_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_tags_tag ON event_tags(tag_id);
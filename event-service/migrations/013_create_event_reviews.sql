@@ -0,0 +1,36 @@
+-- ocene i utisci posle dogadjaja (samo korisnici koji su bili na dogadjaju)
+CREATE TABLE IF NOT EXISTS event_reviews (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    -- ime za prikaz (deo email-a pre @)
+    author_name VARCHAR(100) NOT NULL,
+    rating SMALLINT NOT NULL CHECK (rating BETWEEN 1 AND 5),
+    body TEXT NOT NULL DEFAULT '',
+    -- odgovor organizatora
+    reply TEXT,
+    replied_by UUID,
+    replied_at TIMESTAMP,
+    -- moderacija: sakrivene se ne racunaju u prosek
+    hidden BOOLEAN NOT NULL DEFAULT FALSE,
+    hidden_reason TEXT,
+    report_count INT NOT NULL DEFAULT 0,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    UNIQUE (event_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_reviews_reported ON event_reviews(report_count) WHERE report_count > 0;
+
+-- jedna prijava po korisniku i utisku
+CREATE TABLE IF NOT EXISTS review_reports (
+    review_id UUID NOT NULL REFERENCES event_reviews(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    reason TEXT NOT NULL DEFAULT '',
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (review_id, user_id)
+);
+
+-- prosek i broj vidljivih ocena (za prikaz i sortiranje liste)
+ALTER TABLE events ADD COLUMN IF NOT EXISTS rating_avg DOUBLE PRECISION;
+ALTER TABLE events ADD COLUMN IF NOT EXISTS rating_count INT NOT NULL DEFAULT 0;
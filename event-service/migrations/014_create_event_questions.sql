@@ -0,0 +1,29 @@
+-- pitanja i komentari posetilaca; odgovori imaju parent_id (samo jedan nivo)
+CREATE TABLE IF NOT EXISTS event_questions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    parent_id UUID REFERENCES event_questions(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    author_name VARCHAR(100) NOT NULL,
+    body TEXT NOT NULL,
+    -- odgovor/komentar od organizatora ili osoblja
+    from_staff BOOLEAN NOT NULL DEFAULT FALSE,
+    -- kad je osoblje prvi put odgovorilo na pitanje
+    answered_at TIMESTAMP,
+    pinned BOOLEAN NOT NULL DEFAULT FALSE,
+    hidden BOOLEAN NOT NULL DEFAULT FALSE,
+    upvotes INT NOT NULL DEFAULT 0,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_questions_event ON event_questions(event_id, parent_id);
+-- za ogranicenje broja poruka po korisniku
+CREATE INDEX IF NOT EXISTS idx_event_questions_user ON event_questions(user_id, created_at);
+
+CREATE TABLE IF NOT EXISTS question_votes (
+    question_id UUID NOT NULL REFERENCES event_questions(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (question_id, user_id)
+);
@@ -0,0 +1,22 @@
+-- sacuvani dogadjaji korisnika
+CREATE TABLE IF NOT EXISTS event_bookmarks (
+    user_id UUID NOT NULL,
+    event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, event_id)
+);
+
+-- praceni organizatori i kategorije (filter following=true na listi)
+CREATE TABLE IF NOT EXISTS organizer_follows (
+    user_id UUID NOT NULL,
+    organizer_id UUID NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, organizer_id)
+);
+
+CREATE TABLE IF NOT EXISTS category_follows (
+    user_id UUID NOT NULL,
+    category_slug VARCHAR(100) NOT NULL REFERENCES categories(slug) ON UPDATE CASCADE ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, category_slug)
+);
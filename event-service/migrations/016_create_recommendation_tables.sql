@@ -0,0 +1,17 @@
+-- slicnost dogadjaja po zajednickim posetiocima (preracunava se svake noci)
+CREATE TABLE IF NOT EXISTS event_similarity (
+    event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    -- predstojeci dogadjaj koji se preporucuje onima koji su bili na event_id
+    similar_event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    score DOUBLE PRECISION NOT NULL,
+    PRIMARY KEY (event_id, similar_event_id)
+);
+
+-- popularnost predstojecih dogadjaja (preporuke za nove korisnike)
+CREATE TABLE IF NOT EXISTS event_popularity (
+    event_id UUID PRIMARY KEY REFERENCES events(id) ON DELETE CASCADE,
+    registrations INT NOT NULL DEFAULT 0,
+    bookmarks INT NOT NULL DEFAULT 0,
+    score DOUBLE PRECISION NOT NULL DEFAULT 0,
+    computed_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
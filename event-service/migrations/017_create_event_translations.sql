@@ -0,0 +1,11 @@
+-- prevodi naziva, opisa i lokacije; polja u events su na podrazumevanom jeziku (DEFAULT_LOCALE)
+CREATE TABLE IF NOT EXISTS event_translations (
+    event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    locale VARCHAR(10) NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    -- prazno = koristi se opis/lokacija sa podrazumevanog jezika
+    description TEXT,
+    location VARCHAR(255),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (event_id, locale)
+);
@@ -0,0 +1,3 @@
+-- opis je Markdown; uz njega se cuva ociscen HTML (stari redovi se renderuju pri pokretanju servisa)
+ALTER TABLE events ADD COLUMN IF NOT EXISTS description_html TEXT NOT NULL DEFAULT '';
+ALTER TABLE event_translations ADD COLUMN IF NOT EXISTS description_html TEXT;
@@ -0,0 +1,6 @@
+-- Uslovi za prijavu na dogadjaj (godine, domen emaila, polja profila, limit po seriji)
+CREATE TABLE IF NOT EXISTS event_eligibility (
+    event_id UUID PRIMARY KEY REFERENCES events(id) ON DELETE CASCADE,
+    rules JSONB NOT NULL,
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
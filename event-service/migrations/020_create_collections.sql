@@ -0,0 +1,25 @@
+-- izdvojeni dogadjaji (vise prioriteta = ranije na pocetnoj)
+ALTER TABLE events ADD COLUMN IF NOT EXISTS featured BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE events ADD COLUMN IF NOT EXISTS featured_priority INT NOT NULL DEFAULT 0;
+CREATE INDEX IF NOT EXISTS idx_events_featured ON events (featured_priority DESC, date_time) WHERE featured;
+
+-- kolekcije dogadjaja koje rucno slaze admin; vidljive samo u periodu active_from - active_until
+CREATE TABLE IF NOT EXISTS collections (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    slug VARCHAR(100) NOT NULL UNIQUE,
+    title VARCHAR(255) NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    -- NULL = bez ogranicenja sa te strane
+    active_from TIMESTAMP,
+    active_until TIMESTAMP,
+    created_by UUID NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS collection_events (
+    collection_id UUID NOT NULL REFERENCES collections(id) ON DELETE CASCADE,
+    event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    position INT NOT NULL,
+    PRIMARY KEY (collection_id, event_id)
+);
@@ -0,0 +1,11 @@
+-- evidencija objavljenih poruka za ogranicenje po korisniku; samo se dodaje,
+-- pa brisanje pitanja ne vraca pravo na nove poruke
+CREATE TABLE IF NOT EXISTS question_posts (
+    user_id UUID NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_question_posts_user ON question_posts(user_id, created_at);
+
+-- autor koji brise pitanje sa odgovorima ostavlja samo trag, odgovori osoblja ostaju
+ALTER TABLE event_questions ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMP;
@@ -0,0 +1,9 @@
+-- uslovi za prijavu koje dobijaju novi termini serije (kao i ostala polja sablona)
+ALTER TABLE event_series ADD COLUMN IF NOT EXISTS eligibility JSONB;
+
+-- do sada su novi termini preuzimali uslove od poslednjeg termina koji ih ima
+UPDATE event_series s SET eligibility = (
+    SELECT el.rules FROM event_eligibility el JOIN events e ON e.id = el.event_id
+    WHERE e.series_id = s.id ORDER BY e.date_time DESC LIMIT 1
+)
+WHERE s.eligibility IS NULL;
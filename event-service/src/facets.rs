@@ -0,0 +1,220 @@
+// Faceti za listu dogadjaja - brojevi po kategoriji, tagu, gradu, datumu, ceni i dostupnosti
+
+use std::collections::HashMap;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Utc, Weekday};
+use chrono_tz::Tz;
+use uuid::Uuid;
+
+use crate::ical::TIMEZONE;
+use crate::models::{
+    AvailabilityFacet, Event, EventFacets, EventRegistrationCountsResponse, FacetCount, PriceFacet,
+};
+use crate::AppState;
+
+/// Periodi za `when` i facet datuma
+pub const DATE_BUCKETS: &[&str] = &["today", "tomorrow", "weekend", "week", "month", "later", "past"];
+
+/// Grad u SQL-u, isto kao city_of (za filter city)
+pub const CITY_SQL: &str = "lower(trim(regexp_replace(location, '^.*,', '')))";
+
+/// Cena dogadjaja u SQL-u: najjeftinija javna karta, 0 ako nema tipova karata
+pub const PRICE_SQL: &str =
+    "COALESCE((SELECT MIN(t.price_cents) FROM ticket_types t WHERE t.event_id = e.id AND t.visibility = 'public'), 0)";
+
+/// Grad iz lokacije: poslednji deo posle zareza ("Spens, Novi Sad" -> "Novi Sad")
+pub fn city_of(location: &str) -> Option<&str> {
+    let city = location.rsplit(',').next().unwrap_or_default().trim();
+    (!city.is_empty()).then_some(city)
+}
+
+/// Sada po lokalnom vremenu (datumi u bazi su po Beogradu)
+pub fn local_now() -> NaiveDateTime {
+    let tz: Tz = TIMEZONE.parse().expect("validna vremenska zona");
+    Utc::now().with_timezone(&tz).naive_local()
+}
+
+/// Period [od, do) za `when`; None granica = bez granice. Nepoznat period -> None.
+pub fn date_range(bucket: &str, now: NaiveDateTime) -> Option<(Option<NaiveDateTime>, Option<NaiveDateTime>)> {
+    let today = now.date();
+    let midnight = |d: NaiveDate| d.and_hms_opt(0, 0, 0).expect("ponoc postoji");
+    let next_month = if today.month() == 12 {
+        NaiveDate::from_ymd_opt(today.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(today.year(), today.month() + 1, 1)
+    }
+    .expect("prvi u mesecu postoji");
+
+    let range = match bucket {
+        "today" => (Some(midnight(today)), Some(midnight(today + Duration::days(1)))),
+        "tomorrow" => (Some(midnight(today + Duration::days(1))), Some(midnight(today + Duration::days(2)))),
+        "weekend" => {
+            // subota i nedelja ove sedmice (u nedelju je to danasnji vikend)
+            let offset = Weekday::Sat.num_days_from_monday() as i64 - today.weekday().num_days_from_monday() as i64;
+            let saturday = today + Duration::days(offset);
+            (Some(midnight(saturday)), Some(midnight(saturday + Duration::days(2))))
+        }
+        "week" => (Some(now), Some(now + Duration::days(7))),
+        "month" => (Some(now), Some(midnight(next_month))),
+        "later" => (Some(midnight(next_month)), None),
+        "past" => (None, Some(now)),
+        // samo za filter (feed-ovi), nije facet
+        "upcoming" => (Some(now), None),
+        _ => return None,
+    };
+    Some(range)
+}
+
+/// Broj potvrdjenih prijava po dogadjaju iz registration-service (dogadjaji bez prijava nisu u mapi)
+pub async fn confirmed_counts(state: &AppState, event_ids: &[Uuid]) -> Result<HashMap<Uuid, i64>, String> {
+    if event_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let url = format!("{}/internal/events/counts", state.registration_service_url);
+    let resp = state
+        .http_client
+        .post(&url)
+        .json(&serde_json::json!({ "event_ids": event_ids }))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    let body = resp
+        .json::<EventRegistrationCountsResponse>()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    match body.data {
+        Some(list) if body.success => Ok(list.into_iter().map(|c| (c.event_id, c.confirmed)).collect()),
+        _ => Err("Registration servis vratio gresku".to_string()),
+    }
+}
+
+/// Da li dogadjaj ima slobodnih mesta
+pub fn has_seats(event: &Event, confirmed: &HashMap<Uuid, i64>) -> bool {
+    confirmed.get(&event.id).copied().unwrap_or(0) < event.capacity as i64
+}
+
+/// Vrednosti po broju dogadjaja (najvise prvo), pa po nazivu
+fn sorted(counts: HashMap<String, usize>) -> Vec<FacetCount> {
+    let mut list: Vec<FacetCount> = counts.into_iter().map(|(value, count)| FacetCount { value, count }).collect();
+    list.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    list
+}
+
+/// Faceti za vec filtrirane dogadjaje (sa tagovima). Bez `confirmed` nema dostupnosti.
+pub async fn build_facets(
+    state: &AppState,
+    events: &[Event],
+    confirmed: Option<&HashMap<Uuid, i64>>,
+) -> Result<EventFacets, sqlx::Error> {
+    let mut categories = HashMap::new();
+    let mut tags = HashMap::new();
+    // grad se broji bez obzira na velika/mala slova, a prikazuje kako je prvi put upisan
+    let mut cities: HashMap<String, (String, usize)> = HashMap::new();
+    for event in events {
+        *categories.entry(event.category.clone()).or_default() += 1;
+        for tag in &event.tags {
+            *tags.entry(tag.clone()).or_default() += 1;
+        }
+        if let Some(city) = city_of(&event.location) {
+            cities.entry(city.to_lowercase()).or_insert_with(|| (city.to_string(), 0)).1 += 1;
+        }
+    }
+
+    let now = local_now();
+    let dates = DATE_BUCKETS
+        .iter()
+        .filter_map(|bucket| {
+            let (from, to) = date_range(bucket, now)?;
+            let count = events
+                .iter()
+                .filter(|e| from.is_none_or(|f| e.date_time >= f) && to.is_none_or(|t| e.date_time < t))
+                .count();
+            Some(FacetCount { value: bucket.to_string(), count })
+        })
+        .collect();
+
+    let ids: Vec<Uuid> = events.iter().map(|e| e.id).collect();
+    let prices = sqlx::query_as::<_, (Uuid, i64)>(
+        "SELECT event_id, MIN(price_cents) FROM ticket_types
+         WHERE event_id = ANY($1) AND visibility = 'public' GROUP BY event_id",
+    )
+    .bind(&ids)
+    .fetch_all(&state.db)
+    .await?;
+    let prices: HashMap<Uuid, i64> = prices.into_iter().collect();
+
+    let mut price = PriceFacet::default();
+    for event in events {
+        let cents = prices.get(&event.id).copied().unwrap_or(0);
+        if cents == 0 {
+            price.free += 1;
+        } else {
+            price.paid += 1;
+        }
+        price.min_cents = Some(price.min_cents.map_or(cents, |m| m.min(cents)));
+        price.max_cents = Some(price.max_cents.map_or(cents, |m| m.max(cents)));
+    }
+
+    let availability = confirmed.map(|confirmed| {
+        let available = events.iter().filter(|e| has_seats(e, confirmed)).count();
+        AvailabilityFacet { available, sold_out: events.len() - available }
+    });
+
+    Ok(EventFacets {
+        categories: sorted(categories),
+        tags: sorted(tags),
+        cities: sorted(cities.into_values().collect()),
+        dates,
+        price,
+        availability,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn range(bucket: &str, now: &str) -> (Option<NaiveDateTime>, Option<NaiveDateTime>) {
+        date_range(bucket, at(now)).unwrap()
+    }
+
+    #[test]
+    fn city_is_last_part_of_location() {
+        assert_eq!(city_of("Spens, Novi Sad"), Some("Novi Sad"));
+        assert_eq!(city_of("Beograd"), Some("Beograd"));
+        assert_eq!(city_of("Dom omladine, "), None);
+    }
+
+    #[test]
+    fn day_buckets_start_at_midnight() {
+        // sreda
+        let now = "2027-03-10 15:30";
+        assert_eq!(range("today", now), (Some(at("2027-03-10 00:00")), Some(at("2027-03-11 00:00"))));
+        assert_eq!(range("tomorrow", now), (Some(at("2027-03-11 00:00")), Some(at("2027-03-12 00:00"))));
+        assert_eq!(range("week", now), (Some(at(now)), Some(at("2027-03-17 15:30"))));
+        assert_eq!(range("past", now), (None, Some(at(now))));
+        assert_eq!(date_range("sutra", at(now)), None);
+    }
+
+    #[test]
+    fn weekend_is_this_weeks_saturday_and_sunday() {
+        let weekend = (Some(at("2027-03-13 00:00")), Some(at("2027-03-15 00:00")));
+        assert_eq!(range("weekend", "2027-03-08 09:00"), weekend);
+        assert_eq!(range("weekend", "2027-03-13 20:00"), weekend);
+        // u nedelju je to danasnji vikend
+        assert_eq!(range("weekend", "2027-03-14 20:00"), weekend);
+    }
+
+    #[test]
+    fn month_and_later_split_at_next_month() {
+        assert_eq!(range("month", "2027-03-10 15:30"), (Some(at("2027-03-10 15:30")), Some(at("2027-04-01 00:00"))));
+        assert_eq!(range("later", "2027-03-10 15:30"), (Some(at("2027-04-01 00:00")), None));
+        // decembar prelazi u sledecu godinu
+        assert_eq!(range("later", "2027-12-20 10:00"), (Some(at("2028-01-01 00:00")), None));
+    }
+}
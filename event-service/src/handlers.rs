@@ -1,13 +1,57 @@
 // Handleri za event servis
 
-use axum::{extract::{Path, Query, State}, http::{HeaderMap, StatusCode}, Json};
+use axum::{
+    extract::{multipart::MultipartError, Multipart, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{Html, IntoResponse, Response},
+    Json,
+};
+use chrono::{Duration, NaiveDateTime};
+use serde::Serialize;
 use serde_json::json;
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder, Transaction};
 use uuid::Uuid;
 
-use crate::models::{CreateEventRequest, Event, EventQuery, UpdateEventRequest};
+use crate::models::{
+    AddStaffRequest, AdmissionRequest, Agenda, AgendaDay, AgendaTrack, AuthServiceResponse,
+    CalendarRequest, CapacityStrategy, CapacitySync, CapacitySyncResponse, Category,
+    CategoryQuery, CategoryRequest, CategoryWithCount, CloneEventRequest, Collection,
+    CollectionDetails, CollectionQuery, CollectionRequest, CollectionSummary,
+    CreateEventRequest, CreateQuestionRequest, CreateReviewRequest, CreateSeriesRequest,
+    CreateSessionRequest, CreateSpeakerRequest, CreateTicketTypeRequest, EditScope,
+    EligibilityRequest, Event, EventAccessSettings, EventEligibility, EventListWithFacets,
+    EventPrefill, EventQuery, EventRevision, EventSeries, EventSnapshot, EventSummary,
+    EventSummaryQuery, EventTemplate, EventTranslation, EventTranslations, FeaturedRequest,
+    FollowedCategory, FollowedOrganizer, Follows, ImportQuery, ImportReport, ImportRowError,
+    Invitation, InviteRequest, LangQuery, ModerateQuestionRequest, ModerateReviewRequest,
+    Question, QuestionThread, RatingSummary, RecommendationQuery, RecommendationStats,
+    RecommendedEvent, RegistrationCounts, RegistrationCountsResponse, RegistrationData,
+    RegistrationServiceResponse, ReportReviewRequest, RestoreReport, RestoreRevisionQuery,
+    RestoredRegistrations, RestoredRegistrationsResponse, Review, ReviewList,
+    ReviewReplyRequest, SeriesDetails, Session, SessionDetails, Speaker, StaffMember,
+    TemplateRequest, TicketType, TranslationRequest, TrashedEvent, UpdateEventRequest,
+    UpdateOccurrenceRequest, UpdateQuestionRequest, UpdateReviewRequest, UpdateSessionRequest,
+    UpdateSpeakerRequest, UpdateTicketTypeRequest, UserData,
+};
+use crate::facets::{build_facets, confirmed_counts, date_range, has_seats, local_now, CITY_SQL, PRICE_SQL};
+use crate::i18n::{apply_translations, normalize_locale, preferred_locales};
+use crate::ical::render_calendar;
+use crate::markdown::render as render_markdown;
+use crate::images::{content_type_for, delete_images, extension_for, release_images, store_image};
+use crate::import::{parse_csv, parse_ics};
+use crate::recommendations::{load_signals, precompute, rank};
+use crate::revisions::{etag, if_match_version, record_revision, restore_request};
+use crate::rrule::RRule;
+use crate::series::sync_series;
+use crate::syndication::{json_ld, remaining_seats, render_atom, render_landing, render_rss};
+use crate::taxonomy::{
+    attach_tags, category_names, match_category, normalize_tags, resolve_category, set_event_tags, slugify,
+};
 use crate::AppState;
-use shared::auth::{extract_claims, require_role};
+use shared::auth::{extract_claims, require_role, Claims, INTERNAL_TOKEN_HEADER};
+use shared::eligibility::{valid_attribute_key, EligibilityRules, PROFILE_FIELDS};
 use shared::models::ApiResponse;
+use shared::staff::{role_allows, EventAccess, Permission, OWNER, STAFF_ROLES};
 
 /// GET health
 pub async fn health_check() -> Json<serde_json::Value> {
@@ -17,28 +61,81 @@ pub async fn health_check() -> Json<serde_json::Value> {
     }))
 }
 
-/// create event
-pub async fn create_event(
-    headers: HeaderMap,
-    State(state): State<AppState>,
-    Json(req): Json<CreateEventRequest>,
-) -> Result<(StatusCode, Json<ApiResponse<Event>>), (StatusCode, String)> {
-    let claims = extract_claims(&headers, &state.jwt_secret)?;
-    require_role(&claims, "Admin")?;
+/// Dozvoljeni statusi dogadjaja
+const EVENT_STATUSES: &[&str] = &["draft", "published"];
 
-    let organizer_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+fn validate_status(status: Option<&str>) -> Result<(), &'static str> {
+    match status {
+        Some(s) if !EVENT_STATUSES.contains(&s) => Err("Status mora biti draft ili published"),
+        _ => Ok(()),
+    }
+}
+
+/// Gornja granica kapaciteta (stiti od greske u kucanju, npr. 1000000 umesto 100000)
+const MAX_CAPACITY: i32 = 100_000;
+
+fn validate_capacity(capacity: i32) -> Result<(), &'static str> {
+    if capacity <= 0 {
+        return Err("Kapacitet mora biti veci od 0");
+    }
+    if capacity > MAX_CAPACITY {
+        return Err("Kapacitet moze biti najvise 100000");
+    }
+    Ok(())
+}
+
+/// Vidljivost dogadjaja
+const EVENT_VISIBILITIES: &[&str] = &["public", "unlisted", "private"];
 
+fn validate_access(visibility: Option<&str>, access_code: Option<&str>) -> Result<(), &'static str> {
+    if visibility.is_some_and(|v| !EVENT_VISIBILITIES.contains(&v)) {
+        return Err("Vidljivost mora biti public, unlisted ili private");
+    }
+    if access_code.is_some_and(|c| c.trim().len() > 64) {
+        return Err("Pristupni kod moze imati najvise 64 karaktera");
+    }
+    Ok(())
+}
+
+/// Pravila za novi dogadjaj - ista za create i za uvoz
+fn validate_create(req: &CreateEventRequest) -> Result<(), &'static str> {
     // ne moze dogadjaj u proslosti
     if req.date_time < chrono::Utc::now().naive_utc() {
-        return Ok((
-            StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("Datum dogadjaja ne moze biti u proslosti")),
-        ));
+        return Err("Datum dogadjaja ne moze biti u proslosti");
+    }
+    if req.title.trim().is_empty() {
+        return Err("Naziv je obavezan");
+    }
+    validate_capacity(req.capacity)?;
+    validate_coordinates(req.latitude, req.longitude)?;
+    validate_status(req.status.as_deref())?;
+    validate_access(req.visibility.as_deref(), req.access_code.as_deref())?;
+    normalize_tags(&req.tags)?;
+    validate_image_url(req.image_url.as_deref())
+}
+
+/// Kategorija mora postojati u /categories; vraca naziv kako je upisan (prihvata i slug)
+async fn canonical_category(db: &PgPool, raw: &str) -> Result<String, (StatusCode, String)> {
+    match resolve_category(db, raw).await {
+        Ok(Some(name)) => Ok(name),
+        Ok(None) => Err((StatusCode::BAD_REQUEST, format!("Nepoznata kategorija: {}", raw.trim()))),
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, format!("Greska: {}", e))),
     }
+}
 
-    let result = sqlx::query_as::<_, Event>(
-        "INSERT INTO events (id, organizer_id, title, description, location, date_time, capacity, category, image_url)
-         VALUES (gen_random_uuid(), $1, $2, $3, $4, $5, $6, $7, $8)
+/// Upisuje dogadjaj sa tagovima (kategorija je vec proverena)
+async fn insert_event(
+    conn: &mut PgConnection,
+    organizer_id: Uuid,
+    req: &CreateEventRequest,
+) -> Result<Event, sqlx::Error> {
+    let mut event = sqlx::query_as::<_, Event>(
+        "INSERT INTO events (id, organizer_id, title, description, location, date_time, capacity, category, image_url, latitude, longitude, status,
+                             image_thumbnails, image_keys, visibility, access_code, description_html)
+         VALUES (gen_random_uuid(), $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, COALESCE($11, 'published'),
+                 (SELECT image_thumbnails FROM event_templates WHERE id = $12 AND image_url = $8),
+                 COALESCE((SELECT image_keys FROM event_templates WHERE id = $12 AND image_url = $8), '{}'),
+                 COALESCE($13, 'public'), NULLIF(TRIM($14), ''), $15)
          RETURNING *",
     )
     .bind(organizer_id)
@@ -49,10 +146,48 @@ pub async fn create_event(
     .bind(req.capacity)
     .bind(&req.category)
     .bind(&req.image_url)
-    .fetch_one(&state.db)
-    .await;
+    .bind(req.latitude)
+    .bind(req.longitude)
+    .bind(&req.status)
+    // slika iz sablona zadrzava thumbnail-ove (i deli fajlove sa sablonom)
+    .bind(req.template_id)
+    .bind(&req.visibility)
+    .bind(&req.access_code)
+    .bind(render_markdown(&req.description))
+    .fetch_one(&mut *conn)
+    .await?;
 
-    match result {
+    let tags = normalize_tags(&req.tags).unwrap_or_default();
+    event.tags = set_event_tags(conn, event.id, &tags).await?;
+    Ok(event)
+}
+
+/// create event
+pub async fn create_event(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Json(mut req): Json<CreateEventRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<Event>>), (StatusCode, String)> {
+    let claims = extract_claims(&headers, &state.jwt_secret)?;
+    require_role(&claims, "Admin")?;
+
+    let organizer_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+
+    if let Err(msg) = validate_create(&req) {
+        return Ok((StatusCode::BAD_REQUEST, Json(ApiResponse::error(msg))));
+    }
+    match canonical_category(&state.db, &req.category).await {
+        Ok(name) => req.category = name,
+        Err((status, msg)) => return Ok((status, Json(ApiResponse::error(&msg)))),
+    }
+
+    let result = async {
+        let mut tx = state.db.begin().await?;
+        let event = insert_event(&mut tx, organizer_id, &req).await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(event)
+    };
+    match result.await {
         Ok(event) => Ok((
             StatusCode::CREATED,
             Json(ApiResponse::success("Dogadjaj kreiran", event)),
@@ -64,125 +199,666 @@ pub async fn create_event(
     }
 }
 
-/// get events
+/// POST /events/import?format=csv|ics&dry_run=true - uvoz vise dogadjaja odjednom.
+/// Svaki red prolazi iste provere kao create; ispravni redovi se upisuju u jednoj transakciji.
+pub async fn import_events(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Query(params): Query<ImportQuery>,
+    body: String,
+) -> (StatusCode, Json<ApiResponse<ImportReport>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    if let Err((status, msg)) = require_role(&claims, "Admin") {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+    let organizer_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+
+    // format iz parametra, inace iz Content-Type
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let format = match params.format.as_deref() {
+        Some(f) => f.to_ascii_lowercase(),
+        None if content_type.starts_with("text/csv") => "csv".to_string(),
+        None if content_type.starts_with("text/calendar") => "ics".to_string(),
+        None => return (StatusCode::BAD_REQUEST, Json(ApiResponse::error("Zadajte format=csv ili format=ics"))),
+    };
+
+    let parsed = match format.as_str() {
+        "csv" => parse_csv(&body),
+        "ics" => parse_ics(&body, params.capacity.unwrap_or(DEFAULT_IMPORT_CAPACITY)),
+        other => return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(&format!("Nepodrzan format: {}", other)))),
+    };
+    let rows = match parsed {
+        Ok(rows) => rows,
+        Err(msg) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(&msg))),
+    };
+
+    let categories = match category_names(&state.db).await {
+        Ok(c) => c,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+
+    let total = rows.len();
+    let mut errors = Vec::new();
+    let mut valid = Vec::new();
+    for (row, parsed) in rows {
+        let checked = parsed
+            .and_then(|req| validate_create(&req).map(|_| req).map_err(str::to_string))
+            .and_then(|mut req| match match_category(&categories, &req.category) {
+                Some(name) => {
+                    req.category = name;
+                    Ok(req)
+                }
+                None => Err(format!("Nepoznata kategorija: {}", req.category)),
+            });
+        match checked {
+            Ok(req) => valid.push((row, req)),
+            Err(message) => errors.push(ImportRowError { row, message }),
+        }
+    }
+
+    let mut report = ImportReport {
+        dry_run: params.dry_run,
+        total,
+        valid: valid.len(),
+        imported: 0,
+        errors,
+        events: Vec::new(),
+    };
+    if params.dry_run || valid.is_empty() {
+        let message = if params.dry_run { "Provera zavrsena (nista nije upisano)" } else { "Nema ispravnih redova za uvoz" };
+        return (StatusCode::OK, Json(ApiResponse::success(message, report)));
+    }
+
+    let mut tx = match state.db.begin().await {
+        Ok(tx) => tx,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+    for (row, req) in &valid {
+        match insert_event(&mut tx, organizer_id, req).await {
+            Ok(event) => report.events.push(event),
+            // greska baze ponistava ceo uvoz
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiResponse::error(&format!("Red {}: {} - nista nije uvezeno", row, e))),
+                )
+            }
+        }
+    }
+    if let Err(e) = tx.commit().await {
+        return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e))));
+    }
+
+    report.imported = report.events.len();
+    (StatusCode::CREATED, Json(ApiResponse::success("Dogadjaji uvezeni", report)))
+}
+
+/// Kapacitet za .ics uvoz kad nije zadat (iCalendar ga nema)
+const DEFAULT_IMPORT_CAPACITY: i32 = 100;
+
+/// Radius Zemlje u km (za haversine)
+const EARTH_RADIUS_KM: f64 = 6371.0;
+/// Priblizno km po stepenu geografske sirine (za bounding box)
+const KM_PER_DEGREE: f64 = 111.045;
+const DEFAULT_RADIUS_KM: f64 = 10.0;
+const MAX_RADIUS_KM: f64 = 500.0;
+
+/// Parsira "lat,lng" iz near parametra
+fn parse_near(raw: &str) -> Option<(f64, f64)> {
+    let (lat, lng) = raw.split_once(',')?;
+    let lat = lat.trim().parse::<f64>().ok()?;
+    let lng = lng.trim().parse::<f64>().ok()?;
+    validate_coordinates(Some(lat), Some(lng)).ok()?;
+    Some((lat, lng))
+}
+
+/// Koordinate moraju biti zadate obe (ili nijedna) i u validnom opsegu
+fn validate_coordinates(lat: Option<f64>, lng: Option<f64>) -> Result<(), &'static str> {
+    match (lat, lng) {
+        (None, None) => Ok(()),
+        (Some(lat), Some(lng)) => {
+            if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lng) {
+                Err("Koordinate van opsega (lat -90..90, lng -180..180)")
+            } else {
+                Ok(())
+            }
+        }
+        _ => Err("Latitude i longitude moraju biti zadati zajedno"),
+    }
+}
+
+/// image_url mora biti pravi URL - slike se salju preko POST /events/:id/image, ne kao base64
+fn validate_image_url(url: Option<&str>) -> Result<(), &'static str> {
+    match url {
+        Some(u) if !(u.starts_with("http://") || u.starts_with("https://") || u.starts_with('/')) => {
+            Err("image_url mora biti http(s) URL - sliku posaljite preko POST /events/:id/image")
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Bounding box oko tacke: opseg latitude (ogranicen na polove) i jedan ili dva opsega longitude -
+/// preko ±180 se deli na dva, a kad krug obuhvata pol uzimaju se sve longitude
+fn bounding_box(lat: f64, lng: f64, radius_km: f64) -> ((f64, f64), Vec<(f64, f64)>) {
+    let lat_delta = radius_km / KM_PER_DEGREE;
+    let lat_range = ((lat - lat_delta).max(-90.0), (lat + lat_delta).min(90.0));
+    if lat - lat_delta <= -90.0 || lat + lat_delta >= 90.0 {
+        return (lat_range, vec![(-180.0, 180.0)]);
+    }
+
+    let lng_delta = radius_km / (KM_PER_DEGREE * lat.to_radians().cos());
+    let (west, east) = (lng - lng_delta, lng + lng_delta);
+    let lng_ranges = if lng_delta >= 180.0 {
+        vec![(-180.0, 180.0)]
+    } else if west < -180.0 {
+        vec![(west + 360.0, 180.0), (-180.0, east)]
+    } else if east > 180.0 {
+        vec![(west, 180.0), (-180.0, east - 360.0)]
+    } else {
+        vec![(west, east)]
+    };
+    (lat_range, lng_ranges)
+}
+
+/// Pravi SELECT za listu dogadjaja na osnovu filtera.
+/// Sa `near` racuna udaljenost u bazi: prvo bounding box (koristi index), pa haversine.
+/// Nacrti se vide samo sa drafts=true: Admin vidi sve, ostali samo svoje.
+/// `feed_limit` je za Atom/RSS: samo predstojeci, najvise toliko.
+fn build_list_query(
+    params: &EventQuery,
+    viewer: Option<&Claims>,
+    feed_limit: Option<i64>,
+) -> Result<QueryBuilder<'static, Postgres>, String> {
+    let near = match params.near.as_deref() {
+        Some(raw) => Some(parse_near(raw).ok_or("near mora biti u formatu lat,lng")?),
+        None => None,
+    };
+    let radius_km = params.radius_km.unwrap_or(DEFAULT_RADIUS_KM);
+    if near.is_some() && !(radius_km > 0.0 && radius_km <= MAX_RADIUS_KM) {
+        return Err(format!("radius_km mora biti izmedju 0 i {}", MAX_RADIUS_KM));
+    }
+
+    let sort_by_distance = match params.sort.as_deref() {
+        None => near.is_some(),
+        Some("date") => false,
+        Some("distance") if near.is_some() => true,
+        Some("distance") => return Err("Sortiranje po udaljenosti zahteva near parametar".to_string()),
+        Some("rating") | Some("featured") => false,
+        Some(other) => return Err(format!("Nepoznato sortiranje: {} (date, distance, rating ili featured)", other)),
+    };
+
+    let mut qb = match near {
+        Some((lat, lng)) => {
+            let ((south, north), lng_ranges) = bounding_box(lat, lng, radius_km);
+
+            let mut qb = QueryBuilder::new("SELECT * FROM (SELECT events.*, ");
+            qb.push_bind(EARTH_RADIUS_KM)
+                .push(" * 2 * ASIN(SQRT(LEAST(1, POWER(SIN(RADIANS(latitude - ")
+                .push_bind(lat)
+                .push(") / 2), 2) + COS(RADIANS(")
+                .push_bind(lat)
+                .push(")) * COS(RADIANS(latitude)) * POWER(SIN(RADIANS(longitude - ")
+                .push_bind(lng)
+                .push(") / 2), 2)))) AS distance_km FROM events WHERE deleted_at IS NULL AND latitude BETWEEN ")
+                .push_bind(south)
+                .push(" AND ")
+                .push_bind(north)
+                .push(" AND (");
+            for (i, (west, east)) in lng_ranges.into_iter().enumerate() {
+                if i > 0 {
+                    qb.push(" OR ");
+                }
+                qb.push("longitude BETWEEN ").push_bind(west).push(" AND ").push_bind(east);
+            }
+            qb.push(")) e WHERE distance_km <= ").push_bind(radius_km);
+            qb
+        }
+        None => QueryBuilder::new("SELECT * FROM events e WHERE deleted_at IS NULL"),
+    };
+
+    // javno se vide samo objavljeni public dogadjaji; ulogovani i privatne na koje su pozvani,
+    // a sa drafts=true i sve koje organizuju (ili su u osoblju)
+    match viewer {
+        Some(claims) if params.drafts && claims.role == "Admin" => {}
+        Some(claims) => {
+            qb.push(" AND ((status = 'published' AND (visibility = 'public' OR (visibility = 'private' AND id IN (SELECT event_id FROM event_invitations WHERE email = ")
+                .push_bind(claims.email.to_lowercase())
+                .push("))))");
+            if params.drafts {
+                let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+                qb.push(" OR organizer_id = ")
+                    .push_bind(user_id)
+                    .push(" OR id IN (SELECT event_id FROM event_staff WHERE user_id = ")
+                    .push_bind(user_id)
+                    .push(")");
+            }
+            qb.push(")");
+        }
+        None => {
+            qb.push(" AND status = 'published' AND visibility = 'public'");
+        }
+    }
+
+    if params.featured {
+        qb.push(" AND featured");
+    }
+    if let Some(cat) = &params.category {
+        // slug ili naziv kategorije
+        qb.push(" AND category = COALESCE((SELECT name FROM categories WHERE slug = ")
+            .push_bind(cat.clone())
+            .push("), ")
+            .push_bind(cat.clone())
+            .push(")");
+    }
+    if let Some(tag) = &params.tag {
+        let slugs: Vec<String> = tag.split(',').map(slugify).filter(|s| !s.is_empty()).collect();
+        if !slugs.is_empty() {
+            qb.push(" AND id IN (SELECT et.event_id FROM event_tags et JOIN tags t ON t.id = et.tag_id WHERE t.slug = ANY(")
+                .push_bind(slugs)
+                .push("))");
+        }
+    }
+    if let Some(city) = &params.city {
+        qb.push(format!(" AND {} = lower(trim(", CITY_SQL)).push_bind(city.clone()).push("))");
+    }
+    if let Some(when) = &params.when {
+        let (from, to) = date_range(when, local_now())
+            .ok_or("when mora biti today, tomorrow, weekend, week, month, later, past ili upcoming")?;
+        if let Some(from) = from {
+            qb.push(" AND date_time >= ").push_bind(from);
+        }
+        if let Some(to) = to {
+            qb.push(" AND date_time < ").push_bind(to);
+        }
+    }
+    if feed_limit.is_some() {
+        qb.push(" AND date_time >= ").push_bind(local_now());
+    }
+    if params.following {
+        let user_id = viewer
+            .and_then(|c| Uuid::parse_str(&c.sub).ok())
+            .ok_or("following=true zahteva prijavu")?;
+        qb.push(" AND date_time >= ")
+            .push_bind(local_now())
+            .push(" AND (organizer_id IN (SELECT organizer_id FROM organizer_follows WHERE user_id = ")
+            .push_bind(user_id)
+            .push(") OR category IN (SELECT c.name FROM category_follows f JOIN categories c ON c.slug = f.category_slug WHERE f.user_id = ")
+            .push_bind(user_id)
+            .push("))");
+    }
+    match params.price.as_deref() {
+        None => {}
+        Some("free") => {
+            qb.push(format!(" AND {} = 0", PRICE_SQL));
+        }
+        Some("paid") => {
+            qb.push(format!(" AND {} > 0", PRICE_SQL));
+        }
+        Some(_) => return Err("price mora biti free ili paid".to_string()),
+    }
+    if let Some(search) = &params.search {
+        // trazi i po prevodima, bez obzira na jezik prikaza
+        let pattern = format!("%{}%", search);
+        qb.push(" AND (title ILIKE ")
+            .push_bind(pattern.clone())
+            .push(" OR description ILIKE ")
+            .push_bind(pattern.clone())
+            .push(" OR id IN (SELECT event_id FROM event_translations WHERE title ILIKE ")
+            .push_bind(pattern.clone())
+            .push(" OR description ILIKE ")
+            .push_bind(pattern)
+            .push("))");
+    }
+
+    if sort_by_distance {
+        qb.push(" ORDER BY distance_km, date_time");
+    } else if params.sort.as_deref() == Some("rating") {
+        // neocenjeni na kraj, pa oni sa vise ocena
+        qb.push(" ORDER BY rating_avg DESC NULLS LAST, rating_count DESC, date_time");
+    } else if matches!((params.sort.as_deref(), params.featured), (Some("featured"), _) | (None, true)) {
+        // izdvojeni prvi, po prioritetu
+        qb.push(" ORDER BY featured DESC, featured_priority DESC, date_time");
+    } else {
+        qb.push(" ORDER BY date_time");
+    }
+    if let Some(limit) = feed_limit {
+        qb.push(" LIMIT ").push_bind(limit);
+    }
+
+    Ok(qb)
+}
+
+/// get events; sa facets=true vraca {events, facets} umesto same liste
 pub async fn list_events(
+    headers: HeaderMap,
     Query(params): Query<EventQuery>,
     State(state): State<AppState>,
-) -> (StatusCode, Json<ApiResponse<Vec<Event>>>) {
-    // u zavisnosti od filtera  
-    let result = match (&params.category, &params.search) {
-        (Some(cat), Some(search)) => {
-            let pattern = format!("%{}%", search);
-            sqlx::query_as::<_, Event>(
-                "SELECT * FROM events WHERE category = $1 AND (title ILIKE $2 OR description ILIKE $2) ORDER BY date_time",
-            )
-            .bind(cat)
-            .bind(&pattern)
-            .fetch_all(&state.db)
-            .await
-        }
-        (Some(cat), None) => {
-            sqlx::query_as::<_, Event>(
-                "SELECT * FROM events WHERE category = $1 ORDER BY date_time",
-            )
-            .bind(cat)
-            .fetch_all(&state.db)
-            .await
+) -> Response {
+    // JWT nije obavezan, osim za drafts=true i following=true
+    let viewer = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => Some(c),
+        Err((status, msg)) if params.drafts || params.following => {
+            return (status, Json(ApiResponse::<()>::error(&msg))).into_response()
         }
-        (None, Some(search)) => {
-            let pattern = format!("%{}%", search);
-            sqlx::query_as::<_, Event>(
-                "SELECT * FROM events WHERE title ILIKE $1 OR description ILIKE $1 ORDER BY date_time",
+        Err(_) => None,
+    };
+
+    let mut qb = match build_list_query(&params, viewer.as_ref(), None) {
+        Ok(qb) => qb,
+        Err(msg) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::<()>::error(&msg))).into_response(),
+    };
+
+    let locales = preferred_locales(&headers, params.lang.as_deref());
+    let result = match qb.build_query_as::<Event>().fetch_all(&state.db).await {
+        Ok(mut events) => match attach_tags(&state.db, &mut events).await {
+            Ok(()) => apply_translations(&state.db, &mut events, &locales, &state.default_locale)
+                .await
+                .map(|_| events),
+            Err(e) => Err(e),
+        },
+        Err(e) => Err(e),
+    };
+    let mut events = match result {
+        Ok(events) => events,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<()>::error(&format!("Greska: {}", e))),
             )
-            .bind(&pattern)
-            .fetch_all(&state.db)
-            .await
+                .into_response()
         }
-        (None, None) => {
-            sqlx::query_as::<_, Event>("SELECT * FROM events ORDER BY date_time")
-                .fetch_all(&state.db)
-                .await
+    };
+
+    // dostupnost zavisi od prijava, pa se racuna posle upita
+    let confirmed = if params.available || params.facets {
+        let ids: Vec<Uuid> = events.iter().map(|e| e.id).collect();
+        match confirmed_counts(&state, &ids).await {
+            Ok(c) => Some(c),
+            Err(e) if params.available => {
+                tracing::warn!("Broj prijava nije dostupan: {}", e);
+                return (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    Json(ApiResponse::<()>::error("Registration servis nije dostupan - ne mogu da proverim slobodna mesta")),
+                )
+                    .into_response();
+            }
+            Err(e) => {
+                tracing::warn!("Broj prijava nije dostupan, faceti bez dostupnosti: {}", e);
+                None
+            }
         }
+    } else {
+        None
     };
+    if let (true, Some(confirmed)) = (params.available, &confirmed) {
+        events.retain(|e| has_seats(e, confirmed));
+    }
 
-    match result {
-        Ok(events) => (
+    if !params.facets {
+        return (StatusCode::OK, Json(ApiResponse::success("Lista dogadjaja", events))).into_response();
+    }
+    match build_facets(&state, &events, confirmed.as_ref()).await {
+        Ok(facets) => (
             StatusCode::OK,
-            Json(ApiResponse::success("Lista dogadjaja", events)),
-        ),
+            Json(ApiResponse::success("Lista dogadjaja", EventListWithFacets { events, facets })),
+        )
+            .into_response(),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error(&format!("Greska: {}", e))),
-        ),
+            Json(ApiResponse::<()>::error(&format!("Greska: {}", e))),
+        )
+            .into_response(),
+    }
+}
+
+async fn is_invited(db: &PgPool, event_id: Uuid, email: &str) -> bool {
+    sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM event_invitations WHERE event_id = $1 AND email = $2)")
+        .bind(event_id)
+        .bind(email.to_lowercase())
+        .fetch_one(db)
+        .await
+        .unwrap_or(false)
+}
+
+/// Da li korisnik iz JWT-a (ako ga ima) vidi dogadjaj preko id-a.
+/// Osoblje vidi sve; ostali objavljene public, a privatne samo ako su pozvani.
+/// `via_link` - otvoren preko tajnog linka, pa su unlisted i private vidljivi svima.
+async fn can_see(headers: &HeaderMap, state: &AppState, event: &Event, via_link: bool) -> bool {
+    let claims = extract_claims(headers, &state.jwt_secret).ok();
+    if let Some(claims) = &claims {
+        let staff = event_role(&state.db, event, claims).await;
+        if staff.is_some_and(|role| role_allows(&role, Permission::View)) {
+            return true;
+        }
+    }
+
+    if event.status == "draft" {
+        return false;
     }
+    match event.visibility.as_str() {
+        "public" => true,
+        _ if via_link => true,
+        "private" => match &claims {
+            Some(c) => is_invited(&state.db, event.id, &c.email).await,
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+/// Odgovor sa .ics sadrzajem
+fn ics_response(body: String, filename: &str) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, format!("inline; filename=\"{}\"", filename)),
+        ],
+        body,
+    )
+        .into_response()
 }
 
-/// get event by id
+/// Tagovi i prevod na trazeni jezik za jedan dogadjaj (get po id-u i po linku)
+async fn load_event_details(
+    state: &AppState,
+    headers: &HeaderMap,
+    lang: Option<&str>,
+    event: &mut Event,
+) -> Result<(), sqlx::Error> {
+    let events = std::slice::from_mut(event);
+    attach_tags(&state.db, events).await?;
+    let locales = preferred_locales(headers, lang);
+    apply_translations(&state.db, events, &locales, &state.default_locale).await
+}
+
+/// get event by id; `/events/:id.ics` vraca dogadjaj kao iCalendar
 pub async fn get_event(
+    headers: HeaderMap,
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
-) -> (StatusCode, Json<ApiResponse<Event>>) {
-    let result = sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id = $1")
+    Path(raw_id): Path<String>,
+    Query(query): Query<LangQuery>,
+) -> Response {
+    let (id, as_ics) = match raw_id.strip_suffix(".ics") {
+        Some(id) => (id, true),
+        None => (raw_id.as_str(), false),
+    };
+    let Ok(id) = Uuid::parse_str(id) else {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::<()>::error("Neispravan ID dogadjaja"))).into_response();
+    };
+
+    let result = sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id = $1 AND deleted_at IS NULL")
         .bind(id)
         .fetch_optional(&state.db)
         .await;
 
+    // draft i dogadjaj bez pristupa - kao da ne postoji
+    let result = match result {
+        Ok(Some(event)) if !can_see(&headers, &state, &event, false).await => Ok(None),
+        Ok(Some(mut event)) => load_event_details(&state, &headers, query.lang.as_deref(), &mut event)
+            .await
+            .map(|_| Some(event)),
+        other => other,
+    };
+
     match result {
-        Ok(Some(event)) => (
-            StatusCode::OK,
-            Json(ApiResponse::success("Dogadjaj pronadjen", event)),
-        ),
+        Ok(Some(event)) if as_ics => {
+            let body = render_calendar(std::slice::from_ref(&event), None, &state.public_app_url);
+            ics_response(body, &format!("{}.ics", event.id))
+        }
+        Ok(Some(event)) => {
+            let locale = event.locale.clone().unwrap_or_default();
+            let mut resp = event_response(StatusCode::OK, "Dogadjaj pronadjen", event);
+            if let Ok(value) = locale.parse() {
+                resp.headers_mut().insert(header::CONTENT_LANGUAGE, value);
+            }
+            resp
+        }
         Ok(None) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("Dogadjaj ne postoji")),
-        ),
+            Json(ApiResponse::<()>::error("Dogadjaj ne postoji")),
+        )
+            .into_response(),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error(&format!("Greska: {}", e))),
-        ),
+            Json(ApiResponse::<()>::error(&format!("Greska: {}", e))),
+        )
+            .into_response(),
     }
 }
 
-/// update event
-pub async fn update_event(
+/// GET /events/link/:slug - dogadjaj preko tajnog linka (radi i za unlisted i private)
+pub async fn get_event_by_slug(
     headers: HeaderMap,
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
-    Json(req): Json<UpdateEventRequest>,
+    Path(slug): Path<String>,
+    Query(query): Query<LangQuery>,
 ) -> (StatusCode, Json<ApiResponse<Event>>) {
-    let claims = match extract_claims(&headers, &state.jwt_secret) {
-        Ok(c) => c,
-        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
-    };
+    let result = sqlx::query_as::<_, Event>("SELECT * FROM events WHERE slug = $1 AND deleted_at IS NULL")
+        .bind(&slug)
+        .fetch_optional(&state.db)
+        .await;
 
-    let event = match sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id = $1").bind(id).fetch_optional(&state.db).await {
-        Ok(Some(e)) => e,
-        Ok(None) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Dogadjaj ne postoji"))),
-        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    match result {
+        Ok(Some(mut event)) if can_see(&headers, &state, &event, true).await => {
+            if let Err(e) = load_event_details(&state, &headers, query.lang.as_deref(), &mut event).await {
+                return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e))));
+            }
+            (StatusCode::OK, Json(ApiResponse::success("Dogadjaj pronadjen", event)))
+        }
+        Ok(_) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Dogadjaj ne postoji"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// GET /events/feed.ics - javni kalendar, isti filteri kao lista dogadjaja
+pub async fn events_feed(
+    Query(params): Query<EventQuery>,
+    State(state): State<AppState>,
+) -> Response {
+    let mut qb = match build_list_query(&params, None, None) {
+        Ok(qb) => qb,
+        Err(msg) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::<()>::error(&msg))).into_response(),
     };
 
-    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
-    if event.organizer_id != user_id && claims.role != "Admin" {
-        return (StatusCode::FORBIDDEN, Json(ApiResponse::error("Nemate dozvolu da menjate ovaj dogadjaj")));
+    match qb.build_query_as::<Event>().fetch_all(&state.db).await {
+        Ok(events) => {
+            let name = match &params.category {
+                Some(cat) => format!("Dogadjaji - {}", cat),
+                None => "Dogadjaji".to_string(),
+            };
+            ics_response(render_calendar(&events, Some(&name), &state.public_app_url), "events.ics")
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(&format!("Greska: {}", e))),
+        )
+            .into_response(),
+    }
+}
+
+/// Internal: kalendar sa zadatim dogadjajima (registration-service pravi licni feed korisnika)
+pub async fn internal_calendar(
+    State(state): State<AppState>,
+    Json(req): Json<CalendarRequest>,
+) -> Response {
+    let result = sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id = ANY($1) AND deleted_at IS NULL ORDER BY date_time")
+        .bind(&req.ids)
+        .fetch_all(&state.db)
+        .await;
+
+    match result {
+        Ok(events) => ics_response(render_calendar(&events, Some(&req.name), &state.public_app_url), "calendar.ics"),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(&format!("Greska: {}", e))),
+        )
+            .into_response(),
     }
+}
 
+/// Provera polja za izmenu (datum i koordinate)
+fn validate_update(req: &UpdateEventRequest) -> Result<(), &'static str> {
     if let Some(dt) = req.date_time {
         if dt < chrono::Utc::now().naive_utc() {
-            return (StatusCode::BAD_REQUEST, Json(ApiResponse::error("Datum ne moze biti u proslosti")));
+            return Err("Datum ne moze biti u proslosti");
         }
     }
+    if let Some(capacity) = req.capacity {
+        validate_capacity(capacity)?;
+    }
+    validate_coordinates(req.latitude, req.longitude)?;
+    validate_status(req.status.as_deref())?;
+    validate_access(req.visibility.as_deref(), req.access_code.as_deref())?;
+    if let Some(tags) = &req.tags {
+        normalize_tags(tags)?;
+    }
+    validate_image_url(req.image_url.as_deref())
+}
 
-    let result = sqlx::query_as::<_, Event>(
+/// Proverava kategoriju iz izmene (ako je poslata) i menja je u naziv iz /categories
+async fn resolve_update_category(db: &PgPool, req: &mut UpdateEventRequest) -> Result<(), (StatusCode, String)> {
+    if let Some(raw) = &req.category {
+        req.category = Some(canonical_category(db, raw).await?);
+    }
+    Ok(())
+}
+
+/// Primenjuje izmene na jedan dogadjaj (menja se samo ono sto je poslato) i podize verziju.
+/// Ako je dogadjaj termin serije, postaje izuzetak - izmene serije ga vise ne diraju.
+/// Sa `expected_version` menja samo ako je verzija ista, inace vraca None.
+pub(crate) async fn apply_event_update<'e>(
+    db: impl sqlx::PgExecutor<'e>,
+    id: Uuid,
+    req: &UpdateEventRequest,
+    expected_version: Option<i32>,
+) -> Result<Option<Event>, sqlx::Error> {
+    sqlx::query_as::<_, Event>(
         "UPDATE events SET
             title = COALESCE($2, title),
             description = COALESCE($3, description),
+            description_html = COALESCE($15, description_html),
             location = COALESCE($4, location),
             date_time = COALESCE($5, date_time),
             capacity = COALESCE($6, capacity),
             category = COALESCE($7, category),
             image_url = COALESCE($8, image_url),
+            image_thumbnails = CASE WHEN $8 IS NULL OR $8 = image_url THEN image_thumbnails END,
+            image_keys = CASE WHEN $8 IS NULL OR $8 = image_url THEN image_keys ELSE '{}' END,
+            latitude = COALESCE($9, latitude),
+            longitude = COALESCE($10, longitude),
+            status = COALESCE($11, status),
+            visibility = COALESCE($12, visibility),
+            access_code = CASE WHEN $13::text IS NULL THEN access_code ELSE NULLIF(TRIM($13), '') END,
+            is_exception = (series_id IS NOT NULL),
+            version = version + 1,
             updated_at = NOW()
-         WHERE id = $1 RETURNING *",
+         WHERE id = $1 AND ($14::int IS NULL OR version = $14) RETURNING *",
     )
     .bind(id)
     .bind(&req.title)
@@ -192,52 +868,5365 @@ pub async fn update_event(
     .bind(req.capacity)
     .bind(&req.category)
     .bind(&req.image_url)
-    .fetch_one(&state.db)
-    .await;
+    .bind(req.latitude)
+    .bind(req.longitude)
+    .bind(&req.status)
+    .bind(&req.visibility)
+    .bind(&req.access_code)
+    .bind(expected_version)
+    .bind(req.description.as_deref().map(render_markdown))
+    .fetch_optional(db)
+    .await
+}
 
-    match result {
-        Ok(updated) => (StatusCode::OK, Json(ApiResponse::success("Dogadjaj azuriran", updated))),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+/// Izmena + revizija (i tagovi, ako su poslati) u istoj transakciji.
+/// None = u medjuvremenu ga je neko drugi izmenio.
+pub(crate) async fn update_with_revision(
+    db: &PgPool,
+    event: &Event,
+    req: &UpdateEventRequest,
+    expected_version: Option<i32>,
+    author_id: Uuid,
+) -> Result<Option<Event>, sqlx::Error> {
+    let mut tx = db.begin().await?;
+    let Some(mut updated) = apply_event_update(&mut *tx, event.id, req, expected_version).await? else {
+        return Ok(None);
+    };
+    record_revision(&mut tx, event, &updated, author_id).await?;
+    if let Some(tags) = &req.tags {
+        let tags = normalize_tags(tags).unwrap_or_default();
+        updated.tags = set_event_tags(&mut tx, updated.id, &tags).await?;
+    }
+    tx.commit().await?;
+
+    if req.tags.is_none() {
+        attach_tags(db, std::slice::from_mut(&mut updated)).await?;
     }
+    Ok(Some(updated))
 }
 
-/// delete event
-pub async fn delete_event(
-    headers: HeaderMap,
-    State(state): State<AppState>,
-    Path(id): Path<Uuid>,
-) -> (StatusCode, Json<ApiResponse<String>>) {
-    let claims = match extract_claims(&headers, &state.jwt_secret) {
-        Ok(c) => c,
-        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+/// Smanjenje kapaciteta ispod broja potvrdjenih prijava prolazi samo uz strategiju (waitlist/cancel)
+async fn check_capacity_change(
+    state: &AppState,
+    event: &Event,
+    capacity: Option<i32>,
+    strategy: Option<CapacityStrategy>,
+) -> Result<(), (StatusCode, String)> {
+    let Some(capacity) = capacity.filter(|c| *c < event.capacity) else {
+        return Ok(());
     };
+    if matches!(strategy, Some(CapacityStrategy::Waitlist | CapacityStrategy::Cancel)) {
+        return Ok(());
+    }
 
-    let existing = sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id = $1")
-        .bind(id)
-        .fetch_optional(&state.db)
-        .await;
-
-    let event = match existing {
-        Ok(Some(e)) => e,
-        Ok(None) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Dogadjaj ne postoji"))),
-        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    let url = format!("{}/internal/event/{}/counts", state.registration_service_url, event.id);
+    let counts = match state.http_client.get(&url).send().await {
+        Ok(resp) => resp.json::<RegistrationCountsResponse>().await.ok(),
+        Err(_) => None,
+    };
+    let confirmed = match counts {
+        Some(RegistrationCountsResponse { success: true, data: Some(c) }) => c.confirmed,
+        _ => {
+            return Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Registration servis nije dostupan - ne mogu da proverim broj prijava".to_string(),
+            ))
+        }
     };
 
-    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
-    if event.organizer_id != user_id && claims.role != "Admin" {
-        return (StatusCode::FORBIDDEN, Json(ApiResponse::error("Nemate dozvolu da obrisete ovaj dogadjaj")));
+    if (capacity as i64) < confirmed {
+        return Err((
+            StatusCode::CONFLICT,
+            format!(
+                "Kapacitet {} je manji od broja potvrdjenih prijava ({}). Izaberite capacity_strategy: \
+                 waitlist (poslednji prijavljeni idu na listu cekanja) ili cancel (otkazuju se uz napomenu)",
+                capacity, confirmed
+            ),
+        ));
     }
+    Ok(())
+}
 
-    // Otkazi sve prijave za ovaj dogadjaj pre brisanja
+/// Posle promene kapaciteta: visak prijava ide na listu cekanja / otkazuje se,
+/// a vise mesta popunjava lista cekanja
+async fn sync_capacity(state: &AppState, before: &Event, after: &Event, req: &UpdateEventRequest) -> CapacitySync {
+    if before.capacity == after.capacity {
+        return CapacitySync::default();
+    }
+
+    let strategy = match req.capacity_strategy {
+        Some(CapacityStrategy::Waitlist) => Some("waitlist"),
+        Some(CapacityStrategy::Cancel) => Some("cancel"),
+        _ => None,
+    };
+    let url = format!("{}/internal/event/{}/capacity", state.registration_service_url, after.id);
+    let resp = state
+        .http_client
+        .post(&url)
+        .json(&json!({ "capacity": after.capacity, "strategy": strategy, "notice": req.capacity_notice }))
+        .send()
+        .await;
+
+    let body = match resp {
+        Ok(r) => r.json::<CapacitySyncResponse>().await.map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+    match body {
+        Ok(CapacitySyncResponse { success: true, data: Some(sync) }) => sync,
+        Ok(_) => {
+            tracing::warn!("Registration servis nije uskladio prijave za {}", after.id);
+            CapacitySync::default()
+        }
+        Err(e) => {
+            tracing::warn!("Nisam uspeo da uskladim prijave za {}: {}", after.id, e);
+            CapacitySync::default()
+        }
+    }
+}
+
+/// Poruka posle izmene - sa brojem pomerenih prijava ako ih ima
+fn updated_message(base: &str, sync: &CapacitySync, strategy: Option<CapacityStrategy>) -> String {
+    match (sync.moved, sync.promoted) {
+        (0, 0) => base.to_string(),
+        (0, promoted) => format!("{}; sa liste cekanja potvrdjeno: {}", base, promoted),
+        (moved, _) if strategy == Some(CapacityStrategy::Cancel) => format!("{}; otkazano prijava: {}", base, moved),
+        (moved, _) => format!("{}; na listu cekanja premesteno: {}", base, moved),
+    }
+}
+
+/// Odgovor sa dogadjajem i njegovim ETag-om
+fn event_response(status: StatusCode, message: &str, event: Event) -> Response {
+    let tag = etag(event.version);
+    (status, [(header::ETAG, tag)], Json(ApiResponse::success(message, event))).into_response()
+}
+
+/// 412 kad If-Match ne odgovara trenutnoj verziji
+fn version_conflict(current: i32) -> Response {
+    (
+        StatusCode::PRECONDITION_FAILED,
+        [(header::ETAG, etag(current))],
+        Json(ApiResponse::<()>::error(&format!(
+            "Dogadjaj je u medjuvremenu izmenjen (trenutna verzija {}) - ucitajte ga ponovo",
+            current
+        ))),
+    )
+        .into_response()
+}
+
+/// update event - trazi If-Match sa verzijom koju je klijent ucitao
+pub async fn update_event(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(mut req): Json<UpdateEventRequest>,
+) -> Response {
+    let (event, claims) = match load_managed_event::<()>(&state, &headers, id, Permission::Edit).await {
+        Ok(v) => v,
+        Err(err) => return err.into_response(),
+    };
+
+    if !headers.contains_key(header::IF_MATCH) {
+        return (
+            StatusCode::PRECONDITION_REQUIRED,
+            Json(ApiResponse::<()>::error("Potreban je If-Match header sa ETag-om dogadjaja")),
+        )
+            .into_response();
+    }
+    let expected = match if_match_version(&headers) {
+        Ok(v) => v,
+        Err(msg) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::<()>::error(msg))).into_response(),
+    };
+    if expected.is_some_and(|v| v != event.version) {
+        return version_conflict(event.version);
+    }
+
+    if let Err(msg) = validate_update(&req) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::<()>::error(msg))).into_response();
+    }
+    if let Err((status, msg)) = resolve_update_category(&state.db, &mut req).await {
+        return (status, Json(ApiResponse::<()>::error(&msg))).into_response();
+    }
+    if let Err((status, msg)) = check_capacity_change(&state, &event, req.capacity, req.capacity_strategy).await {
+        return (status, Json(ApiResponse::<()>::error(&msg))).into_response();
+    }
+
+    let author_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+    match update_with_revision(&state.db, &event, &req, Some(event.version), author_id).await {
+        Ok(Some(updated)) => {
+            cleanup_replaced_image(&state, &event, &req).await;
+            let sync = sync_capacity(&state, &event, &updated, &req).await;
+            let msg = updated_message("Dogadjaj azuriran", &sync, req.capacity_strategy);
+            event_response(StatusCode::OK, &msg, updated)
+        }
+        // izmenjen izmedju citanja i upisa
+        Ok(None) => {
+            let current = sqlx::query_scalar::<_, i32>("SELECT version FROM events WHERE id = $1")
+                .bind(id)
+                .fetch_optional(&state.db)
+                .await;
+            match current {
+                Ok(Some(v)) => version_conflict(v),
+                _ => (StatusCode::NOT_FOUND, Json(ApiResponse::<()>::error("Dogadjaj ne postoji"))).into_response(),
+            }
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(&format!("Greska: {}", e))),
+        )
+            .into_response(),
+    }
+}
+
+/// Ako je izmena zamenila uploadovanu sliku drugim URL-om, brise stare fajlove
+async fn cleanup_replaced_image(state: &AppState, event: &Event, req: &UpdateEventRequest) {
+    if req.image_url.is_some() && req.image_url != event.image_url {
+        release_images(state, &event.image_keys).await;
+    }
+}
+
+/// Otkazuje prijave u registration-service (oznacene kao otkazane brisanjem, da bi mogle da se vrate)
+async fn cancel_registrations(state: &AppState, id: Uuid) {
     let cancel_url = format!("{}/internal/event/{}/cancel-registrations", state.registration_service_url, id);
     if let Err(e) = state.http_client.post(&cancel_url).send().await {
         tracing::warn!("Nisam uspeo da otkazem prijave u registration-service: {}", e);
     }
+}
+
+/// Premesta dogadjaj u kantu i otkazuje prijave (vracaju se ako se dogadjaj vrati iz kante).
+/// `deleted_by` je None kad brise sistem (npr. termin koji je ispao iz pravila serije).
+pub(crate) async fn trash_event(state: &AppState, id: Uuid, deleted_by: Option<Uuid>) -> Result<(), sqlx::Error> {
+    let trashed = sqlx::query(
+        "UPDATE events SET deleted_at = NOW(), deleted_by = $2, version = version + 1, updated_at = NOW()
+         WHERE id = $1 AND deleted_at IS NULL",
+    )
+    .bind(id)
+    .bind(deleted_by)
+    .execute(&state.db)
+    .await?;
+
+    if trashed.rows_affected() > 0 {
+        cancel_registrations(state, id).await;
+    }
+    Ok(())
+}
 
-    let _ = sqlx::query("DELETE FROM events WHERE id = $1")
+/// Otkazuje prijave i trajno brise dogadjaj - samo za ciscenje kante (trash::run_purge)
+pub(crate) async fn remove_event(state: &AppState, id: Uuid) {
+    cancel_registrations(state, id).await;
+
+    let deleted = sqlx::query_scalar::<_, Vec<String>>("DELETE FROM events WHERE id = $1 RETURNING image_keys")
         .bind(id)
-        .execute(&state.db)
+        .fetch_optional(&state.db)
         .await;
 
-    (StatusCode::OK, Json(ApiResponse::success("Dogadjaj obrisan", "ok".to_string())))
+    if let Ok(Some(keys)) = deleted {
+        release_images(state, &keys).await;
+    }
+}
+
+/// delete event - ide u kantu (prijave se otkazuju, slika ostaje do trajnog brisanja)
+pub async fn delete_event(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let (event, claims) = match load_managed_event(&state, &headers, id, Permission::Delete).await {
+        Ok(v) => v,
+        Err(err) => return err,
+    };
+
+    // obrisan termin serije ide u EXDATE da ga materijalizacija ne bi vratila
+    if let (Some(series_id), Some(occurrence)) = (event.series_id, event.occurrence_date) {
+        let _ = sqlx::query("UPDATE event_series SET exdates = array_append(exdates, $2), updated_at = NOW() WHERE id = $1")
+            .bind(series_id)
+            .bind(occurrence)
+            .execute(&state.db)
+            .await;
+    }
+
+    if let Err(e) = trash_event(&state, id, Uuid::parse_str(&claims.sub).ok()).await {
+        return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e))));
+    }
+
+    let msg = format!("Dogadjaj premesten u kantu (trajno brisanje za {} dana)", state.trash_retention_days);
+    (StatusCode::OK, Json(ApiResponse::success(&msg, "ok".to_string())))
+}
+
+// ---- Serije dogadjaja ----
+
+async fn series_details(db: &PgPool, series: EventSeries) -> Result<SeriesDetails, sqlx::Error> {
+    let occurrences = sqlx::query_as::<_, Event>(
+        "SELECT * FROM events WHERE series_id = $1 AND deleted_at IS NULL ORDER BY date_time",
+    )
+    .bind(series.id)
+    .fetch_all(db)
+    .await?;
+
+    Ok(SeriesDetails { series, occurrences })
+}
+
+/// Da li korisnik ima dozvolu na svakom terminu serije (osoblje se dodeljuje po terminu);
+/// serijom bez termina upravljaju samo njen organizator i Admin
+async fn can_manage_series(
+    db: &PgPool,
+    series: &EventSeries,
+    claims: &Claims,
+    permission: Permission,
+) -> Result<bool, sqlx::Error> {
+    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+    if claims.role == "Admin" {
+        return Ok(true);
+    }
+
+    let roles = sqlx::query_as::<_, (bool, Option<String>)>(
+        "SELECT e.organizer_id = $2, s.role FROM events e
+         LEFT JOIN event_staff s ON s.event_id = e.id AND s.user_id = $2
+         WHERE e.series_id = $1 AND e.deleted_at IS NULL",
+    )
+    .bind(series.id)
+    .bind(user_id)
+    .fetch_all(db)
+    .await?;
+
+    if roles.is_empty() {
+        return Ok(series.organizer_id == user_id);
+    }
+    Ok(roles
+        .iter()
+        .all(|(owner, role)| *owner || role.as_deref().is_some_and(|r| role_allows(r, permission))))
+}
+
+/// POST /series - nova serija; termini se odmah prave do horizonta
+pub async fn create_series(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Json(mut req): Json<CreateSeriesRequest>,
+) -> (StatusCode, Json<ApiResponse<SeriesDetails>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    if let Err((status, msg)) = require_role(&claims, "Admin") {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+    let organizer_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+
+    let rule: RRule = match req.rrule.parse() {
+        Ok(r) => r,
+        Err(msg) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(&format!("Neispravno RRULE pravilo: {}", msg)))),
+    };
+    if let Err(msg) = validate_capacity(req.capacity)
+        .and_then(|_| validate_coordinates(req.latitude, req.longitude))
+        .and_then(|_| validate_image_url(req.image_url.as_deref()))
+    {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(msg)));
+    }
+    match canonical_category(&state.db, &req.category).await {
+        Ok(name) => req.category = name,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    }
+
+    let result = sqlx::query_as::<_, EventSeries>(
+        "INSERT INTO event_series (id, organizer_id, title, description, location, capacity, category, image_url, latitude, longitude, dtstart, rrule, exdates)
+         VALUES (gen_random_uuid(), $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+         RETURNING *",
+    )
+    .bind(organizer_id)
+    .bind(&req.title)
+    .bind(&req.description)
+    .bind(&req.location)
+    .bind(req.capacity)
+    .bind(&req.category)
+    .bind(&req.image_url)
+    .bind(req.latitude)
+    .bind(req.longitude)
+    .bind(req.dtstart)
+    .bind(rule.to_string())
+    .bind(&req.exdates)
+    .fetch_one(&state.db)
+    .await;
+
+    let series = match result {
+        Ok(s) => s,
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+
+    if let Err(e) = sync_series(&state, &series).await {
+        return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e))));
+    }
+
+    match series_details(&state.db, series).await {
+        Ok(details) => (StatusCode::CREATED, Json(ApiResponse::success("Serija kreirana", details))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// GET /series/:id - serija sa terminima koje korisnik sme da vidi
+pub async fn get_series(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<SeriesDetails>>) {
+    let series = match sqlx::query_as::<_, EventSeries>("SELECT * FROM event_series WHERE id = $1").bind(id).fetch_optional(&state.db).await {
+        Ok(Some(s)) => s,
+        Ok(None) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Serija ne postoji"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+
+    let mut details = match series_details(&state.db, series).await {
+        Ok(d) => d,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+
+    // privatni, nelistani i nacrti se ne vide; serija ciji nijedan termin nije vidljiv ne postoji
+    let total = details.occurrences.len();
+    let mut visible = Vec::with_capacity(total);
+    for event in details.occurrences {
+        if can_see(&headers, &state, &event, false).await {
+            visible.push(event);
+        }
+    }
+    if total > 0 && visible.is_empty() {
+        return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Serija ne postoji")));
+    }
+    details.occurrences = visible;
+
+    (StatusCode::OK, Json(ApiResponse::success("Serija pronadjena", details)))
+}
+
+/// DELETE /series/:id - brise seriju, buduci termini idu u kantu (prosli ostaju kao obicni dogadjaji)
+pub async fn delete_series(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+
+    let series = match sqlx::query_as::<_, EventSeries>("SELECT * FROM event_series WHERE id = $1").bind(id).fetch_optional(&state.db).await {
+        Ok(Some(s)) => s,
+        Ok(None) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Serija ne postoji"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+
+    match can_manage_series(&state.db, &series, &claims, Permission::Delete).await {
+        Ok(true) => {}
+        Ok(false) => return (StatusCode::FORBIDDEN, Json(ApiResponse::error("Nemate dozvolu da obrisete ovu seriju"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+
+    // bez liste buduci termini bi posle brisanja serije ostali kao obicni dogadjaji
+    let upcoming = match sqlx::query_scalar::<_, Uuid>(
+        "SELECT id FROM events WHERE series_id = $1 AND date_time >= $2 AND deleted_at IS NULL",
+    )
+    .bind(id)
+    .bind(local_now())
+    .fetch_all(&state.db)
+    .await
+    {
+        Ok(ids) => ids,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+
+    for event_id in upcoming {
+        if let Err(e) = trash_event(&state, event_id, Some(user_id)).await {
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e))));
+        }
+    }
+
+    if let Err(e) = sqlx::query("DELETE FROM event_series WHERE id = $1").bind(id).execute(&state.db).await {
+        return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e))));
+    }
+
+    (StatusCode::OK, Json(ApiResponse::success("Serija obrisana", "ok".to_string())))
+}
+
+/// Primenjuje izmene sablona na termine od `from` nadalje i pomera ih za `delta`.
+/// Rucno izmenjeni termini (izuzeci) zadrzavaju svoja polja, ali prelaze u `new_series`.
+/// Svaki izmenjen termin dobija reviziju; vraca parove (pre, posle) za uskladjivanje kapaciteta.
+async fn apply_to_occurrences(
+    tx: &mut Transaction<'_, Postgres>,
+    old_series: Uuid,
+    new_series: Uuid,
+    from: NaiveDateTime,
+    delta: Duration,
+    changes: &UpdateEventRequest,
+    author_id: Uuid,
+) -> Result<Vec<(Event, Event)>, sqlx::Error> {
+    let delta_secs = delta.num_seconds() as f64;
+
+    let mut before = sqlx::query_as::<_, Event>(
+        "SELECT * FROM events WHERE series_id = $1 AND occurrence_date >= $2 AND NOT is_exception FOR UPDATE",
+    )
+    .bind(old_series)
+    .bind(from)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let after = sqlx::query_as::<_, Event>(
+        "UPDATE events SET
+            title = COALESCE($4, title),
+            description = COALESCE($5, description),
+            description_html = COALESCE($12, description_html),
+            location = COALESCE($6, location),
+            capacity = COALESCE($7, capacity),
+            category = COALESCE($8, category),
+            image_url = COALESCE($9, image_url),
+            image_thumbnails = CASE WHEN $9 IS NULL OR $9 = image_url THEN image_thumbnails END,
+            latitude = COALESCE($10, latitude),
+            longitude = COALESCE($11, longitude),
+            date_time = date_time + $3 * INTERVAL '1 second',
+            version = version + 1,
+            updated_at = NOW()
+         WHERE series_id = $1 AND occurrence_date >= $2 AND NOT is_exception RETURNING *",
+    )
+    .bind(old_series)
+    .bind(from)
+    .bind(delta_secs)
+    .bind(&changes.title)
+    .bind(&changes.description)
+    .bind(&changes.location)
+    .bind(changes.capacity)
+    .bind(&changes.category)
+    .bind(&changes.image_url)
+    .bind(changes.latitude)
+    .bind(changes.longitude)
+    .bind(changes.description.as_deref().map(render_markdown))
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let mut changed = Vec::with_capacity(after.len());
+    for updated in after {
+        if let Some(i) = before.iter().position(|e| e.id == updated.id) {
+            let old = before.swap_remove(i);
+            record_revision(tx, &old, &updated, author_id).await?;
+            changed.push((old, updated));
+        }
+    }
+
+    sqlx::query(
+        "UPDATE events SET series_id = $2, occurrence_date = occurrence_date + $4 * INTERVAL '1 second'
+         WHERE series_id = $1 AND occurrence_date >= $3",
+    )
+    .bind(old_series)
+    .bind(new_series)
+    .bind(from)
+    .bind(delta_secs)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(changed)
+}
+
+/// Izmena "svi termini" - menja sablon serije i sve buduce termine
+async fn update_whole_series(
+    state: &AppState,
+    series: &EventSeries,
+    event: &Event,
+    new_rule: Option<RRule>,
+    req: &UpdateOccurrenceRequest,
+    author_id: Uuid,
+) -> Result<EventSeries, String> {
+    let delta = req.changes.date_time.map(|dt| dt - event.date_time).unwrap_or_else(Duration::zero);
+    // termini se pomeraju (ne brisu) - pravilo mora da ih i dalje daje
+    let rrule = match new_rule {
+        Some(rule) => rule.to_string(),
+        None => series.rrule.parse::<RRule>()?.shifted(series.dtstart, delta)?.to_string(),
+    };
+    let exdates: Vec<NaiveDateTime> = match &req.exdates {
+        Some(list) => list.clone(),
+        None => series.exdates.iter().map(|d| *d + delta).collect(),
+    };
+    let changes = &req.changes;
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    sqlx::query("SET CONSTRAINTS events_series_occurrence_key DEFERRED")
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let updated = sqlx::query_as::<_, EventSeries>(
+        "UPDATE event_series SET
+            title = COALESCE($2, title),
+            description = COALESCE($3, description),
+            location = COALESCE($4, location),
+            capacity = COALESCE($5, capacity),
+            category = COALESCE($6, category),
+            image_url = COALESCE($7, image_url),
+            latitude = COALESCE($8, latitude),
+            longitude = COALESCE($9, longitude),
+            dtstart = dtstart + $10 * INTERVAL '1 second',
+            rrule = $11,
+            exdates = $12,
+            updated_at = NOW()
+         WHERE id = $1 RETURNING *",
+    )
+    .bind(series.id)
+    .bind(&changes.title)
+    .bind(&changes.description)
+    .bind(&changes.location)
+    .bind(changes.capacity)
+    .bind(&changes.category)
+    .bind(&changes.image_url)
+    .bind(changes.latitude)
+    .bind(changes.longitude)
+    .bind(delta.num_seconds() as f64)
+    .bind(&rrule)
+    .bind(&exdates)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let now = chrono::Utc::now().naive_utc();
+    let changed = apply_to_occurrences(&mut tx, series.id, series.id, now, delta, changes, author_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    for (before, after) in &changed {
+        sync_capacity(state, before, after, changes).await;
+    }
+
+    sync_series(state, &updated).await?;
+    Ok(updated)
+}
+
+/// Izmena "ovaj i sledeci" - stara serija se zavrsava pre ovog termina,
+/// a od njega nadalje ide nova serija sa izmenama
+async fn split_series(
+    state: &AppState,
+    series: &EventSeries,
+    event: &Event,
+    occurrence: NaiveDateTime,
+    new_rule: Option<RRule>,
+    req: &UpdateOccurrenceRequest,
+    author_id: Uuid,
+) -> Result<EventSeries, String> {
+    let old_rule: RRule = series.rrule.parse()?;
+    let delta = req.changes.date_time.map(|dt| dt - event.date_time).unwrap_or_else(Duration::zero);
+
+    let rule_for_new = match new_rule {
+        Some(rule) => rule,
+        None => {
+            let mut rule = old_rule.shifted(series.dtstart, delta)?;
+            // COUNT se deli - novoj seriji ostaje onoliko termina koliko ih je preostalo
+            if rule.count.is_some() {
+                let remaining = old_rule
+                    .occurrences(series.dtstart, &[], NaiveDateTime::MAX)
+                    .into_iter()
+                    .filter(|d| *d >= occurrence)
+                    .count() as u32;
+                rule.count = Some(remaining.max(1));
+            }
+            rule
+        }
+    };
+
+    let mut rule_for_old = old_rule;
+    rule_for_old.count = None;
+    rule_for_old.until = Some(occurrence - Duration::seconds(1));
+
+    let old_exdates: Vec<NaiveDateTime> = series.exdates.iter().copied().filter(|d| *d < occurrence).collect();
+    let new_exdates: Vec<NaiveDateTime> = match &req.exdates {
+        Some(list) => list.clone(),
+        None => series
+            .exdates
+            .iter()
+            .filter(|d| **d >= occurrence)
+            .map(|d| *d + delta)
+            .collect(),
+    };
+    let changes = &req.changes;
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    sqlx::query("SET CONSTRAINTS events_series_occurrence_key DEFERRED")
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let old = sqlx::query_as::<_, EventSeries>(
+        "UPDATE event_series SET rrule = $2, exdates = $3, updated_at = NOW() WHERE id = $1 RETURNING *",
+    )
+    .bind(series.id)
+    .bind(rule_for_old.to_string())
+    .bind(&old_exdates)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let new = sqlx::query_as::<_, EventSeries>(
+        "INSERT INTO event_series (id, organizer_id, title, description, location, capacity, category, image_url, latitude, longitude, dtstart, rrule, exdates, eligibility)
+         SELECT gen_random_uuid(), organizer_id,
+                COALESCE($2, title), COALESCE($3, description), COALESCE($4, location),
+                COALESCE($5, capacity), COALESCE($6, category), COALESCE($7, image_url),
+                COALESCE($8, latitude), COALESCE($9, longitude),
+                $10, $11, $12, eligibility
+         FROM event_series WHERE id = $1
+         RETURNING *",
+    )
+    .bind(series.id)
+    .bind(&changes.title)
+    .bind(&changes.description)
+    .bind(&changes.location)
+    .bind(changes.capacity)
+    .bind(&changes.category)
+    .bind(&changes.image_url)
+    .bind(changes.latitude)
+    .bind(changes.longitude)
+    .bind(occurrence + delta)
+    .bind(rule_for_new.to_string())
+    .bind(&new_exdates)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let changed = apply_to_occurrences(&mut tx, series.id, new.id, occurrence, delta, changes, author_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    for (before, after) in &changed {
+        sync_capacity(state, before, after, changes).await;
+    }
+
+    sync_series(state, &old).await?;
+    sync_series(state, &new).await?;
+    Ok(new)
+}
+
+/// Smanjenje kapaciteta proverava svaki termin koji izmena dira, kao izmena pojedinacnog dogadjaja
+async fn check_occurrences_capacity(
+    state: &AppState,
+    series: &EventSeries,
+    event: &Event,
+    req: &UpdateOccurrenceRequest,
+) -> Result<(), (StatusCode, String)> {
+    let changes = &req.changes;
+    if changes.capacity.is_none() {
+        return Ok(());
+    }
+
+    let from = match (req.scope, event.occurrence_date) {
+        (EditScope::This, _) => return check_capacity_change(state, event, changes.capacity, changes.capacity_strategy).await,
+        (EditScope::Following, Some(occurrence)) if occurrence > series.dtstart => occurrence,
+        _ => chrono::Utc::now().naive_utc(),
+    };
+    let affected = sqlx::query_as::<_, Event>(
+        "SELECT * FROM events WHERE series_id = $1 AND occurrence_date >= $2 AND NOT is_exception AND deleted_at IS NULL",
+    )
+    .bind(series.id)
+    .bind(from)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Greska: {}", e)))?;
+
+    for occurrence in &affected {
+        check_capacity_change(state, occurrence, changes.capacity, changes.capacity_strategy)
+            .await
+            .map_err(|(status, msg)| (status, format!("Termin {}: {}", occurrence.date_time.format("%d.%m.%Y. %H:%M"), msg)))?;
+    }
+    Ok(())
+}
+
+const OCCURRENCE_CONFLICT: &str = "Termin je u medjuvremenu izmenjen - ucitajte ga ponovo";
+
+/// PUT /series/:id/occurrences/:event_id - izmena termina: "this", "following" ili "all"
+pub async fn update_occurrence(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((series_id, event_id)): Path<(Uuid, Uuid)>,
+    Json(mut req): Json<UpdateOccurrenceRequest>,
+) -> (StatusCode, Json<ApiResponse<SeriesDetails>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+
+    let series = match sqlx::query_as::<_, EventSeries>("SELECT * FROM event_series WHERE id = $1").bind(series_id).fetch_optional(&state.db).await {
+        Ok(Some(s)) => s,
+        Ok(None) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Serija ne postoji"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+
+    let event = match sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id = $1 AND series_id = $2 AND deleted_at IS NULL").bind(event_id).bind(series_id).fetch_optional(&state.db).await {
+        Ok(Some(e)) => e,
+        Ok(None) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Termin ne pripada ovoj seriji"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+
+    // "this" menja samo ovaj termin, ostalo sve termine serije
+    let allowed = match req.scope {
+        EditScope::This => Ok(event_role(&state.db, &event, &claims)
+            .await
+            .is_some_and(|role| role_allows(&role, Permission::Edit))),
+        _ => can_manage_series(&state.db, &series, &claims, Permission::Edit).await,
+    };
+    match allowed {
+        Ok(true) => {}
+        Ok(false) => return (StatusCode::FORBIDDEN, Json(ApiResponse::error("Nemate dozvolu da menjate ovu seriju"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+
+    // izmena jednog termina je kao izmena dogadjaja - trazi If-Match
+    if req.scope == EditScope::This {
+        if !headers.contains_key(header::IF_MATCH) {
+            return (StatusCode::PRECONDITION_REQUIRED, Json(ApiResponse::error("Potreban je If-Match header sa ETag-om termina")));
+        }
+        match if_match_version(&headers) {
+            Ok(Some(v)) if v != event.version => return (StatusCode::PRECONDITION_FAILED, Json(ApiResponse::error(OCCURRENCE_CONFLICT))),
+            Ok(_) => {}
+            Err(msg) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(msg))),
+        }
+    }
+    let author_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+
+    if let Err(msg) = validate_update(&req.changes) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(msg)));
+    }
+    if let Err((status, msg)) = resolve_update_category(&state.db, &mut req.changes).await {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+    if let Err((status, msg)) = check_occurrences_capacity(&state, &series, &event, &req).await {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+
+    let new_rule = match req.rrule.as_deref().map(str::parse::<RRule>) {
+        Some(Ok(rule)) => Some(rule),
+        Some(Err(msg)) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(&format!("Neispravno RRULE pravilo: {}", msg)))),
+        None => None,
+    };
+
+    // pomeranje na drugi dan bez novog pravila - staro pravilo mora moci da se pomeri
+    if let (EditScope::Following | EditScope::All, None, Some(new_time)) = (req.scope, &new_rule, req.changes.date_time) {
+        let shifted = series.rrule.parse::<RRule>().and_then(|r| r.shifted(series.dtstart, new_time - event.date_time));
+        if let Err(msg) = shifted {
+            return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(&msg)));
+        }
+    }
+
+    let result = match (req.scope, event.occurrence_date) {
+        (EditScope::This, _) => {
+            if req.rrule.is_some() || req.exdates.is_some() {
+                return (StatusCode::BAD_REQUEST, Json(ApiResponse::error("Pravilo serije se menja samo za \"following\" ili \"all\"")));
+            }
+            match update_with_revision(&state.db, &event, &req.changes, Some(event.version), author_id).await {
+                Ok(Some(updated)) => {
+                    cleanup_replaced_image(&state, &event, &req.changes).await;
+                    sync_capacity(&state, &event, &updated, &req.changes).await;
+                    Ok(series.clone())
+                }
+                // izmenjen izmedju citanja i upisa
+                Ok(None) => return (StatusCode::PRECONDITION_FAILED, Json(ApiResponse::error(OCCURRENCE_CONFLICT))),
+                Err(e) => Err(e.to_string()),
+            }
+        }
+        // od prvog termina "ovaj i sledeci" je isto sto i "svi"
+        (EditScope::Following, Some(occurrence)) if occurrence > series.dtstart => {
+            split_series(&state, &series, &event, occurrence, new_rule, &req, author_id).await
+        }
+        _ => update_whole_series(&state, &series, &event, new_rule, &req, author_id).await,
+    };
+
+    let series = match result {
+        Ok(s) => s,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+
+    match series_details(&state.db, series).await {
+        Ok(details) => (StatusCode::OK, Json(ApiResponse::success("Serija azurirana", details))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+// ---- Slike ----
+
+fn image_too_large<T: Serialize>(max_bytes: usize) -> (StatusCode, Json<ApiResponse<T>>) {
+    (
+        StatusCode::PAYLOAD_TOO_LARGE,
+        Json(ApiResponse::error(&format!("Slika je veca od {} KB", max_bytes / 1024))),
+    )
+}
+
+fn multipart_error<T: Serialize>(e: MultipartError, max_bytes: usize) -> (StatusCode, Json<ApiResponse<T>>) {
+    // prekoracen DefaultBodyLimit
+    if e.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        return image_too_large(max_bytes);
+    }
+    (e.status(), Json(ApiResponse::error(&format!("Neispravan upload: {}", e.body_text()))))
+}
+
+/// POST /events/:id/image - multipart upload (polje "image").
+/// Pravi thumbnail-ove, a image_url postaje URL originala.
+pub async fn upload_event_image(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    mut multipart: Multipart,
+) -> (StatusCode, Json<ApiResponse<Event>>) {
+    let (event, _) = match load_managed_event(&state, &headers, id, Permission::Edit).await {
+        Ok(v) => v,
+        Err(err) => return err,
+    };
+
+    let mut upload = None;
+    loop {
+        match multipart.next_field().await {
+            Ok(Some(field)) if field.name() == Some("image") => {
+                let content_type = field.content_type().unwrap_or_default().to_ascii_lowercase();
+                match field.bytes().await {
+                    Ok(bytes) => {
+                        upload = Some((content_type, bytes));
+                        break;
+                    }
+                    Err(e) => return multipart_error(e, state.max_image_bytes),
+                }
+            }
+            Ok(Some(_)) => continue,
+            Ok(None) => break,
+            Err(e) => return multipart_error(e, state.max_image_bytes),
+        }
+    }
+
+    let Some((content_type, bytes)) = upload else {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error("Nedostaje polje \"image\" sa slikom")));
+    };
+    if extension_for(&content_type).is_none() {
+        return (StatusCode::UNSUPPORTED_MEDIA_TYPE, Json(ApiResponse::error("Dozvoljene su samo JPEG, PNG, WebP i GIF slike")));
+    }
+    if bytes.len() > state.max_image_bytes {
+        return image_too_large(state.max_image_bytes);
+    }
+
+    let stored = match store_image(state.storage.as_ref(), &format!("events/{}", id), bytes.to_vec(), &content_type).await {
+        Ok(s) => s,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+
+    // kao i svaka izmena pojedinacnog termina, upload ga pravi izuzetkom serije
+    let result = sqlx::query_as::<_, Event>(
+        "UPDATE events SET image_url = $2, image_thumbnails = $3, image_keys = $4,
+            is_exception = (series_id IS NOT NULL), version = version + 1, updated_at = NOW()
+         WHERE id = $1 RETURNING *",
+    )
+    .bind(id)
+    .bind(&stored.url)
+    .bind(sqlx::types::Json(&stored.thumbnails))
+    .bind(&stored.keys)
+    .fetch_one(&state.db)
+    .await;
+
+    match result {
+        Ok(updated) => {
+            release_images(&state, &event.image_keys).await;
+            (StatusCode::OK, Json(ApiResponse::success("Slika sacuvana", updated)))
+        }
+        Err(e) => {
+            delete_images(state.storage.as_ref(), &stored.keys).await;
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e))))
+        }
+    }
+}
+
+/// DELETE /events/:id/image - uklanja sliku i njene fajlove
+pub async fn delete_event_image(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<Event>>) {
+    let (event, _) = match load_managed_event(&state, &headers, id, Permission::Edit).await {
+        Ok(v) => v,
+        Err(err) => return err,
+    };
+
+    let result = sqlx::query_as::<_, Event>(
+        "UPDATE events SET image_url = NULL, image_thumbnails = NULL, image_keys = '{}',
+            is_exception = (series_id IS NOT NULL), version = version + 1, updated_at = NOW()
+         WHERE id = $1 RETURNING *",
+    )
+    .bind(id)
+    .fetch_one(&state.db)
+    .await;
+
+    match result {
+        Ok(updated) => {
+            release_images(&state, &event.image_keys).await;
+            (StatusCode::OK, Json(ApiResponse::success("Slika obrisana", updated)))
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// GET /uploads/*key - servira slike sa lokalnog diska (S3 ih servira sam)
+pub async fn serve_upload(State(state): State<AppState>, Path(key): Path<String>) -> Response {
+    let Some(path) = state.storage.local_path(&key) else {
+        return (StatusCode::NOT_FOUND, "Fajl ne postoji").into_response();
+    };
+
+    match tokio::fs::read(&path).await {
+        // kljucevi su jedinstveni (uuid), pa fajl moze da se kesira zauvek
+        Ok(bytes) => (
+            [
+                (header::CONTENT_TYPE, content_type_for(&key)),
+                (header::CACHE_CONTROL, "public, max-age=31536000, immutable"),
+            ],
+            bytes,
+        )
+            .into_response(),
+        Err(_) => (StatusCode::NOT_FOUND, "Fajl ne postoji").into_response(),
+    }
+}
+
+// ---- Sesije i predavaci ----
+
+/// Uloga korisnika na dogadjaju: OWNER za organizatora i Admina, inace uloga iz event_staff
+async fn event_role(db: &PgPool, event: &Event, claims: &Claims) -> Option<String> {
+    let user_id = Uuid::parse_str(&claims.sub).ok()?;
+    if event.organizer_id == user_id || claims.role == "Admin" {
+        return Some(OWNER.to_string());
+    }
+    sqlx::query_scalar::<_, String>("SELECT role FROM event_staff WHERE event_id = $1 AND user_id = $2")
+        .bind(event.id)
+        .bind(user_id)
+        .fetch_optional(db)
+        .await
+        .ok()
+        .flatten()
+}
+
+fn forbidden_message(permission: Permission) -> &'static str {
+    match permission {
+        Permission::View => "Nemate pristup ovom dogadjaju",
+        Permission::Delete => "Nemate dozvolu da obrisete ovaj dogadjaj",
+        Permission::ManageStaff => "Nemate dozvolu da upravljate osobljem dogadjaja",
+        _ => "Nemate dozvolu da menjate ovaj dogadjaj",
+    }
+}
+
+/// Ucitava dogadjaj ako korisnik (organizator, Admin ili osoblje) ima datu dozvolu
+async fn load_managed_event<T: Serialize>(
+    state: &AppState,
+    headers: &HeaderMap,
+    id: Uuid,
+    permission: Permission,
+) -> Result<(Event, Claims), (StatusCode, Json<ApiResponse<T>>)> {
+    let claims = extract_claims(headers, &state.jwt_secret)
+        .map_err(|(status, msg)| (status, Json(ApiResponse::error(&msg))))?;
+
+    let event = match sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id = $1 AND deleted_at IS NULL").bind(id).fetch_optional(&state.db).await {
+        Ok(Some(e)) => e,
+        Ok(None) => return Err((StatusCode::NOT_FOUND, Json(ApiResponse::error("Dogadjaj ne postoji")))),
+        Err(e) => return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e))))),
+    };
+
+    let allowed = event_role(&state.db, &event, &claims)
+        .await
+        .is_some_and(|role| role_allows(&role, permission));
+    if !allowed {
+        return Err((StatusCode::FORBIDDEN, Json(ApiResponse::error(forbidden_message(permission)))));
+    }
+
+    Ok((event, claims))
+}
+
+/// Dogadjaj ciji se delovi (sesije, predavaci, karte...) citaju - 404 ako ga korisnik ne sme videti
+async fn load_visible_event<T: Serialize>(
+    state: &AppState,
+    headers: &HeaderMap,
+    id: Uuid,
+) -> Result<Event, (StatusCode, Json<ApiResponse<T>>)> {
+    match sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id = $1 AND deleted_at IS NULL").bind(id).fetch_optional(&state.db).await {
+        Ok(Some(e)) if can_see(headers, state, &e, false).await => Ok(e),
+        Ok(_) => Err((StatusCode::NOT_FOUND, Json(ApiResponse::error("Dogadjaj ne postoji")))),
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e))))),
+    }
+}
+
+/// Pita registration-service da li korisnik ima prijavu na dogadjaj
+async fn fetch_registration(
+    state: &AppState,
+    event_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<RegistrationData>, String> {
+    let url = format!("{}/internal/event/{}/user/{}", state.registration_service_url, event_id, user_id);
+    let resp = state.http_client.get(&url).send().await.map_err(|e| e.to_string())?;
+
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    let body = resp
+        .json::<RegistrationServiceResponse>()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(if body.success { body.data } else { None })
+}
+
+/// Sesije dogadjaja sa predavacima i brojem prisutnih (opciono samo jedna sesija)
+async fn load_sessions(
+    db: &PgPool,
+    event_id: Uuid,
+    only: Option<Uuid>,
+) -> Result<Vec<SessionDetails>, sqlx::Error> {
+    let sessions = sqlx::query_as::<_, Session>(
+        "SELECT * FROM sessions WHERE event_id = $1 AND ($2::uuid IS NULL OR id = $2)
+         ORDER BY start_time, track NULLS LAST, title",
+    )
+    .bind(event_id)
+    .bind(only)
+    .fetch_all(db)
+    .await?;
+
+    let speakers = sqlx::query_as::<_, Speaker>("SELECT * FROM speakers WHERE event_id = $1 ORDER BY name")
+        .bind(event_id)
+        .fetch_all(db)
+        .await?;
+
+    let links = sqlx::query_as::<_, (Uuid, Uuid)>(
+        "SELECT ss.session_id, ss.speaker_id FROM session_speakers ss
+         JOIN sessions s ON s.id = ss.session_id WHERE s.event_id = $1",
+    )
+    .bind(event_id)
+    .fetch_all(db)
+    .await?;
+
+    let counts = sqlx::query_as::<_, (Uuid, i64)>(
+        "SELECT sa.session_id, COUNT(*) FROM session_attendees sa
+         JOIN sessions s ON s.id = sa.session_id WHERE s.event_id = $1 GROUP BY sa.session_id",
+    )
+    .bind(event_id)
+    .fetch_all(db)
+    .await?;
+
+    Ok(sessions
+        .into_iter()
+        .map(|session| {
+            let session_speakers = speakers
+                .iter()
+                .filter(|sp| links.iter().any(|(s, p)| *s == session.id && *p == sp.id))
+                .cloned()
+                .collect();
+            let attendees = counts
+                .iter()
+                .find(|(s, _)| *s == session.id)
+                .map(|(_, c)| *c)
+                .unwrap_or(0);
+            SessionDetails { session, speakers: session_speakers, attendees }
+        })
+        .collect())
+}
+
+/// Vreme i kapacitet sesije posle primene izmena
+fn validate_session(start: NaiveDateTime, end: NaiveDateTime, capacity: Option<i32>) -> Result<(), &'static str> {
+    if end <= start {
+        return Err("Kraj sesije mora biti posle pocetka");
+    }
+    if capacity.is_some_and(|c| c <= 0) {
+        return Err("Kapacitet sesije mora biti pozitivan");
+    }
+    Ok(())
+}
+
+/// Svi predavaci moraju pripadati istom dogadjaju
+async fn validate_speaker_ids(db: &PgPool, event_id: Uuid, ids: &[Uuid]) -> Result<(), String> {
+    let mut unique = ids.to_vec();
+    unique.sort();
+    unique.dedup();
+
+    let found = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM speakers WHERE event_id = $1 AND id = ANY($2)")
+        .bind(event_id)
+        .bind(&unique)
+        .fetch_one(db)
+        .await
+        .map_err(|e| format!("Greska: {}", e))?;
+
+    if found as usize != unique.len() {
+        return Err("Neki od predavaca ne postoje na ovom dogadjaju".to_string());
+    }
+    Ok(())
+}
+
+async fn set_session_speakers(db: &PgPool, session_id: Uuid, ids: &[Uuid]) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM session_speakers WHERE session_id = $1")
+        .bind(session_id)
+        .execute(db)
+        .await?;
+
+    sqlx::query(
+        "INSERT INTO session_speakers (session_id, speaker_id)
+         SELECT $1, UNNEST($2::uuid[]) ON CONFLICT DO NOTHING",
+    )
+    .bind(session_id)
+    .bind(ids)
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// GET /events/:id/sessions
+pub async fn list_sessions(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(event_id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<Vec<SessionDetails>>>) {
+    if let Err(resp) = load_visible_event(&state, &headers, event_id).await {
+        return resp;
+    }
+    match load_sessions(&state.db, event_id, None).await {
+        Ok(sessions) => (StatusCode::OK, Json(ApiResponse::success("Lista sesija", sessions))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// POST /events/:id/sessions
+pub async fn create_session(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(event_id): Path<Uuid>,
+    Json(req): Json<CreateSessionRequest>,
+) -> (StatusCode, Json<ApiResponse<SessionDetails>>) {
+    if let Err(resp) = load_managed_event(&state, &headers, event_id, Permission::Edit).await {
+        return resp;
+    }
+
+    if let Err(msg) = validate_session(req.start_time, req.end_time, req.capacity) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(msg)));
+    }
+    if let Err(msg) = validate_speaker_ids(&state.db, event_id, &req.speaker_ids).await {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(&msg)));
+    }
+
+    let result = sqlx::query_as::<_, Session>(
+        "INSERT INTO sessions (id, event_id, title, abstract, room, track, start_time, end_time, capacity)
+         VALUES (gen_random_uuid(), $1, $2, $3, $4, $5, $6, $7, $8)
+         RETURNING *",
+    )
+    .bind(event_id)
+    .bind(&req.title)
+    .bind(&req.r#abstract)
+    .bind(&req.room)
+    .bind(&req.track)
+    .bind(req.start_time)
+    .bind(req.end_time)
+    .bind(req.capacity)
+    .fetch_one(&state.db)
+    .await;
+
+    let session = match result {
+        Ok(s) => s,
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+
+    if let Err(e) = set_session_speakers(&state.db, session.id, &req.speaker_ids).await {
+        return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e))));
+    }
+
+    match load_sessions(&state.db, event_id, Some(session.id)).await {
+        Ok(mut list) if !list.is_empty() => (StatusCode::CREATED, Json(ApiResponse::success("Sesija kreirana", list.remove(0)))),
+        Ok(_) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Sesija ne postoji"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// PUT /events/:id/sessions/:session_id
+pub async fn update_session(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((event_id, session_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<UpdateSessionRequest>,
+) -> (StatusCode, Json<ApiResponse<SessionDetails>>) {
+    if let Err(resp) = load_managed_event(&state, &headers, event_id, Permission::Edit).await {
+        return resp;
+    }
+
+    let current = match load_sessions(&state.db, event_id, Some(session_id)).await {
+        Ok(mut list) if !list.is_empty() => list.remove(0),
+        Ok(_) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Sesija ne postoji"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+
+    let start = req.start_time.unwrap_or(current.session.start_time);
+    let end = req.end_time.unwrap_or(current.session.end_time);
+    if let Err(msg) = validate_session(start, end, req.capacity) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(msg)));
+    }
+    if let Some(ids) = &req.speaker_ids {
+        if let Err(msg) = validate_speaker_ids(&state.db, event_id, ids).await {
+            return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(&msg)));
+        }
+    }
+
+    // isti lock reda kao u attend_session - prijava na sesiju ne moze da prodje izmedju provere i izmene
+    let result = async {
+        let mut tx = state.db.begin().await?;
+        sqlx::query("SELECT id FROM sessions WHERE id = $1 FOR UPDATE")
+            .bind(session_id)
+            .execute(&mut *tx)
+            .await?;
+        if let Some(capacity) = req.capacity {
+            let taken = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM session_attendees WHERE session_id = $1")
+                .bind(session_id)
+                .fetch_one(&mut *tx)
+                .await?;
+            if (capacity as i64) < taken {
+                return Ok(false);
+            }
+        }
+
+        sqlx::query(
+            "UPDATE sessions SET
+                title = COALESCE($2, title),
+                abstract = COALESCE($3, abstract),
+                room = COALESCE($4, room),
+                track = COALESCE($5, track),
+                start_time = $6,
+                end_time = $7,
+                capacity = COALESCE($8, capacity),
+                updated_at = NOW()
+             WHERE id = $1",
+        )
+        .bind(session_id)
+        .bind(&req.title)
+        .bind(&req.r#abstract)
+        .bind(&req.room)
+        .bind(&req.track)
+        .bind(start)
+        .bind(end)
+        .bind(req.capacity)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(true)
+    };
+
+    match result.await {
+        Ok(true) => {}
+        Ok(false) => return (StatusCode::CONFLICT, Json(ApiResponse::error("Kapacitet ne moze biti manji od broja prijavljenih na sesiju"))),
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+
+    if let Some(ids) = &req.speaker_ids {
+        if let Err(e) = set_session_speakers(&state.db, session_id, ids).await {
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e))));
+        }
+    }
+
+    match load_sessions(&state.db, event_id, Some(session_id)).await {
+        Ok(mut list) if !list.is_empty() => (StatusCode::OK, Json(ApiResponse::success("Sesija azurirana", list.remove(0)))),
+        Ok(_) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Sesija ne postoji"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// DELETE /events/:id/sessions/:session_id
+pub async fn delete_session(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((event_id, session_id)): Path<(Uuid, Uuid)>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    if let Err(resp) = load_managed_event(&state, &headers, event_id, Permission::Edit).await {
+        return resp;
+    }
+
+    match sqlx::query("DELETE FROM sessions WHERE id = $1 AND event_id = $2").bind(session_id).bind(event_id).execute(&state.db).await {
+        Ok(r) if r.rows_affected() > 0 => (StatusCode::OK, Json(ApiResponse::success("Sesija obrisana", "ok".to_string()))),
+        Ok(_) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Sesija ne postoji"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// GET /events/:id/agenda - sesije grupisane po danu, pa po traci
+pub async fn get_agenda(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(event_id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<Agenda>>) {
+    if let Err(resp) = load_visible_event(&state, &headers, event_id).await {
+        return resp;
+    }
+    let sessions = match load_sessions(&state.db, event_id, None).await {
+        Ok(s) => s,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+
+    let mut days: Vec<AgendaDay> = Vec::new();
+    for details in sessions {
+        let date = details.session.start_time.date();
+        if days.last().map(|d| d.date) != Some(date) {
+            days.push(AgendaDay { date, tracks: Vec::new() });
+        }
+        let day = days.last_mut().expect("dan je upravo dodat");
+
+        match day.tracks.iter_mut().find(|t| t.track == details.session.track) {
+            Some(track) => track.sessions.push(details),
+            None => day.tracks.push(AgendaTrack {
+                track: details.session.track.clone(),
+                sessions: vec![details],
+            }),
+        }
+    }
+
+    // sesije bez trake idu na kraj dana
+    for day in &mut days {
+        day.tracks.sort_by(|a, b| match (&a.track, &b.track) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+    }
+
+    (StatusCode::OK, Json(ApiResponse::success("Agenda", Agenda { event_id, days })))
+}
+
+/// POST /events/:id/sessions/:session_id/attend - samo za korisnike prijavljene na dogadjaj
+pub async fn attend_session(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((event_id, session_id)): Path<(Uuid, Uuid)>,
+) -> (StatusCode, Json<ApiResponse<SessionDetails>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+    if let Err(resp) = load_visible_event(&state, &headers, event_id).await {
+        return resp;
+    }
+
+    match load_sessions(&state.db, event_id, Some(session_id)).await {
+        Ok(list) if !list.is_empty() => {}
+        Ok(_) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Sesija ne postoji"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+
+    match fetch_registration(&state, event_id, user_id).await {
+        Ok(Some(reg)) if reg.status == "confirmed" => {}
+        Ok(_) => return (StatusCode::FORBIDDEN, Json(ApiResponse::error("Morate biti prijavljeni na dogadjaj"))),
+        Err(e) => {
+            tracing::error!("Ne mogu da kontaktiram registration servis: {}", e);
+            return (StatusCode::SERVICE_UNAVAILABLE, Json(ApiResponse::error("Registration servis nije dostupan")));
+        }
+    }
+
+    // red sesije je zakljucan do kraja transakcije, pa dva istovremena zahteva ne mogu oba
+    // da vide poslednje slobodno mesto
+    let result = async {
+        let mut tx = state.db.begin().await?;
+        let capacity = sqlx::query_scalar::<_, Option<i32>>("SELECT capacity FROM sessions WHERE id = $1 FOR UPDATE")
+            .bind(session_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        let already = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM session_attendees WHERE session_id = $1 AND user_id = $2)",
+        )
+        .bind(session_id)
+        .bind(user_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if already {
+            return Ok(Some("Vec ste prijavljeni na ovu sesiju"));
+        }
+        if let Some(capacity) = capacity {
+            let taken = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM session_attendees WHERE session_id = $1")
+                .bind(session_id)
+                .fetch_one(&mut *tx)
+                .await?;
+            if taken >= capacity as i64 {
+                return Ok(Some("Sesija je popunjena"));
+            }
+        }
+        sqlx::query("INSERT INTO session_attendees (session_id, user_id) VALUES ($1, $2)")
+            .bind(session_id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(None)
+    };
+
+    match result.await {
+        Ok(Some(msg)) => return (StatusCode::CONFLICT, Json(ApiResponse::error(msg))),
+        Ok(None) => {}
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+
+    match load_sessions(&state.db, event_id, Some(session_id)).await {
+        Ok(mut list) if !list.is_empty() => (StatusCode::CREATED, Json(ApiResponse::success("Prijavljeni ste na sesiju", list.remove(0)))),
+        Ok(_) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Sesija ne postoji"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// DELETE /events/:id/sessions/:session_id/attend
+pub async fn leave_session(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((event_id, session_id)): Path<(Uuid, Uuid)>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+
+    let result = sqlx::query(
+        "DELETE FROM session_attendees sa USING sessions s
+         WHERE sa.session_id = s.id AND s.id = $1 AND s.event_id = $2 AND sa.user_id = $3",
+    )
+    .bind(session_id)
+    .bind(event_id)
+    .bind(user_id)
+    .execute(&state.db)
+    .await;
+
+    match result {
+        Ok(r) if r.rows_affected() > 0 => (StatusCode::OK, Json(ApiResponse::success("Odjavljeni ste sa sesije", "ok".to_string()))),
+        Ok(_) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Niste prijavljeni na ovu sesiju"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// GET /events/:id/speakers
+pub async fn list_speakers(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(event_id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<Vec<Speaker>>>) {
+    if let Err(resp) = load_visible_event(&state, &headers, event_id).await {
+        return resp;
+    }
+    let result = sqlx::query_as::<_, Speaker>("SELECT * FROM speakers WHERE event_id = $1 ORDER BY name")
+        .bind(event_id)
+        .fetch_all(&state.db)
+        .await;
+
+    match result {
+        Ok(list) => (StatusCode::OK, Json(ApiResponse::success("Lista predavaca", list))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// POST /events/:id/speakers
+pub async fn create_speaker(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(event_id): Path<Uuid>,
+    Json(req): Json<CreateSpeakerRequest>,
+) -> (StatusCode, Json<ApiResponse<Speaker>>) {
+    if let Err(resp) = load_managed_event(&state, &headers, event_id, Permission::Edit).await {
+        return resp;
+    }
+
+    let result = sqlx::query_as::<_, Speaker>(
+        "INSERT INTO speakers (id, event_id, name, bio, photo_url)
+         VALUES (gen_random_uuid(), $1, $2, $3, $4)
+         RETURNING *",
+    )
+    .bind(event_id)
+    .bind(&req.name)
+    .bind(&req.bio)
+    .bind(&req.photo_url)
+    .fetch_one(&state.db)
+    .await;
+
+    match result {
+        Ok(speaker) => (StatusCode::CREATED, Json(ApiResponse::success("Predavac dodat", speaker))),
+        Err(e) => (StatusCode::BAD_REQUEST, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// PUT /events/:id/speakers/:speaker_id
+pub async fn update_speaker(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((event_id, speaker_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<UpdateSpeakerRequest>,
+) -> (StatusCode, Json<ApiResponse<Speaker>>) {
+    if let Err(resp) = load_managed_event(&state, &headers, event_id, Permission::Edit).await {
+        return resp;
+    }
+
+    let result = sqlx::query_as::<_, Speaker>(
+        "UPDATE speakers SET
+            name = COALESCE($3, name),
+            bio = COALESCE($4, bio),
+            photo_url = COALESCE($5, photo_url)
+         WHERE id = $1 AND event_id = $2 RETURNING *",
+    )
+    .bind(speaker_id)
+    .bind(event_id)
+    .bind(&req.name)
+    .bind(&req.bio)
+    .bind(&req.photo_url)
+    .fetch_optional(&state.db)
+    .await;
+
+    match result {
+        Ok(Some(speaker)) => (StatusCode::OK, Json(ApiResponse::success("Predavac azuriran", speaker))),
+        Ok(None) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Predavac ne postoji"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// DELETE /events/:id/speakers/:speaker_id
+pub async fn delete_speaker(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((event_id, speaker_id)): Path<(Uuid, Uuid)>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    if let Err(resp) = load_managed_event(&state, &headers, event_id, Permission::Edit).await {
+        return resp;
+    }
+
+    match sqlx::query("DELETE FROM speakers WHERE id = $1 AND event_id = $2").bind(speaker_id).bind(event_id).execute(&state.db).await {
+        Ok(r) if r.rows_affected() > 0 => (StatusCode::OK, Json(ApiResponse::success("Predavac obrisan", "ok".to_string()))),
+        Ok(_) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Predavac ne postoji"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+// ---- Tipovi karata ----
+
+const TICKET_VISIBILITIES: [&str; 2] = ["public", "hidden"];
+
+/// Provera tipa karte posle primene izmena
+fn validate_ticket_type(
+    price_cents: i64,
+    currency: &str,
+    quota: i32,
+    sales_start: Option<NaiveDateTime>,
+    sales_end: Option<NaiveDateTime>,
+    visibility: &str,
+) -> Result<(), &'static str> {
+    if quota <= 0 {
+        return Err("Kvota mora biti pozitivna");
+    }
+    if price_cents < 0 {
+        return Err("Cena ne moze biti negativna");
+    }
+    if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err("Valuta mora biti ISO kod od 3 slova (npr. RSD)");
+    }
+    if let (Some(start), Some(end)) = (sales_start, sales_end) {
+        if end <= start {
+            return Err("Kraj prodaje mora biti posle pocetka");
+        }
+    }
+    if !TICKET_VISIBILITIES.contains(&visibility) {
+        return Err("Vidljivost mora biti public ili hidden");
+    }
+    Ok(())
+}
+
+/// GET /events/:id/ticket-types - javni tipovi; organizator vidi i skrivene
+pub async fn list_ticket_types(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(event_id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<Vec<TicketType>>>) {
+    if let Err(resp) = load_visible_event(&state, &headers, event_id).await {
+        return resp;
+    }
+    let show_hidden = load_managed_event::<()>(&state, &headers, event_id, Permission::View).await.is_ok();
+
+    let result = sqlx::query_as::<_, TicketType>(
+        "SELECT * FROM ticket_types WHERE event_id = $1 AND ($2 OR visibility = 'public')
+         ORDER BY sort_order, price_cents, name",
+    )
+    .bind(event_id)
+    .bind(show_hidden)
+    .fetch_all(&state.db)
+    .await;
+
+    match result {
+        Ok(list) => (StatusCode::OK, Json(ApiResponse::success("Lista tipova karata", list))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// Internal: svi tipovi karata za dogadjaj, ukljucujuci skrivene (poziva registration-service)
+pub async fn internal_ticket_types(
+    State(state): State<AppState>,
+    Path(event_id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<Vec<TicketType>>>) {
+    let result = sqlx::query_as::<_, TicketType>("SELECT * FROM ticket_types WHERE event_id = $1 ORDER BY sort_order")
+        .bind(event_id)
+        .fetch_all(&state.db)
+        .await;
+
+    match result {
+        Ok(list) => (StatusCode::OK, Json(ApiResponse::success("Lista tipova karata", list))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// Internal: naziv, datum i kapacitet jednog dogadjaja ili svih dogadjaja organizatora (analitika)
+pub async fn internal_event_summary(
+    State(state): State<AppState>,
+    Query(query): Query<EventSummaryQuery>,
+) -> (StatusCode, Json<ApiResponse<Vec<EventSummary>>>) {
+    if query.id.is_none() && query.organizer_id.is_none() {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error("Potreban je id ili organizer_id")));
+    }
+
+    let result = sqlx::query_as::<_, EventSummary>(
+        "SELECT id, title, date_time, capacity, organizer_id, date_time <= $3 AS started FROM events
+         WHERE deleted_at IS NULL AND ($1::uuid IS NULL OR id = $1) AND ($2::uuid IS NULL OR organizer_id = $2)
+         ORDER BY date_time DESC",
+    )
+    .bind(query.id)
+    .bind(query.organizer_id)
+    .bind(local_now())
+    .fetch_all(&state.db)
+    .await;
+
+    match result {
+        Ok(list) => (StatusCode::OK, Json(ApiResponse::success("Dogadjaji", list))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// GET /events/:id/ticket-types/:type_id - radi i za skrivene tipove (direktan link)
+pub async fn get_ticket_type(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((event_id, type_id)): Path<(Uuid, Uuid)>,
+) -> (StatusCode, Json<ApiResponse<TicketType>>) {
+    if let Err(resp) = load_visible_event(&state, &headers, event_id).await {
+        return resp;
+    }
+    let result = sqlx::query_as::<_, TicketType>("SELECT * FROM ticket_types WHERE id = $1 AND event_id = $2")
+        .bind(type_id)
+        .bind(event_id)
+        .fetch_optional(&state.db)
+        .await;
+
+    match result {
+        Ok(Some(tt)) => (StatusCode::OK, Json(ApiResponse::success("Tip karte pronadjen", tt))),
+        Ok(None) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Tip karte ne postoji"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// POST /events/:id/ticket-types
+pub async fn create_ticket_type(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(event_id): Path<Uuid>,
+    Json(req): Json<CreateTicketTypeRequest>,
+) -> (StatusCode, Json<ApiResponse<TicketType>>) {
+    if let Err(resp) = load_managed_event(&state, &headers, event_id, Permission::Edit).await {
+        return resp;
+    }
+
+    let currency = req.currency.clone().unwrap_or_else(|| "RSD".to_string());
+    let visibility = req.visibility.clone().unwrap_or_else(|| "public".to_string());
+    if let Err(msg) = validate_ticket_type(req.price_cents, &currency, req.quota, req.sales_start, req.sales_end, &visibility) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(msg)));
+    }
+
+    let result = sqlx::query_as::<_, TicketType>(
+        "INSERT INTO ticket_types (id, event_id, name, description, price_cents, currency, quota, sales_start, sales_end, visibility, sort_order)
+         VALUES (gen_random_uuid(), $1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+         RETURNING *",
+    )
+    .bind(event_id)
+    .bind(&req.name)
+    .bind(&req.description)
+    .bind(req.price_cents)
+    .bind(&currency)
+    .bind(req.quota)
+    .bind(req.sales_start)
+    .bind(req.sales_end)
+    .bind(&visibility)
+    .bind(req.sort_order)
+    .fetch_one(&state.db)
+    .await;
+
+    match result {
+        Ok(tt) => (StatusCode::CREATED, Json(ApiResponse::success("Tip karte kreiran", tt))),
+        Err(e) => (StatusCode::BAD_REQUEST, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// PUT /events/:id/ticket-types/:type_id
+pub async fn update_ticket_type(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((event_id, type_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<UpdateTicketTypeRequest>,
+) -> (StatusCode, Json<ApiResponse<TicketType>>) {
+    if let Err(resp) = load_managed_event(&state, &headers, event_id, Permission::Edit).await {
+        return resp;
+    }
+
+    let current = match sqlx::query_as::<_, TicketType>("SELECT * FROM ticket_types WHERE id = $1 AND event_id = $2").bind(type_id).bind(event_id).fetch_optional(&state.db).await {
+        Ok(Some(tt)) => tt,
+        Ok(None) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Tip karte ne postoji"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+
+    let sales_start = req.sales_start.unwrap_or(current.sales_start);
+    let sales_end = req.sales_end.unwrap_or(current.sales_end);
+    if let Err(msg) = validate_ticket_type(
+        req.price_cents.unwrap_or(current.price_cents),
+        req.currency.as_deref().unwrap_or(&current.currency),
+        req.quota.unwrap_or(current.quota),
+        sales_start,
+        sales_end,
+        req.visibility.as_deref().unwrap_or(&current.visibility),
+    ) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(msg)));
+    }
+    if let Err((status, msg)) = check_quota_change(&state, &current, req.quota).await {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+
+    let result = sqlx::query_as::<_, TicketType>(
+        "UPDATE ticket_types SET
+            name = COALESCE($2, name),
+            description = COALESCE($3, description),
+            price_cents = COALESCE($4, price_cents),
+            currency = COALESCE($5, currency),
+            quota = COALESCE($6, quota),
+            sales_start = $7,
+            sales_end = $8,
+            visibility = COALESCE($9, visibility),
+            sort_order = COALESCE($10, sort_order),
+            updated_at = NOW()
+         WHERE id = $1 RETURNING *",
+    )
+    .bind(type_id)
+    .bind(&req.name)
+    .bind(&req.description)
+    .bind(req.price_cents)
+    .bind(&req.currency)
+    .bind(req.quota)
+    .bind(sales_start)
+    .bind(sales_end)
+    .bind(&req.visibility)
+    .bind(req.sort_order)
+    .fetch_one(&state.db)
+    .await;
+
+    match result {
+        Ok(tt) => (StatusCode::OK, Json(ApiResponse::success("Tip karte azuriran", tt))),
+        Err(e) => (StatusCode::BAD_REQUEST, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// Kvota ne sme da padne ispod broja vec prodatih karata tog tipa
+async fn check_quota_change(state: &AppState, current: &TicketType, quota: Option<i32>) -> Result<(), (StatusCode, String)> {
+    let Some(quota) = quota.filter(|q| *q < current.quota) else {
+        return Ok(());
+    };
+
+    let sold = ticket_type_counts(state, current).await?.confirmed;
+    if (quota as i64) < sold {
+        return Err((
+            StatusCode::CONFLICT,
+            format!("Kvota {} je manja od broja prodatih karata \"{}\" ({})", quota, current.name, sold),
+        ));
+    }
+    Ok(())
+}
+
+/// Prijave sa ovim tipom karte u registration-service
+async fn ticket_type_counts(state: &AppState, ticket_type: &TicketType) -> Result<RegistrationCounts, (StatusCode, String)> {
+    let url = format!(
+        "{}/internal/event/{}/counts?ticket_type_id={}",
+        state.registration_service_url, ticket_type.event_id, ticket_type.id
+    );
+    let counts = match state.http_client.get(&url).send().await {
+        Ok(resp) => resp.json::<RegistrationCountsResponse>().await.ok(),
+        Err(_) => None,
+    };
+    match counts {
+        Some(RegistrationCountsResponse { success: true, data: Some(c) }) => Ok(c),
+        _ => Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Registration servis nije dostupan - ne mogu da proverim broj prodatih karata".to_string(),
+        )),
+    }
+}
+
+/// DELETE /events/:id/ticket-types/:type_id - samo dok nijedna karta nije prodata (inace se sakriva)
+pub async fn delete_ticket_type(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((event_id, type_id)): Path<(Uuid, Uuid)>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    if let Err(resp) = load_managed_event(&state, &headers, event_id, Permission::Edit).await {
+        return resp;
+    }
+
+    let ticket_type = match sqlx::query_as::<_, TicketType>("SELECT * FROM ticket_types WHERE id = $1 AND event_id = $2")
+        .bind(type_id)
+        .bind(event_id)
+        .fetch_optional(&state.db)
+        .await
+    {
+        Ok(Some(tt)) => tt,
+        Ok(None) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Tip karte ne postoji"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+    match ticket_type_counts(&state, &ticket_type).await {
+        Ok(c) if c.confirmed + c.waitlisted > 0 => {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(&format!(
+                    "Karte \"{}\" imaju {} prijava - tip se ne moze obrisati, postavite visibility na hidden",
+                    ticket_type.name,
+                    c.confirmed + c.waitlisted
+                ))),
+            )
+        }
+        Ok(_) => {}
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    }
+
+    match sqlx::query("DELETE FROM ticket_types WHERE id = $1 AND event_id = $2").bind(type_id).bind(event_id).execute(&state.db).await {
+        Ok(r) if r.rows_affected() > 0 => (StatusCode::OK, Json(ApiResponse::success("Tip karte obrisan", "ok".to_string()))),
+        Ok(_) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Tip karte ne postoji"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+// ---- Kloniranje i sabloni ----
+
+/// POST /events/:id/clone - kopija dogadjaja na novi datum, kao draft.
+/// Sesije (sa predavacima) i tipovi karata se pomeraju za istu razliku u vremenu.
+pub async fn clone_event(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<CloneEventRequest>,
+) -> (StatusCode, Json<ApiResponse<Event>>) {
+    let (event, claims) = match load_managed_event(&state, &headers, id, Permission::Edit).await {
+        Ok(v) => v,
+        Err(err) => return err,
+    };
+    // klon je novi dogadjaj, a njih prave samo Admini
+    if let Err((status, msg)) = require_role(&claims, "Admin") {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+
+    if req.date_time < chrono::Utc::now().naive_utc() {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error("Datum dogadjaja ne moze biti u proslosti")));
+    }
+    if req.title.as_deref().is_some_and(|t| t.trim().is_empty()) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error("Naziv je obavezan")));
+    }
+
+    let organizer_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+    let delta_secs = (req.date_time - event.date_time).num_seconds() as f64;
+
+    match copy_event(&state.db, &event, organizer_id, &req, delta_secs).await {
+        Ok(clone) => (StatusCode::CREATED, Json(ApiResponse::success("Dogadjaj kloniran kao draft", clone))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+async fn copy_event(
+    db: &PgPool,
+    event: &Event,
+    organizer_id: Uuid,
+    req: &CloneEventRequest,
+    delta_secs: f64,
+) -> Result<Event, sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    // klon deli fajlove slike sa originalom (i tagove) (release_images ih brise tek kad ih niko ne koristi)
+    let mut clone = sqlx::query_as::<_, Event>(
+        "INSERT INTO events (organizer_id, title, description, description_html, location, date_time, capacity, category,
+                             image_url, image_thumbnails, image_keys, latitude, longitude, status, visibility, access_code)
+         SELECT $2, COALESCE($3, title), description, description_html, location, $4, capacity, category,
+                image_url, image_thumbnails, image_keys, latitude, longitude, 'draft', visibility, access_code
+         FROM events WHERE id = $1
+         RETURNING *",
+    )
+    .bind(event.id)
+    .bind(organizer_id)
+    .bind(&req.title)
+    .bind(req.date_time)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query("INSERT INTO event_tags (event_id, tag_id) SELECT $2, tag_id FROM event_tags WHERE event_id = $1")
+        .bind(event.id)
+        .bind(clone.id)
+        .execute(&mut *tx)
+        .await?;
+
+    // klon nije u seriji, pa limit po seriji ne prelazi
+    sqlx::query(
+        "INSERT INTO event_eligibility (event_id, rules)
+         SELECT $2, rules - 'max_per_series' FROM event_eligibility WHERE event_id = $1",
+    )
+    .bind(event.id)
+    .bind(clone.id)
+    .execute(&mut *tx)
+    .await?;
+
+    if req.include_sessions {
+        // nova id-eva za predavace i sesije, pa veze izmedju njih preko starih id-eva
+        sqlx::query(
+            "WITH sp AS (
+                SELECT id AS old_id, gen_random_uuid() AS new_id, name, bio, photo_url
+                FROM speakers WHERE event_id = $1
+             ), sp_ins AS (
+                INSERT INTO speakers (id, event_id, name, bio, photo_url)
+                SELECT new_id, $2, name, bio, photo_url FROM sp
+             ), se AS (
+                SELECT id AS old_id, gen_random_uuid() AS new_id, title, abstract, room, track,
+                       start_time, end_time, capacity
+                FROM sessions WHERE event_id = $1
+             ), se_ins AS (
+                INSERT INTO sessions (id, event_id, title, abstract, room, track, start_time, end_time, capacity)
+                SELECT new_id, $2, title, abstract, room, track,
+                       start_time + $3 * INTERVAL '1 second', end_time + $3 * INTERVAL '1 second', capacity
+                FROM se
+             )
+             INSERT INTO session_speakers (session_id, speaker_id)
+             SELECT se.new_id, sp.new_id
+             FROM session_speakers ss
+             JOIN se ON se.old_id = ss.session_id
+             JOIN sp ON sp.old_id = ss.speaker_id",
+        )
+        .bind(event.id)
+        .bind(clone.id)
+        .bind(delta_secs)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if req.include_ticket_types {
+        sqlx::query(
+            "INSERT INTO ticket_types (event_id, name, description, price_cents, currency, quota,
+                                       sales_start, sales_end, visibility, sort_order)
+             SELECT $2, name, description, price_cents, currency, quota,
+                    sales_start + $3 * INTERVAL '1 second', sales_end + $3 * INTERVAL '1 second',
+                    visibility, sort_order
+             FROM ticket_types WHERE event_id = $1",
+        )
+        .bind(event.id)
+        .bind(clone.id)
+        .bind(delta_secs)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    attach_tags(db, std::slice::from_mut(&mut clone)).await?;
+    Ok(clone)
+}
+
+/// Ucitava sablon koji pripada korisniku (Admin moze sve)
+async fn load_own_template<T: Serialize>(
+    state: &AppState,
+    headers: &HeaderMap,
+    id: Uuid,
+) -> Result<(EventTemplate, Claims), (StatusCode, Json<ApiResponse<T>>)> {
+    let claims = extract_claims(headers, &state.jwt_secret)
+        .map_err(|(status, msg)| (status, Json(ApiResponse::error(&msg))))?;
+
+    let template = match sqlx::query_as::<_, EventTemplate>("SELECT * FROM event_templates WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+    {
+        Ok(Some(t)) => t,
+        Ok(None) => return Err((StatusCode::NOT_FOUND, Json(ApiResponse::error("Sablon ne postoji")))),
+        Err(e) => return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e))))),
+    };
+
+    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+    if template.organizer_id != user_id && claims.role != "Admin" {
+        return Err((StatusCode::FORBIDDEN, Json(ApiResponse::error("Nemate pristup ovom sablonu"))));
+    }
+
+    Ok((template, claims))
+}
+
+fn validate_template(req: &TemplateRequest) -> Result<(), &'static str> {
+    if req.name.as_deref().is_some_and(|n| n.trim().is_empty()) {
+        return Err("Naziv sablona je obavezan");
+    }
+    if let Some(capacity) = req.capacity {
+        validate_capacity(capacity)?;
+    }
+    validate_coordinates(req.latitude, req.longitude)?;
+    validate_image_url(req.image_url.as_deref())
+}
+
+async fn resolve_template_category(db: &PgPool, req: &mut TemplateRequest) -> Result<(), (StatusCode, String)> {
+    if let Some(raw) = &req.category {
+        req.category = Some(canonical_category(db, raw).await?);
+    }
+    Ok(())
+}
+
+/// GET /templates - moji sabloni
+pub async fn list_templates(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+) -> (StatusCode, Json<ApiResponse<Vec<EventTemplate>>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+
+    let result = sqlx::query_as::<_, EventTemplate>(
+        "SELECT * FROM event_templates WHERE organizer_id = $1 ORDER BY name",
+    )
+    .bind(user_id)
+    .fetch_all(&state.db)
+    .await;
+
+    match result {
+        Ok(list) => (StatusCode::OK, Json(ApiResponse::success("Sabloni", list))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// POST /templates - novi sablon; sa from_event_id polja se preuzimaju iz dogadjaja (poslata polja imaju prednost)
+pub async fn create_template(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Json(mut req): Json<TemplateRequest>,
+) -> (StatusCode, Json<ApiResponse<EventTemplate>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    if let Err((status, msg)) = require_role(&claims, "Admin") {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+    let organizer_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+
+    if req.name.is_none() {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error("Naziv sablona je obavezan")));
+    }
+    if let Err(msg) = validate_template(&req) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(msg)));
+    }
+    if let Err((status, msg)) = resolve_template_category(&state.db, &mut req).await {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+
+    let source = match req.from_event_id {
+        Some(event_id) => match load_managed_event(&state, &headers, event_id, Permission::View).await {
+            Ok((event, _)) => Some(event),
+            Err(err) => return err,
+        },
+        None => None,
+    };
+
+    // slika iz dogadjaja zadrzava thumbnail-ove i deli fajlove; nova slika (URL) ih nema
+    let (image_url, image_thumbnails, image_keys) = match (&req.image_url, &source) {
+        (Some(url), _) => (Some(url.clone()), None, Vec::new()),
+        (None, Some(event)) => (event.image_url.clone(), event.image_thumbnails.clone(), event.image_keys.clone()),
+        (None, None) => (None, None, Vec::new()),
+    };
+    let pick = |own: &Option<String>, from: fn(&Event) -> &String| {
+        own.clone().or_else(|| source.as_ref().map(|e| from(e).clone()))
+    };
+
+    let result = sqlx::query_as::<_, EventTemplate>(
+        "INSERT INTO event_templates (organizer_id, name, title, description, location, capacity, category,
+                                      image_url, image_thumbnails, image_keys, latitude, longitude)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+         RETURNING *",
+    )
+    .bind(organizer_id)
+    .bind(req.name.as_deref().map(str::trim))
+    .bind(pick(&req.title, |e| &e.title))
+    .bind(pick(&req.description, |e| &e.description))
+    .bind(pick(&req.location, |e| &e.location))
+    .bind(req.capacity.or(source.as_ref().map(|e| e.capacity)))
+    .bind(pick(&req.category, |e| &e.category))
+    .bind(image_url)
+    .bind(image_thumbnails)
+    .bind(image_keys)
+    .bind(req.latitude.or(source.as_ref().and_then(|e| e.latitude)))
+    .bind(req.longitude.or(source.as_ref().and_then(|e| e.longitude)))
+    .fetch_one(&state.db)
+    .await;
+
+    match result {
+        Ok(template) => (StatusCode::CREATED, Json(ApiResponse::success("Sablon sacuvan", template))),
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            (StatusCode::CONFLICT, Json(ApiResponse::error("Sablon sa tim nazivom vec postoji")))
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// GET /templates/:id
+pub async fn get_template(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<EventTemplate>>) {
+    match load_own_template(&state, &headers, id).await {
+        Ok((template, _)) => (StatusCode::OK, Json(ApiResponse::success("Sablon", template))),
+        Err(err) => err,
+    }
+}
+
+/// GET /templates/:id/prefill - polja za CreateEventRequest (datum bira organizator).
+/// template_id treba poslati uz POST /events da bi dogadjaj preuzeo i thumbnail-ove slike.
+pub async fn template_prefill(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<EventPrefill>>) {
+    let template = match load_own_template(&state, &headers, id).await {
+        Ok((t, _)) => t,
+        Err(err) => return err,
+    };
+
+    let prefill = EventPrefill {
+        title: template.title,
+        description: template.description,
+        location: template.location,
+        date_time: None,
+        capacity: template.capacity,
+        category: template.category,
+        image_url: template.image_url,
+        latitude: template.latitude,
+        longitude: template.longitude,
+        template_id: template.id,
+    };
+    (StatusCode::OK, Json(ApiResponse::success("Popunjen dogadjaj iz sablona", prefill)))
+}
+
+/// PUT /templates/:id
+pub async fn update_template(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(mut req): Json<TemplateRequest>,
+) -> (StatusCode, Json<ApiResponse<EventTemplate>>) {
+    let template = match load_own_template(&state, &headers, id).await {
+        Ok((t, _)) => t,
+        Err(err) => return err,
+    };
+    if let Err(msg) = validate_template(&req) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(msg)));
+    }
+    if let Err((status, msg)) = resolve_template_category(&state.db, &mut req).await {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+
+    let result = sqlx::query_as::<_, EventTemplate>(
+        "UPDATE event_templates SET
+            name = COALESCE($2, name),
+            title = COALESCE($3, title),
+            description = COALESCE($4, description),
+            location = COALESCE($5, location),
+            capacity = COALESCE($6, capacity),
+            category = COALESCE($7, category),
+            image_url = COALESCE($8, image_url),
+            image_thumbnails = CASE WHEN $8 IS NULL OR $8 = image_url THEN image_thumbnails END,
+            image_keys = CASE WHEN $8 IS NULL OR $8 = image_url THEN image_keys ELSE '{}' END,
+            latitude = COALESCE($9, latitude),
+            longitude = COALESCE($10, longitude),
+            updated_at = NOW()
+         WHERE id = $1 RETURNING *",
+    )
+    .bind(id)
+    .bind(req.name.as_deref().map(str::trim))
+    .bind(&req.title)
+    .bind(&req.description)
+    .bind(&req.location)
+    .bind(req.capacity)
+    .bind(&req.category)
+    .bind(&req.image_url)
+    .bind(req.latitude)
+    .bind(req.longitude)
+    .fetch_one(&state.db)
+    .await;
+
+    match result {
+        Ok(updated) => {
+            if req.image_url.is_some() && req.image_url != template.image_url {
+                release_images(&state, &template.image_keys).await;
+            }
+            (StatusCode::OK, Json(ApiResponse::success("Sablon azuriran", updated)))
+        }
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            (StatusCode::CONFLICT, Json(ApiResponse::error("Sablon sa tim nazivom vec postoji")))
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// DELETE /templates/:id
+pub async fn delete_template(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let template = match load_own_template(&state, &headers, id).await {
+        Ok((t, _)) => t,
+        Err(err) => return err,
+    };
+
+    match sqlx::query("DELETE FROM event_templates WHERE id = $1").bind(id).execute(&state.db).await {
+        Ok(_) => {
+            release_images(&state, &template.image_keys).await;
+            (StatusCode::OK, Json(ApiResponse::success("Sablon obrisan", "ok".to_string())))
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+// ---- Osoblje dogadjaja ----
+
+/// GET /events/:id/staff - osoblje dogadjaja (vidi svako ko je i sam u osoblju)
+pub async fn list_staff(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(event_id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<Vec<StaffMember>>>) {
+    if let Err(resp) = load_managed_event(&state, &headers, event_id, Permission::View).await {
+        return resp;
+    }
+
+    let result = sqlx::query_as::<_, StaffMember>(
+        "SELECT * FROM event_staff WHERE event_id = $1 ORDER BY created_at",
+    )
+    .bind(event_id)
+    .fetch_all(&state.db)
+    .await;
+
+    match result {
+        Ok(list) => (StatusCode::OK, Json(ApiResponse::success("Osoblje dogadjaja", list))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// Trazi korisnika u auth-service po email-u
+async fn fetch_user_by_email(state: &AppState, email: &str) -> Result<Option<UserData>, String> {
+    let url = format!("{}/internal/users/lookup", state.auth_service_url);
+    let resp = state
+        .http_client
+        .get(&url)
+        .header(INTERNAL_TOKEN_HEADER, &state.internal_token)
+        .query(&[("email", email)])
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let body: AuthServiceResponse = resp.json().await.map_err(|e| e.to_string())?;
+    Ok(body.data.filter(|_| body.success))
+}
+
+/// POST /events/:id/staff - dodaje osobu (ili joj menja ulogu).
+/// co_organizer-a moze da postavi ili skine samo organizator (ili Admin).
+pub async fn add_staff(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(event_id): Path<Uuid>,
+    Json(req): Json<AddStaffRequest>,
+) -> (StatusCode, Json<ApiResponse<StaffMember>>) {
+    let (event, claims) = match load_managed_event(&state, &headers, event_id, Permission::ManageStaff).await {
+        Ok(v) => v,
+        Err(err) => return err,
+    };
+
+    if !STAFF_ROLES.contains(&req.role.as_str()) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(&format!("Uloga mora biti jedna od: {}", STAFF_ROLES.join(", ")))),
+        );
+    }
+
+    let user = match fetch_user_by_email(&state, &req.email).await {
+        Ok(Some(u)) => u,
+        Ok(None) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Korisnik sa tim emailom ne postoji"))),
+        Err(e) => return (StatusCode::BAD_GATEWAY, Json(ApiResponse::error(&format!("Auth servis nije dostupan: {}", e)))),
+    };
+    if user.id == event.organizer_id {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error("Organizator vec ima sve dozvole")));
+    }
+
+    let is_owner = event_role(&state.db, &event, &claims).await.as_deref() == Some(OWNER);
+    let current = sqlx::query_scalar::<_, String>("SELECT role FROM event_staff WHERE event_id = $1 AND user_id = $2")
+        .bind(event_id)
+        .bind(user.id)
+        .fetch_optional(&state.db)
+        .await
+        .ok()
+        .flatten();
+    if !is_owner && (req.role == "co_organizer" || current.as_deref() == Some("co_organizer")) {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::error("Samo organizator upravlja suorganizatorima")));
+    }
+
+    let added_by = Uuid::parse_str(&claims.sub).unwrap_or_default();
+    let result = sqlx::query_as::<_, StaffMember>(
+        "INSERT INTO event_staff (event_id, user_id, email, role, added_by)
+         VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT (event_id, user_id) DO UPDATE SET role = EXCLUDED.role, email = EXCLUDED.email
+         RETURNING *",
+    )
+    .bind(event_id)
+    .bind(user.id)
+    .bind(&user.email)
+    .bind(&req.role)
+    .bind(added_by)
+    .fetch_one(&state.db)
+    .await;
+
+    match result {
+        Ok(member) if current.is_some() => (StatusCode::OK, Json(ApiResponse::success("Uloga promenjena", member))),
+        Ok(member) => (StatusCode::CREATED, Json(ApiResponse::success("Osoba dodata u osoblje", member))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// DELETE /events/:id/staff/:user_id - uklanja osobu iz osoblja (svako moze da ukloni sebe)
+pub async fn remove_staff(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((event_id, user_id)): Path<(Uuid, Uuid)>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+
+    let role = sqlx::query_scalar::<_, String>("SELECT role FROM event_staff WHERE event_id = $1 AND user_id = $2")
+        .bind(event_id)
+        .bind(user_id)
+        .fetch_optional(&state.db)
+        .await;
+    let role = match role {
+        Ok(Some(r)) => r,
+        Ok(None) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Osoba nije u osoblju dogadjaja"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+
+    let leaving_self = Uuid::parse_str(&claims.sub).ok() == Some(user_id);
+    if !leaving_self {
+        let (event, claims) = match load_managed_event(&state, &headers, event_id, Permission::ManageStaff).await {
+            Ok(v) => v,
+            Err(err) => return err,
+        };
+        let is_owner = event_role(&state.db, &event, &claims).await.as_deref() == Some(OWNER);
+        if role == "co_organizer" && !is_owner {
+            return (StatusCode::FORBIDDEN, Json(ApiResponse::error("Samo organizator upravlja suorganizatorima")));
+        }
+    }
+
+    match sqlx::query("DELETE FROM event_staff WHERE event_id = $1 AND user_id = $2")
+        .bind(event_id)
+        .bind(user_id)
+        .execute(&state.db)
+        .await
+    {
+        Ok(_) => (StatusCode::OK, Json(ApiResponse::success("Osoba uklonjena iz osoblja", "ok".to_string()))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// GET /internal/events/:id/access/:user_id - uloga korisnika na dogadjaju (za registration-service).
+/// Admin se proverava na strani pozivaoca (iz JWT-a), ovde samo organizator i osoblje.
+pub async fn internal_event_access(
+    State(state): State<AppState>,
+    Path((event_id, user_id)): Path<(Uuid, Uuid)>,
+) -> (StatusCode, Json<ApiResponse<EventAccess>>) {
+    let result = sqlx::query_scalar::<_, String>(
+        "SELECT $3 FROM events WHERE id = $1 AND organizer_id = $2 AND deleted_at IS NULL
+         UNION ALL
+         SELECT s.role FROM event_staff s JOIN events e ON e.id = s.event_id
+         WHERE s.event_id = $1 AND s.user_id = $2 AND e.deleted_at IS NULL",
+    )
+    .bind(event_id)
+    .bind(user_id)
+    .bind(OWNER)
+    .fetch_optional(&state.db)
+    .await;
+
+    match result {
+        Ok(role) => (StatusCode::OK, Json(ApiResponse::success("Pristup", EventAccess { role }))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+// ---- Pristup: link, pristupni kod i pozivnice ----
+
+async fn access_settings(db: &PgPool, event: &Event) -> Result<EventAccessSettings, sqlx::Error> {
+    let invitations = sqlx::query_as::<_, Invitation>(
+        "SELECT * FROM event_invitations WHERE event_id = $1 ORDER BY created_at",
+    )
+    .bind(event.id)
+    .fetch_all(db)
+    .await?;
+
+    Ok(EventAccessSettings {
+        visibility: event.visibility.clone(),
+        slug: event.slug.clone(),
+        access_code: event.access_code.clone(),
+        invitations,
+    })
+}
+
+/// GET /events/:id/access - vidljivost, link, pristupni kod i pozivnice
+pub async fn get_event_access(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<EventAccessSettings>>) {
+    let (event, _) = match load_managed_event(&state, &headers, id, Permission::Edit).await {
+        Ok(v) => v,
+        Err(err) => return err,
+    };
+
+    match access_settings(&state.db, &event).await {
+        Ok(settings) => (StatusCode::OK, Json(ApiResponse::success("Podesavanja pristupa", settings))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// POST /events/:id/access/slug - novi tajni link (stari prestaje da radi)
+pub async fn rotate_event_slug(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<EventAccessSettings>>) {
+    if let Err(resp) = load_managed_event(&state, &headers, id, Permission::Edit).await {
+        return resp;
+    }
+
+    let updated = sqlx::query_as::<_, Event>(
+        "UPDATE events SET slug = replace(gen_random_uuid()::text, '-', ''),
+            version = version + 1, updated_at = NOW()
+         WHERE id = $1 RETURNING *",
+    )
+    .bind(id)
+    .fetch_one(&state.db)
+    .await;
+
+    let result = match updated {
+        Ok(event) => access_settings(&state.db, &event).await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(settings) => (StatusCode::OK, Json(ApiResponse::success("Novi link napravljen", settings))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// POST /events/:id/invitations - poziva korisnike po email-u (postojece pozivnice se preskacu)
+pub async fn invite_to_event(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<InviteRequest>,
+) -> (StatusCode, Json<ApiResponse<Vec<Invitation>>>) {
+    let (_, claims) = match load_managed_event(&state, &headers, id, Permission::Edit).await {
+        Ok(v) => v,
+        Err(err) => return err,
+    };
+
+    let emails: Vec<String> = req
+        .emails
+        .iter()
+        .map(|e| e.trim().to_lowercase())
+        .filter(|e| !e.is_empty())
+        .collect();
+    if emails.is_empty() {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error("Navedite bar jedan email")));
+    }
+    if let Some(bad) = emails.iter().find(|e| !e.contains('@') || e.len() > 255) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(&format!("Neispravan email: {}", bad))));
+    }
+
+    let invited_by = Uuid::parse_str(&claims.sub).unwrap_or_default();
+    let result = sqlx::query_as::<_, Invitation>(
+        "INSERT INTO event_invitations (event_id, email, invited_by)
+         SELECT $1, e, $3 FROM UNNEST($2::text[]) AS e
+         ON CONFLICT (event_id, email) DO NOTHING
+         RETURNING *",
+    )
+    .bind(id)
+    .bind(&emails)
+    .bind(invited_by)
+    .fetch_all(&state.db)
+    .await;
+
+    match result {
+        Ok(created) => (StatusCode::CREATED, Json(ApiResponse::success("Pozivnice poslate", created))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// DELETE /events/:id/invitations/:email
+pub async fn revoke_invitation(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((id, email)): Path<(Uuid, String)>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    if let Err(resp) = load_managed_event(&state, &headers, id, Permission::Edit).await {
+        return resp;
+    }
+
+    let result = sqlx::query("DELETE FROM event_invitations WHERE event_id = $1 AND email = $2")
+        .bind(id)
+        .bind(email.trim().to_lowercase())
+        .execute(&state.db)
+        .await;
+
+    match result {
+        Ok(r) if r.rows_affected() == 0 => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Pozivnica ne postoji"))),
+        Ok(_) => (StatusCode::OK, Json(ApiResponse::success("Pozivnica povucena", "ok".to_string()))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// POST /internal/events/:id/admission - sme li korisnik da se prijavi (registration-service).
+/// Draft je "ne postoji"; privatni trazi osoblje, pozivnicu ili tacan pristupni kod.
+pub async fn internal_admission(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<AdmissionRequest>,
+) -> (StatusCode, Json<ApiResponse<Event>>) {
+    let event = match sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id = $1 AND deleted_at IS NULL").bind(id).fetch_optional(&state.db).await {
+        Ok(Some(e)) if e.status == "published" => e,
+        Ok(_) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Dogadjaj ne postoji"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+
+    if event.visibility != "private" {
+        return (StatusCode::OK, Json(ApiResponse::success("Prijava dozvoljena", event)));
+    }
+
+    let code_ok = match (&event.access_code, &req.access_code) {
+        (Some(expected), Some(given)) => expected == given.trim(),
+        _ => false,
+    };
+    let is_staff = event.organizer_id == req.user_id
+        || sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM event_staff WHERE event_id = $1 AND user_id = $2)")
+            .bind(id)
+            .bind(req.user_id)
+            .fetch_one(&state.db)
+            .await
+            .unwrap_or(false);
+
+    if code_ok || is_staff || is_invited(&state.db, id, &req.email).await {
+        return (StatusCode::OK, Json(ApiResponse::success("Prijava dozvoljena", event)));
+    }
+
+    let msg = if req.access_code.is_some() {
+        "Pogresan pristupni kod"
+    } else {
+        "Privatan dogadjaj - potrebna je pozivnica ili pristupni kod"
+    };
+    (StatusCode::FORBIDDEN, Json(ApiResponse::error(msg)))
+}
+
+// ---- Istorija izmena ----
+
+/// GET /events/:id/revisions - sve verzije, najnovija prva
+pub async fn list_revisions(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<Vec<EventRevision>>>) {
+    if let Err(err) = load_managed_event(&state, &headers, id, Permission::View).await {
+        return err;
+    }
+
+    let result = sqlx::query_as::<_, EventRevision>(
+        "SELECT * FROM event_revisions WHERE event_id = $1 ORDER BY version DESC",
+    )
+    .bind(id)
+    .fetch_all(&state.db)
+    .await;
+
+    match result {
+        Ok(list) => (StatusCode::OK, Json(ApiResponse::success("Istorija izmena", list))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// POST /events/:id/revisions/:version/restore - vraca stanje iz te verzije kao novu izmenu.
+/// If-Match nije obavezan, ali ako je poslat mora da odgovara.
+pub async fn restore_revision(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((id, version)): Path<(Uuid, i32)>,
+    Query(query): Query<RestoreRevisionQuery>,
+) -> Response {
+    let (event, claims) = match load_managed_event::<()>(&state, &headers, id, Permission::Edit).await {
+        Ok(v) => v,
+        Err(err) => return err.into_response(),
+    };
+
+    let expected = match if_match_version(&headers) {
+        Ok(v) => v,
+        Err(msg) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::<()>::error(msg))).into_response(),
+    };
+    if expected.is_some_and(|v| v != event.version) {
+        return version_conflict(event.version);
+    }
+
+    let snapshot = sqlx::query_scalar::<_, sqlx::types::Json<EventSnapshot>>(
+        "SELECT snapshot FROM event_revisions WHERE event_id = $1 AND version = $2",
+    )
+    .bind(id)
+    .bind(version)
+    .fetch_optional(&state.db)
+    .await;
+
+    let mut req = match snapshot {
+        Ok(Some(s)) => UpdateEventRequest {
+            capacity_strategy: query.capacity_strategy,
+            ..restore_request(s.0)
+        },
+        Ok(None) => {
+            return (StatusCode::NOT_FOUND, Json(ApiResponse::<()>::error("Verzija ne postoji"))).into_response()
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<()>::error(&format!("Greska: {}", e))),
+            )
+                .into_response()
+        }
+    };
+
+    // stari datum je mozda vec prosao, a kategorija obrisana
+    if let Err(msg) = validate_update(&req) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::<()>::error(msg))).into_response();
+    }
+    if let Err((status, msg)) = resolve_update_category(&state.db, &mut req).await {
+        return (status, Json(ApiResponse::<()>::error(&msg))).into_response();
+    }
+    if let Err((status, msg)) = check_capacity_change(&state, &event, req.capacity, req.capacity_strategy).await {
+        return (status, Json(ApiResponse::<()>::error(&msg))).into_response();
+    }
+
+    let author_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+    match update_with_revision(&state.db, &event, &req, Some(event.version), author_id).await {
+        Ok(Some(updated)) => {
+            let sync = sync_capacity(&state, &event, &updated, &req).await;
+            let msg = updated_message(&format!("Vraceno stanje iz verzije {}", version), &sync, req.capacity_strategy);
+            event_response(StatusCode::OK, &msg, updated)
+        }
+        Ok(None) => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::<()>::error("Dogadjaj je u medjuvremenu izmenjen - pokusajte ponovo")),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(&format!("Greska: {}", e))),
+        )
+            .into_response(),
+    }
+}
+
+// ---- Kanta ----
+
+/// GET /events/trash - moji obrisani dogadjaji (Admin vidi sve), najnoviji prvi
+pub async fn list_trash(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+) -> (StatusCode, Json<ApiResponse<Vec<TrashedEvent>>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+
+    // i dogadjaji gde je korisnik u osoblju, ako mu uloga dozvoljava vracanje (kao restore_event)
+    let result = sqlx::query_as::<_, Event>(
+        "SELECT * FROM events
+         WHERE deleted_at IS NOT NULL
+           AND ($1 OR organizer_id = $2 OR id IN (SELECT event_id FROM event_staff WHERE user_id = $2))
+         ORDER BY deleted_at DESC",
+    )
+    .bind(claims.role == "Admin")
+    .bind(user_id)
+    .fetch_all(&state.db)
+    .await;
+
+    match result {
+        Ok(events) => {
+            let mut allowed = Vec::with_capacity(events.len());
+            for event in events {
+                if event_role(&state.db, &event, &claims).await.is_some_and(|role| role_allows(&role, Permission::Delete)) {
+                    allowed.push(event);
+                }
+            }
+            let retention = Duration::days(state.trash_retention_days);
+            let list = allowed
+                .into_iter()
+                .map(|event| TrashedEvent {
+                    purge_at: event.deleted_at.unwrap_or(event.updated_at) + retention,
+                    event,
+                })
+                .collect();
+            (StatusCode::OK, Json(ApiResponse::success("Kanta", list)))
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// Vraca prijave otkazane brisanjem, koliko ima mesta (registration-service ih bira redom prijavljivanja)
+async fn restore_registrations(state: &AppState, event: &Event) -> Result<RestoredRegistrations, String> {
+    let url = format!("{}/internal/event/{}/restore-registrations", state.registration_service_url, event.id);
+    let resp = state
+        .http_client
+        .post(&url)
+        .json(&json!({ "capacity": event.capacity }))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let body = resp
+        .json::<RestoredRegistrationsResponse>()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    match body.data {
+        Some(report) if body.success => Ok(report),
+        _ => Err("Registration servis vratio gresku".to_string()),
+    }
+}
+
+/// POST /events/:id/restore - vraca dogadjaj iz kante (ko sme da brise, sme i da vrati)
+pub async fn restore_event(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<RestoreReport>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+
+    let event = match sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id = $1 AND deleted_at IS NOT NULL").bind(id).fetch_optional(&state.db).await {
+        Ok(Some(e)) => e,
+        Ok(None) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Dogadjaj nije u kanti"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+
+    let allowed = event_role(&state.db, &event, &claims)
+        .await
+        .is_some_and(|role| role_allows(&role, Permission::Delete));
+    if !allowed {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::error(forbidden_message(Permission::Delete))));
+    }
+
+    let restored = sqlx::query_as::<_, Event>(
+        "UPDATE events SET deleted_at = NULL, deleted_by = NULL, version = version + 1, updated_at = NOW()
+         WHERE id = $1 RETURNING *",
+    )
+    .bind(id)
+    .fetch_one(&state.db)
+    .await;
+
+    let event = match restored {
+        Ok(e) => e,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+
+    // termin se vraca i u pravilo serije
+    if let (Some(series_id), Some(occurrence)) = (event.series_id, event.occurrence_date) {
+        let _ = sqlx::query("UPDATE event_series SET exdates = array_remove(exdates, $2), updated_at = NOW() WHERE id = $1")
+            .bind(series_id)
+            .bind(occurrence)
+            .execute(&state.db)
+            .await;
+    }
+
+    let registrations = match restore_registrations(&state, &event).await {
+        Ok(r) => r,
+        Err(e) => {
+            tracing::warn!("Nisam uspeo da vratim prijave za {}: {}", event.id, e);
+            RestoredRegistrations::default()
+        }
+    };
+
+    let msg = if registrations.not_restored > 0 {
+        format!(
+            "Dogadjaj vracen; vraceno prijava: {}, bez mesta: {}",
+            registrations.restored, registrations.not_restored
+        )
+    } else {
+        format!("Dogadjaj vracen; vraceno prijava: {}", registrations.restored)
+    };
+    (StatusCode::OK, Json(ApiResponse::success(&msg, RestoreReport { event, registrations })))
+}
+
+// ---- Kategorije ----
+
+/// GET /categories?lang=en - kategorije po redosledu, sa brojem objavljenih javnih dogadjaja
+pub async fn list_categories(
+    headers: HeaderMap,
+    Query(query): Query<CategoryQuery>,
+    State(state): State<AppState>,
+) -> (StatusCode, Json<ApiResponse<Vec<CategoryWithCount>>>) {
+    let result = sqlx::query_as::<_, CategoryWithCount>(
+        "SELECT c.*,
+                (SELECT COUNT(*) FROM events e
+                 WHERE e.category = c.name AND e.status = 'published' AND e.visibility = 'public'
+                   AND e.deleted_at IS NULL) AS event_count
+         FROM categories c ORDER BY c.sort_order, c.name",
+    )
+    .fetch_all(&state.db)
+    .await;
+
+    match result {
+        Ok(mut list) => {
+            let locales = preferred_locales(&headers, query.lang.as_deref());
+            for item in &mut list {
+                let c = &item.category;
+                item.label = locales
+                    .iter()
+                    .find_map(|locale| c.translations.get(locale))
+                    .unwrap_or(&c.name)
+                    .clone();
+            }
+            (StatusCode::OK, Json(ApiResponse::success("Kategorije", list)))
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+fn validate_category(req: &CategoryRequest) -> Result<(), &'static str> {
+    if req.name.as_deref().is_some_and(|n| n.trim().is_empty() || n.trim().chars().count() > 50) {
+        return Err("Naziv kategorije mora imati od 1 do 50 karaktera");
+    }
+    if req.slug.as_deref().is_some_and(|s| slugify(s).is_empty() || slugify(s).len() > 50) {
+        return Err("Slug mora imati od 1 do 50 slova ili brojeva");
+    }
+    Ok(())
+}
+
+fn category_error(e: sqlx::Error) -> (StatusCode, Json<ApiResponse<Category>>) {
+    match e {
+        sqlx::Error::Database(e) if e.is_unique_violation() => {
+            (StatusCode::CONFLICT, Json(ApiResponse::error("Kategorija sa tim nazivom ili slugom vec postoji")))
+        }
+        e => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// POST /categories (Admin)
+pub async fn create_category(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Json(req): Json<CategoryRequest>,
+) -> (StatusCode, Json<ApiResponse<Category>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    if let Err((status, msg)) = require_role(&claims, "Admin") {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+    let Some(name) = req.name.as_deref().map(str::trim) else {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error("Naziv kategorije je obavezan")));
+    };
+    if let Err(msg) = validate_category(&req) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(msg)));
+    }
+    let slug = slugify(req.slug.as_deref().unwrap_or(name));
+    if slug.is_empty() {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error("Naziv mora sadrzati bar jedno slovo ili broj")));
+    }
+
+    let result = sqlx::query_as::<_, Category>(
+        "INSERT INTO categories (slug, name, translations, sort_order)
+         VALUES ($1, $2, $3, COALESCE($4, 100)) RETURNING *",
+    )
+    .bind(&slug)
+    .bind(name)
+    .bind(sqlx::types::Json(req.translations.clone().unwrap_or_default()))
+    .bind(req.sort_order)
+    .fetch_one(&state.db)
+    .await;
+
+    match result {
+        Ok(category) => (StatusCode::CREATED, Json(ApiResponse::success("Kategorija kreirana", category))),
+        Err(e) => category_error(e),
+    }
+}
+
+/// PUT /categories/:slug (Admin). Novi naziv se upisuje i u dogadjaje, serije i sablone.
+pub async fn update_category(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    Json(req): Json<CategoryRequest>,
+) -> (StatusCode, Json<ApiResponse<Category>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    if let Err((status, msg)) = require_role(&claims, "Admin") {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+    if let Err(msg) = validate_category(&req) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(msg)));
+    }
+
+    let result = async {
+        let mut tx = state.db.begin().await?;
+        let old_name = sqlx::query_scalar::<_, String>("SELECT name FROM categories WHERE slug = $1 FOR UPDATE")
+            .bind(&slug)
+            .fetch_optional(&mut *tx)
+            .await?;
+        let Some(old_name) = old_name else {
+            return Ok(None);
+        };
+
+        let category = sqlx::query_as::<_, Category>(
+            "UPDATE categories SET
+                name = COALESCE($2, name),
+                slug = COALESCE($3, slug),
+                translations = COALESCE($4, translations),
+                sort_order = COALESCE($5, sort_order)
+             WHERE slug = $1 RETURNING *",
+        )
+        .bind(&slug)
+        .bind(req.name.as_deref().map(str::trim))
+        .bind(req.slug.as_deref().map(slugify))
+        .bind(req.translations.clone().map(sqlx::types::Json))
+        .bind(req.sort_order)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if category.name != old_name {
+            for table in ["events", "event_series", "event_templates"] {
+                sqlx::query(&format!("UPDATE {} SET category = $2 WHERE category = $1", table))
+                    .bind(&old_name)
+                    .bind(&category.name)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(Some(category))
+    };
+
+    match result.await {
+        Ok(Some(category)) => (StatusCode::OK, Json(ApiResponse::success("Kategorija azurirana", category))),
+        Ok(None) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Kategorija ne postoji"))),
+        Err(e) => category_error(e),
+    }
+}
+
+/// DELETE /categories/:slug (Admin) - samo ako je niko ne koristi (i dogadjaji u kanti)
+pub async fn delete_category(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    if let Err((status, msg)) = require_role(&claims, "Admin") {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+
+    let in_use = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT (SELECT COUNT(*) FROM events WHERE category = c.name)
+              + (SELECT COUNT(*) FROM event_series WHERE category = c.name)
+              + (SELECT COUNT(*) FROM event_templates WHERE category = c.name)
+         FROM categories c WHERE c.slug = $1",
+    )
+    .bind(&slug)
+    .fetch_optional(&state.db)
+    .await;
+
+    match in_use {
+        Ok(None) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Kategorija ne postoji"))),
+        Ok(Some(Some(n))) if n > 0 => {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(&format!(
+                    "Kategoriju koristi {} dogadjaja/serija/sablona - prvo ih premestite u drugu",
+                    n
+                ))),
+            )
+        }
+        Ok(_) => {}
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+
+    match sqlx::query("DELETE FROM categories WHERE slug = $1").bind(&slug).execute(&state.db).await {
+        Ok(_) => (StatusCode::OK, Json(ApiResponse::success("Kategorija obrisana", "ok".to_string()))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+// ---- Feed-ovi i javne stranice ----
+
+/// Najvise dogadjaja u Atom/RSS feed-u
+const FEED_LIMIT: i64 = 50;
+
+/// Predstojeci objavljeni javni dogadjaji za feed (isti filteri kao lista)
+async fn feed_events(state: &AppState, params: &EventQuery) -> Result<Vec<Event>, (StatusCode, String)> {
+    let mut qb = build_list_query(params, None, Some(FEED_LIMIT)).map_err(|msg| (StatusCode::BAD_REQUEST, msg))?;
+    let mut events = qb
+        .build_query_as::<Event>()
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Greska: {}", e)))?;
+
+    attach_tags(&state.db, &mut events)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Greska: {}", e)))?;
+    Ok(events)
+}
+
+fn feed_title(params: &EventQuery) -> String {
+    match &params.category {
+        Some(cat) => format!("Dogadjaji - {}", cat),
+        None => "Dogadjaji".to_string(),
+    }
+}
+
+fn feed_self_url(state: &AppState, path: &str, uri: &axum::http::Uri) -> String {
+    match uri.query() {
+        Some(q) => format!("{}{}?{}", state.public_api_url, path, q),
+        None => format!("{}{}", state.public_api_url, path),
+    }
+}
+
+/// GET /events/feed.atom - Atom feed predstojecih dogadjaja (isti filteri kao /events)
+pub async fn events_atom(
+    Query(params): Query<EventQuery>,
+    State(state): State<AppState>,
+    uri: axum::http::Uri,
+) -> Response {
+    match feed_events(&state, &params).await {
+        Ok(events) => {
+            let self_url = feed_self_url(&state, "/events/feed.atom", &uri);
+            let body = render_atom(&events, &feed_title(&params), &self_url, &state.public_api_url);
+            ([(header::CONTENT_TYPE, "application/atom+xml; charset=utf-8")], body).into_response()
+        }
+        Err((status, msg)) => (status, Json(ApiResponse::<()>::error(&msg))).into_response(),
+    }
+}
+
+/// GET /events/feed.rss - RSS 2.0 feed predstojecih dogadjaja
+pub async fn events_rss(
+    Query(params): Query<EventQuery>,
+    State(state): State<AppState>,
+    uri: axum::http::Uri,
+) -> Response {
+    match feed_events(&state, &params).await {
+        Ok(events) => {
+            let self_url = feed_self_url(&state, "/events/feed.rss", &uri);
+            let body = render_rss(&events, &feed_title(&params), &self_url, &state.public_api_url, &state.public_app_url);
+            ([(header::CONTENT_TYPE, "application/rss+xml; charset=utf-8")], body).into_response()
+        }
+        Err((status, msg)) => (status, Json(ApiResponse::<()>::error(&msg))).into_response(),
+    }
+}
+
+/// Dogadjaj koji korisnik vidi + schema.org JSON-LD za njega. Ok(None) = ne postoji ili nije vidljiv.
+async fn public_event_ld(
+    headers: &HeaderMap,
+    state: &AppState,
+    id: Uuid,
+) -> Result<Option<(Event, serde_json::Value)>, sqlx::Error> {
+    let event = sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id = $1 AND deleted_at IS NULL")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?;
+    let mut event = match event {
+        Some(e) if can_see(headers, state, &e, false).await => e,
+        _ => return Ok(None),
+    };
+    attach_tags(&state.db, std::slice::from_mut(&mut event)).await?;
+
+    let ticket_types = sqlx::query_as::<_, TicketType>(
+        "SELECT * FROM ticket_types WHERE event_id = $1 AND visibility = 'public' ORDER BY sort_order, price_cents",
+    )
+    .bind(id)
+    .fetch_all(&state.db)
+    .await?;
+
+    // bez registration-service-a JSON-LD ide bez slobodnih mesta
+    let remaining = match confirmed_counts(state, &[id]).await {
+        Ok(confirmed) => Some(remaining_seats(&event, &confirmed)),
+        Err(e) => {
+            tracing::warn!("Broj prijava za {} nije dostupan: {}", id, e);
+            None
+        }
+    };
+
+    let ld = json_ld(&event, &ticket_types, remaining, &state.public_api_url);
+    Ok(Some((event, ld)))
+}
+
+/// GET /events/:id/jsonld - schema.org Event (application/ld+json)
+pub async fn event_json_ld(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    match public_event_ld(&headers, &state, id).await {
+        Ok(Some((_, ld))) => ([(header::CONTENT_TYPE, "application/ld+json")], ld.to_string()).into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, Json(ApiResponse::<()>::error("Dogadjaj ne postoji"))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(&format!("Greska: {}", e))),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /events/:id/page - HTML stranica dogadjaja sa Open Graph tagovima (pregled linka na mrezama)
+pub async fn event_page(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    match public_event_ld(&headers, &state, id).await {
+        Ok(Some((event, ld))) => {
+            Html(render_landing(&event, &ld, &state.public_api_url, &state.public_app_url)).into_response()
+        }
+        Ok(None) => (StatusCode::NOT_FOUND, Html("<!DOCTYPE html><h1>Dogadjaj ne postoji</h1>")).into_response(),
+        Err(e) => {
+            tracing::warn!("Stranica dogadjaja {}: {}", id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Html("<!DOCTYPE html><h1>Greska</h1>")).into_response()
+        }
+    }
+}
+
+// ---- Utisci i ocene ----
+
+const MAX_REVIEW_LEN: usize = 2000;
+/// posle ovoliko prijava utisak se automatski sakriva do odluke admina
+const REVIEW_AUTO_HIDE_REPORTS: i32 = 3;
+
+fn validate_review(rating: Option<i16>, body: Option<&str>) -> Result<(), &'static str> {
+    if rating.is_some_and(|r| !(1..=5).contains(&r)) {
+        return Err("Ocena mora biti od 1 do 5");
+    }
+    if body.is_some_and(|b| b.chars().count() > MAX_REVIEW_LEN) {
+        return Err("Utisak moze imati najvise 2000 karaktera");
+    }
+    Ok(())
+}
+
+/// Preracunava prosek i broj ocena dogadjaja (bez sakrivenih). Verzija dogadjaja se ne menja.
+async fn refresh_rating(db: &PgPool, event_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE events SET
+            rating_avg = (SELECT AVG(rating)::float8 FROM event_reviews WHERE event_id = $1 AND NOT hidden),
+            rating_count = (SELECT COUNT(*) FROM event_reviews WHERE event_id = $1 AND NOT hidden)
+         WHERE id = $1",
+    )
+    .bind(event_id)
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+async fn find_review(db: &PgPool, event_id: Uuid, review_id: Uuid) -> Result<Option<Review>, sqlx::Error> {
+    sqlx::query_as::<_, Review>("SELECT * FROM event_reviews WHERE id = $1 AND event_id = $2")
+        .bind(review_id)
+        .bind(event_id)
+        .fetch_optional(db)
+        .await
+}
+
+/// Red iz baze ili 404 sa datom porukom (500 ako baza pukne)
+fn found_or<R, T: Serialize>(
+    result: Result<Option<R>, sqlx::Error>,
+    missing: &str,
+) -> Result<R, (StatusCode, Json<ApiResponse<T>>)> {
+    match result {
+        Ok(Some(r)) => Ok(r),
+        Ok(None) => Err((StatusCode::NOT_FOUND, Json(ApiResponse::error(missing)))),
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e))))),
+    }
+}
+
+/// GET /events/:id/reviews - vidljivi utisci i prosek; Admin vidi i sakrivene
+pub async fn list_reviews(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<ReviewList>>) {
+    let event = match sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id = $1 AND deleted_at IS NULL")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+    {
+        Ok(Some(e)) if can_see(&headers, &state, &e, false).await => e,
+        Ok(_) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Dogadjaj ne postoji"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+    let moderator = extract_claims(&headers, &state.jwt_secret).is_ok_and(|c| c.role == "Admin");
+
+    let reviews = sqlx::query_as::<_, Review>(
+        "SELECT * FROM event_reviews WHERE event_id = $1 AND (NOT hidden OR $2) ORDER BY created_at DESC",
+    )
+    .bind(event.id)
+    .bind(moderator)
+    .fetch_all(&state.db)
+    .await;
+
+    match reviews {
+        Ok(reviews) => {
+            let mut distribution = [0i64; 5];
+            for r in reviews.iter().filter(|r| !r.hidden) {
+                distribution[(r.rating - 1) as usize] += 1;
+            }
+            let summary = RatingSummary {
+                average: event.rating_avg,
+                count: event.rating_count as i64,
+                distribution,
+            };
+            (StatusCode::OK, Json(ApiResponse::success("Utisci", ReviewList { summary, reviews })))
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// POST /events/:id/reviews - samo posetilac sa potvrdjenom prijavom i check-in-om, posle pocetka dogadjaja
+pub async fn create_review(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<CreateReviewRequest>,
+) -> (StatusCode, Json<ApiResponse<Review>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+    if let Err(msg) = validate_review(Some(req.rating), Some(&req.body)) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(msg)));
+    }
+
+    let event = match sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id = $1 AND deleted_at IS NULL")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+    {
+        Ok(Some(e)) => e,
+        Ok(None) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Dogadjaj ne postoji"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+    if event.date_time > local_now() {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error("Utisak se moze ostaviti tek posle dogadjaja")));
+    }
+
+    let registration = match fetch_registration(&state, id, user_id).await {
+        Ok(r) => r,
+        Err(e) => {
+            tracing::warn!("registration-service nije dostupan: {}", e);
+            return (StatusCode::SERVICE_UNAVAILABLE, Json(ApiResponse::error("Provera prijave trenutno nije moguca")));
+        }
+    };
+    let attended = registration.is_some_and(|r| r.status == "confirmed" && r.checked_in_at.is_some());
+    if !attended {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error("Utisak mogu ostaviti samo posetioci koji su prisustvovali dogadjaju")),
+        );
+    }
+
+    let author_name = claims.email.split('@').next().unwrap_or_default().to_string();
+    let result = sqlx::query_as::<_, Review>(
+        "INSERT INTO event_reviews (id, event_id, user_id, author_name, rating, body)
+         VALUES ($1, $2, $3, $4, $5, $6) RETURNING *",
+    )
+    .bind(Uuid::new_v4())
+    .bind(id)
+    .bind(user_id)
+    .bind(&author_name)
+    .bind(req.rating)
+    .bind(req.body.trim())
+    .fetch_one(&state.db)
+    .await;
+
+    match result {
+        Ok(review) => {
+            if let Err(e) = refresh_rating(&state.db, id).await {
+                tracing::warn!("Prosek ocena za {}: {}", id, e);
+            }
+            (StatusCode::CREATED, Json(ApiResponse::success("Utisak sacuvan", review)))
+        }
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            (StatusCode::CONFLICT, Json(ApiResponse::error("Vec ste ostavili utisak za ovaj dogadjaj")))
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// PUT /events/:id/reviews/:review_id - autor menja ocenu ili tekst
+pub async fn update_review(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((id, review_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<UpdateReviewRequest>,
+) -> (StatusCode, Json<ApiResponse<Review>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+    if let Err(msg) = validate_review(req.rating, req.body.as_deref()) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(msg)));
+    }
+    let review = match found_or(find_review(&state.db, id, review_id).await, "Utisak ne postoji") {
+        Ok(r) => r,
+        Err(resp) => return resp,
+    };
+    if review.user_id != user_id {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::error("Mozete menjati samo svoj utisak")));
+    }
+
+    let result = sqlx::query_as::<_, Review>(
+        "UPDATE event_reviews SET rating = COALESCE($2, rating), body = COALESCE($3, body), updated_at = NOW()
+         WHERE id = $1 RETURNING *",
+    )
+    .bind(review_id)
+    .bind(req.rating)
+    .bind(req.body.as_deref().map(str::trim))
+    .fetch_one(&state.db)
+    .await;
+
+    match result {
+        Ok(review) => {
+            if let Err(e) = refresh_rating(&state.db, id).await {
+                tracing::warn!("Prosek ocena za {}: {}", id, e);
+            }
+            (StatusCode::OK, Json(ApiResponse::success("Utisak izmenjen", review)))
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// DELETE /events/:id/reviews/:review_id - autor ili Admin
+pub async fn delete_review(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((id, review_id)): Path<(Uuid, Uuid)>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+    let review = match found_or(find_review(&state.db, id, review_id).await, "Utisak ne postoji") {
+        Ok(r) => r,
+        Err(resp) => return resp,
+    };
+    if review.user_id != user_id && claims.role != "Admin" {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::error("Mozete obrisati samo svoj utisak")));
+    }
+
+    match sqlx::query("DELETE FROM event_reviews WHERE id = $1").bind(review_id).execute(&state.db).await {
+        Ok(_) => {
+            if let Err(e) = refresh_rating(&state.db, id).await {
+                tracing::warn!("Prosek ocena za {}: {}", id, e);
+            }
+            (StatusCode::OK, Json(ApiResponse::success("Utisak obrisan", ())))
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// PUT /events/:id/reviews/:review_id/reply - odgovor organizatora (ili osoblja sa pravom izmene)
+pub async fn reply_to_review(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((id, review_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<ReviewReplyRequest>,
+) -> (StatusCode, Json<ApiResponse<Review>>) {
+    let reply = req.reply.trim();
+    if reply.is_empty() {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error("Odgovor ne moze biti prazan")));
+    }
+    if let Err(msg) = validate_review(None, Some(reply)) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(msg)));
+    }
+    set_review_reply(&state, &headers, id, review_id, Some(reply)).await
+}
+
+/// DELETE /events/:id/reviews/:review_id/reply
+pub async fn delete_review_reply(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((id, review_id)): Path<(Uuid, Uuid)>,
+) -> (StatusCode, Json<ApiResponse<Review>>) {
+    set_review_reply(&state, &headers, id, review_id, None).await
+}
+
+async fn set_review_reply(
+    state: &AppState,
+    headers: &HeaderMap,
+    id: Uuid,
+    review_id: Uuid,
+    reply: Option<&str>,
+) -> (StatusCode, Json<ApiResponse<Review>>) {
+    let (_, claims) = match load_managed_event(state, headers, id, Permission::Edit).await {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    if let Err(resp) = found_or::<_, Review>(find_review(&state.db, id, review_id).await, "Utisak ne postoji") {
+        return resp;
+    }
+
+    let result = sqlx::query_as::<_, Review>(
+        "UPDATE event_reviews SET reply = $2,
+            replied_by = CASE WHEN $2::text IS NULL THEN NULL ELSE $3 END,
+            replied_at = CASE WHEN $2::text IS NULL THEN NULL ELSE NOW() END
+         WHERE id = $1 RETURNING *",
+    )
+    .bind(review_id)
+    .bind(reply)
+    .bind(Uuid::parse_str(&claims.sub).unwrap_or_default())
+    .fetch_one(&state.db)
+    .await;
+
+    match result {
+        Ok(review) => {
+            let msg = if reply.is_some() { "Odgovor sacuvan" } else { "Odgovor obrisan" };
+            (StatusCode::OK, Json(ApiResponse::success(msg, review)))
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// POST /events/:id/reviews/:review_id/report - prijava neprikladnog utiska (jednom po korisniku)
+pub async fn report_review(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((id, review_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<ReportReviewRequest>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+    let review = match found_or(find_review(&state.db, id, review_id).await, "Utisak ne postoji") {
+        Ok(r) => r,
+        Err(resp) => return resp,
+    };
+    if review.user_id == user_id {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error("Ne mozete prijaviti svoj utisak")));
+    }
+    if req.reason.chars().count() > 500 {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error("Razlog moze imati najvise 500 karaktera")));
+    }
+
+    let result = async {
+        let mut tx = state.db.begin().await?;
+        let inserted = sqlx::query(
+            "INSERT INTO review_reports (review_id, user_id, reason) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING",
+        )
+        .bind(review_id)
+        .bind(user_id)
+        .bind(req.reason.trim())
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if inserted == 0 {
+            return Ok::<_, sqlx::Error>(false);
+        }
+        // posle REVIEW_AUTO_HIDE_REPORTS prijava utisak se sakriva dok admin ne odluci
+        sqlx::query(
+            "UPDATE event_reviews SET report_count = report_count + 1,
+                hidden = hidden OR report_count + 1 >= $2,
+                hidden_reason = CASE WHEN NOT hidden AND report_count + 1 >= $2
+                                     THEN 'Automatski sakriveno zbog prijava' ELSE hidden_reason END
+             WHERE id = $1",
+        )
+        .bind(review_id)
+        .bind(REVIEW_AUTO_HIDE_REPORTS)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+    .await;
+
+    match result {
+        Ok(true) => {
+            if let Err(e) = refresh_rating(&state.db, id).await {
+                tracing::warn!("Prosek ocena za {}: {}", id, e);
+            }
+            (StatusCode::OK, Json(ApiResponse::success("Utisak prijavljen", ())))
+        }
+        Ok(false) => (StatusCode::CONFLICT, Json(ApiResponse::error("Vec ste prijavili ovaj utisak"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// PUT /events/:id/reviews/:review_id/moderation (Admin) - sakriva utisak ili ga vraca i odbacuje prijave
+pub async fn moderate_review(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((id, review_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<ModerateReviewRequest>,
+) -> (StatusCode, Json<ApiResponse<Review>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    if let Err((status, msg)) = require_role(&claims, "Admin") {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+    if let Err(resp) = found_or::<_, Review>(find_review(&state.db, id, review_id).await, "Utisak ne postoji") {
+        return resp;
+    }
+
+    let result = async {
+        let mut tx = state.db.begin().await?;
+        if !req.hidden {
+            sqlx::query("DELETE FROM review_reports WHERE review_id = $1").bind(review_id).execute(&mut *tx).await?;
+        }
+        let review = sqlx::query_as::<_, Review>(
+            "UPDATE event_reviews SET hidden = $2,
+                hidden_reason = CASE WHEN $2 THEN COALESCE($3, 'Sakriveno od strane moderatora') END,
+                report_count = CASE WHEN $2 THEN report_count ELSE 0 END
+             WHERE id = $1 RETURNING *",
+        )
+        .bind(review_id)
+        .bind(req.hidden)
+        .bind(req.reason.as_deref().map(str::trim).filter(|r| !r.is_empty()))
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(review)
+    }
+    .await;
+
+    match result {
+        Ok(review) => {
+            if let Err(e) = refresh_rating(&state.db, id).await {
+                tracing::warn!("Prosek ocena za {}: {}", id, e);
+            }
+            let msg = if review.hidden { "Utisak sakriven" } else { "Utisak vracen" };
+            (StatusCode::OK, Json(ApiResponse::success(msg, review)))
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// GET /reviews/reported (Admin) - prijavljeni utisci, najvise prijava prvo
+pub async fn list_reported_reviews(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+) -> (StatusCode, Json<ApiResponse<Vec<Review>>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    if let Err((status, msg)) = require_role(&claims, "Admin") {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+
+    let result = sqlx::query_as::<_, Review>(
+        "SELECT * FROM event_reviews WHERE report_count > 0 ORDER BY report_count DESC, created_at DESC LIMIT 200",
+    )
+    .fetch_all(&state.db)
+    .await;
+
+    match result {
+        Ok(reviews) => (StatusCode::OK, Json(ApiResponse::success("Prijavljeni utisci", reviews))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+// ---- Pitanja i komentari ----
+
+const MAX_QUESTION_LEN: usize = 2000;
+/// najmanji razmak izmedju dve poruke istog korisnika
+const QA_MIN_INTERVAL_SECS: i64 = 10;
+
+fn validate_question(body: &str) -> Result<(), &'static str> {
+    if body.is_empty() {
+        return Err("Poruka ne moze biti prazna");
+    }
+    if body.chars().count() > MAX_QUESTION_LEN {
+        return Err("Poruka moze imati najvise 2000 karaktera");
+    }
+    Ok(())
+}
+
+async fn find_question(db: &PgPool, event_id: Uuid, question_id: Uuid) -> Result<Option<Question>, sqlx::Error> {
+    sqlx::query_as::<_, Question>("SELECT * FROM event_questions WHERE id = $1 AND event_id = $2")
+        .bind(question_id)
+        .bind(event_id)
+        .fetch_optional(db)
+        .await
+}
+
+/// Da li korisnik moze da moderira pitanja (organizator, Admin, osoblje sa pravom izmene)
+async fn qa_moderator(db: &PgPool, event: &Event, claims: &Claims) -> bool {
+    event_role(db, event, claims)
+        .await
+        .is_some_and(|role| role_allows(&role, Permission::Edit))
+}
+
+/// Koliko sekundi korisnik treba da saceka pre sledece poruke (None = moze odmah).
+/// Broji iz question_posts, pa obrisana pitanja i dalje ulaze u ogranicenje.
+async fn qa_retry_after(conn: &mut PgConnection, user_id: Uuid, per_hour: i64) -> Result<Option<i64>, sqlx::Error> {
+    let (count, since_last, oldest_age) = sqlx::query_as::<_, (i64, Option<f64>, Option<f64>)>(
+        "SELECT COUNT(*),
+                EXTRACT(EPOCH FROM NOW() - MAX(created_at))::float8,
+                EXTRACT(EPOCH FROM NOW() - MIN(created_at))::float8
+         FROM question_posts WHERE user_id = $1 AND created_at > NOW() - INTERVAL '1 hour'",
+    )
+    .bind(user_id)
+    .fetch_one(conn)
+    .await?;
+
+    if count >= per_hour {
+        return Ok(Some((3600.0 - oldest_age.unwrap_or(0.0)).ceil().max(1.0) as i64));
+    }
+    match since_last {
+        Some(secs) if secs < QA_MIN_INTERVAL_SECS as f64 => Ok(Some((QA_MIN_INTERVAL_SECS as f64 - secs).ceil() as i64)),
+        _ => Ok(None),
+    }
+}
+
+/// Pitanja sa odgovorima: zakacena i odgovorena prvo, pa po glasovima. Sakrivena vide samo moderatori.
+fn build_threads(questions: Vec<Question>, moderator: bool) -> Vec<QuestionThread> {
+    let (roots, replies): (Vec<_>, Vec<_>) = questions.into_iter().partition(|q| q.parent_id.is_none());
+    let mut threads: Vec<QuestionThread> = roots
+        .into_iter()
+        .filter(|q| moderator || !q.hidden)
+        .map(|question| QuestionThread { question, replies: Vec::new() })
+        .collect();
+
+    for reply in replies.into_iter().filter(|q| moderator || !q.hidden) {
+        if let Some(thread) = threads.iter_mut().find(|t| Some(t.question.id) == reply.parent_id) {
+            thread.replies.push(reply);
+        }
+    }
+    // pitanje koje je autor obrisao ostaje samo dok ima odgovore
+    threads.retain(|t| t.question.deleted_at.is_none() || !t.replies.is_empty());
+    for thread in &mut threads {
+        thread.replies.sort_by_key(|r| r.created_at);
+    }
+    threads.sort_by(|a, b| {
+        let (a, b) = (&a.question, &b.question);
+        b.pinned
+            .cmp(&a.pinned)
+            .then(b.answered_at.is_some().cmp(&a.answered_at.is_some()))
+            .then(b.upvotes.cmp(&a.upvotes))
+            .then(b.created_at.cmp(&a.created_at))
+    });
+    threads
+}
+
+/// GET /events/:id/questions - pitanja i komentari (JWT opciono: oznacava svoje glasove)
+pub async fn list_questions(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<Vec<QuestionThread>>>) {
+    let event = match sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id = $1 AND deleted_at IS NULL")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+    {
+        Ok(Some(e)) if can_see(&headers, &state, &e, false).await => e,
+        Ok(_) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Dogadjaj ne postoji"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+    let claims = extract_claims(&headers, &state.jwt_secret).ok();
+    let moderator = match &claims {
+        Some(c) => qa_moderator(&state.db, &event, c).await,
+        None => false,
+    };
+
+    let result = async {
+        let mut questions = sqlx::query_as::<_, Question>("SELECT * FROM event_questions WHERE event_id = $1")
+            .bind(id)
+            .fetch_all(&state.db)
+            .await?;
+        if let Some(user_id) = claims.as_ref().and_then(|c| Uuid::parse_str(&c.sub).ok()) {
+            let voted = sqlx::query_scalar::<_, Uuid>(
+                "SELECT v.question_id FROM question_votes v JOIN event_questions q ON q.id = v.question_id
+                 WHERE q.event_id = $1 AND v.user_id = $2",
+            )
+            .bind(id)
+            .bind(user_id)
+            .fetch_all(&state.db)
+            .await?;
+            for q in &mut questions {
+                q.voted = voted.contains(&q.id);
+            }
+        }
+        Ok::<_, sqlx::Error>(questions)
+    }
+    .await;
+
+    match result {
+        Ok(questions) => (
+            StatusCode::OK,
+            Json(ApiResponse::success("Pitanja", build_threads(questions, moderator))),
+        ),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// POST /events/:id/questions - novo pitanje ili odgovor (parent_id).
+/// Odgovor osoblja oznacava pitanje kao odgovoreno. Obicni korisnici imaju ogranicenje broja poruka.
+pub async fn create_question(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<CreateQuestionRequest>,
+) -> Response {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::<()>::error(&msg))).into_response(),
+    };
+    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+    let body = req.body.trim();
+    if let Err(msg) = validate_question(body) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::<()>::error(msg))).into_response();
+    }
+
+    let event = match sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id = $1 AND deleted_at IS NULL")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+    {
+        Ok(Some(e)) if can_see(&headers, &state, &e, false).await => e,
+        Ok(_) => return (StatusCode::NOT_FOUND, Json(ApiResponse::<()>::error("Dogadjaj ne postoji"))).into_response(),
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<()>::error(&format!("Greska: {}", e))),
+            )
+                .into_response()
+        }
+    };
+    let from_staff = event_role(&state.db, &event, &claims).await.is_some();
+
+    // odgovor na odgovor ide pod isto pitanje
+    let parent_id = match req.parent_id {
+        Some(pid) => match found_or::<_, ()>(find_question(&state.db, id, pid).await, "Pitanje ne postoji") {
+            Ok(parent) if parent.hidden && !from_staff => {
+                return (StatusCode::NOT_FOUND, Json(ApiResponse::<()>::error("Pitanje ne postoji"))).into_response()
+            }
+            Ok(parent) => Some(parent.parent_id.unwrap_or(parent.id)),
+            Err(resp) => return resp.into_response(),
+        },
+        None => None,
+    };
+
+    let author_name = claims.email.split('@').next().unwrap_or_default().to_string();
+    // provera ogranicenja i upis su u istoj transakciji, pod lock-om po korisniku
+    let result = async {
+        let mut tx = state.db.begin().await?;
+        if !from_staff {
+            sqlx::query("SELECT pg_advisory_xact_lock(hashtext('qa:' || $1::text))")
+                .bind(user_id)
+                .execute(&mut *tx)
+                .await?;
+            if let Some(secs) = qa_retry_after(&mut tx, user_id, state.qa_posts_per_hour).await? {
+                return Ok(Err(secs));
+            }
+            sqlx::query("INSERT INTO question_posts (user_id) VALUES ($1)")
+                .bind(user_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        let question = sqlx::query_as::<_, Question>(
+            "INSERT INTO event_questions (id, event_id, parent_id, user_id, author_name, body, from_staff)
+             VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING *",
+        )
+        .bind(Uuid::new_v4())
+        .bind(id)
+        .bind(parent_id)
+        .bind(user_id)
+        .bind(&author_name)
+        .bind(body)
+        .bind(from_staff)
+        .fetch_one(&mut *tx)
+        .await?;
+        if let (Some(parent_id), true) = (parent_id, from_staff) {
+            sqlx::query("UPDATE event_questions SET answered_at = COALESCE(answered_at, NOW()) WHERE id = $1")
+                .bind(parent_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(Ok(question))
+    }
+    .await;
+
+    match result {
+        Ok(Ok(question)) => (StatusCode::CREATED, Json(ApiResponse::success("Poruka objavljena", question))).into_response(),
+        Ok(Err(secs)) => (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, secs.to_string())],
+            Json(ApiResponse::<()>::error(&format!("Previse poruka, pokusajte ponovo za {} s", secs))),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(&format!("Greska: {}", e))),
+        )
+            .into_response(),
+    }
+}
+
+/// PUT /events/:id/questions/:question_id - autor menja tekst
+pub async fn update_question(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((id, question_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<UpdateQuestionRequest>,
+) -> (StatusCode, Json<ApiResponse<Question>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+    let body = req.body.trim();
+    if let Err(msg) = validate_question(body) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(msg)));
+    }
+    let question = match found_or(find_question(&state.db, id, question_id).await, "Pitanje ne postoji") {
+        Ok(q) => q,
+        Err(resp) => return resp,
+    };
+    if question.user_id != user_id {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::error("Mozete menjati samo svoju poruku")));
+    }
+    if question.deleted_at.is_some() {
+        return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Pitanje ne postoji")));
+    }
+
+    let result = sqlx::query_as::<_, Question>(
+        "UPDATE event_questions SET body = $2, updated_at = NOW() WHERE id = $1 RETURNING *",
+    )
+    .bind(question_id)
+    .bind(body)
+    .fetch_one(&state.db)
+    .await;
+
+    match result {
+        Ok(question) => (StatusCode::OK, Json(ApiResponse::success("Poruka izmenjena", question))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// DELETE /events/:id/questions/:question_id - autor ili moderator. Moderator brise i odgovore,
+/// a autor pitanja sa odgovorima samo uklanja svoj tekst.
+pub async fn delete_question(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((id, question_id)): Path<(Uuid, Uuid)>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+    let question = match found_or(find_question(&state.db, id, question_id).await, "Pitanje ne postoji") {
+        Ok(q) => q,
+        Err(resp) => return resp,
+    };
+    // tudje poruke brise samo moderator
+    let moderator = load_managed_event::<()>(&state, &headers, id, Permission::Edit).await.is_ok();
+    if question.user_id != user_id && !moderator {
+        return (StatusCode::FORBIDDEN, Json(ApiResponse::error("Mozete obrisati samo svoju poruku")));
+    }
+
+    let result = if moderator {
+        sqlx::query("DELETE FROM event_questions WHERE id = $1").bind(question_id).execute(&state.db).await
+    } else {
+        // bez odgovora se brise cela poruka, inace ostaje trag da odgovori ne nestanu
+        sqlx::query(
+            "WITH tombstone AS (
+                 UPDATE event_questions SET body = '', deleted_at = NOW(), updated_at = NOW()
+                 WHERE id = $1 AND EXISTS (SELECT 1 FROM event_questions WHERE parent_id = $1)
+                 RETURNING id
+             )
+             DELETE FROM event_questions WHERE id = $1 AND NOT EXISTS (SELECT 1 FROM tombstone)",
+        )
+        .bind(question_id)
+        .execute(&state.db)
+        .await
+    };
+
+    match result {
+        Ok(_) => (StatusCode::OK, Json(ApiResponse::success("Poruka obrisana", ()))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// POST /events/:id/questions/:question_id/vote - glas za pitanje (jednom po korisniku)
+pub async fn upvote_question(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((id, question_id)): Path<(Uuid, Uuid)>,
+) -> (StatusCode, Json<ApiResponse<Question>>) {
+    set_question_vote(&state, &headers, id, question_id, true).await
+}
+
+/// DELETE /events/:id/questions/:question_id/vote
+pub async fn remove_question_vote(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((id, question_id)): Path<(Uuid, Uuid)>,
+) -> (StatusCode, Json<ApiResponse<Question>>) {
+    set_question_vote(&state, &headers, id, question_id, false).await
+}
+
+async fn set_question_vote(
+    state: &AppState,
+    headers: &HeaderMap,
+    id: Uuid,
+    question_id: Uuid,
+    vote: bool,
+) -> (StatusCode, Json<ApiResponse<Question>>) {
+    let claims = match extract_claims(headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+    let question = match found_or(find_question(&state.db, id, question_id).await, "Pitanje ne postoji") {
+        Ok(q) if !q.hidden && q.deleted_at.is_none() => q,
+        Ok(_) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Pitanje ne postoji"))),
+        Err(resp) => return resp,
+    };
+    if vote && question.user_id == user_id {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error("Ne mozete glasati za svoju poruku")));
+    }
+
+    let result = async {
+        let mut tx = state.db.begin().await?;
+        let changed = if vote {
+            sqlx::query("INSERT INTO question_votes (question_id, user_id) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+        } else {
+            sqlx::query("DELETE FROM question_votes WHERE question_id = $1 AND user_id = $2")
+        }
+        .bind(question_id)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        let mut question = sqlx::query_as::<_, Question>(
+            "UPDATE event_questions SET upvotes = upvotes + $2 WHERE id = $1 RETURNING *",
+        )
+        .bind(question_id)
+        .bind(match (changed, vote) {
+            (0, _) => 0,
+            (_, true) => 1,
+            (_, false) => -1,
+        })
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        question.voted = vote;
+        Ok::<_, sqlx::Error>(question)
+    }
+    .await;
+
+    match result {
+        Ok(question) => {
+            let msg = if vote { "Glas zabelezen" } else { "Glas povucen" };
+            (StatusCode::OK, Json(ApiResponse::success(msg, question)))
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// PUT /events/:id/questions/:question_id/moderation - sakrivanje i kacenje na vrh
+pub async fn moderate_question(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((id, question_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<ModerateQuestionRequest>,
+) -> (StatusCode, Json<ApiResponse<Question>>) {
+    if let Err(resp) = load_managed_event(&state, &headers, id, Permission::Edit).await {
+        return resp;
+    }
+    let question = match found_or(find_question(&state.db, id, question_id).await, "Pitanje ne postoji") {
+        Ok(q) => q,
+        Err(resp) => return resp,
+    };
+    if req.pinned.is_some() && question.parent_id.is_some() {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error("Mogu se zakaciti samo pitanja, ne odgovori")));
+    }
+
+    let result = sqlx::query_as::<_, Question>(
+        "UPDATE event_questions SET hidden = COALESCE($2, hidden), pinned = COALESCE($3, pinned)
+         WHERE id = $1 RETURNING *",
+    )
+    .bind(question_id)
+    .bind(req.hidden)
+    .bind(req.pinned)
+    .fetch_one(&state.db)
+    .await;
+
+    match result {
+        Ok(question) => (StatusCode::OK, Json(ApiResponse::success("Pitanje azurirano", question))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+// ---- Sacuvani dogadjaji i pracenje ----
+
+/// POST /events/:id/bookmark - sacuvaj dogadjaj (ponovni poziv ne menja nista)
+pub async fn bookmark_event(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    match sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id = $1 AND deleted_at IS NULL")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+    {
+        Ok(Some(e)) if can_see(&headers, &state, &e, false).await => {}
+        Ok(_) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Dogadjaj ne postoji"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+
+    let result = sqlx::query("INSERT INTO event_bookmarks (user_id, event_id) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+        .bind(Uuid::parse_str(&claims.sub).unwrap_or_default())
+        .bind(id)
+        .execute(&state.db)
+        .await;
+
+    match result {
+        Ok(_) => (StatusCode::OK, Json(ApiResponse::success("Dogadjaj sacuvan", ()))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// DELETE /events/:id/bookmark
+pub async fn remove_bookmark(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+
+    let result = sqlx::query("DELETE FROM event_bookmarks WHERE user_id = $1 AND event_id = $2")
+        .bind(Uuid::parse_str(&claims.sub).unwrap_or_default())
+        .bind(id)
+        .execute(&state.db)
+        .await;
+
+    match result {
+        Ok(r) if r.rows_affected() > 0 => (StatusCode::OK, Json(ApiResponse::success("Dogadjaj uklonjen iz sacuvanih", ()))),
+        Ok(_) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Dogadjaj nije sacuvan"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// GET /events/bookmarked - sacuvani dogadjaji koje korisnik i dalje vidi, po datumu
+pub async fn list_bookmarks(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+) -> (StatusCode, Json<ApiResponse<Vec<Event>>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+
+    let result = sqlx::query_as::<_, Event>(
+        "SELECT e.* FROM events e JOIN event_bookmarks b ON b.event_id = e.id
+         WHERE b.user_id = $1 AND e.deleted_at IS NULL ORDER BY e.date_time",
+    )
+    .bind(Uuid::parse_str(&claims.sub).unwrap_or_default())
+    .fetch_all(&state.db)
+    .await;
+
+    let events = match result {
+        Ok(events) => events,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+    // dogadjaj je mogao u medjuvremenu da postane privatan ili vracen u nacrt
+    let mut visible = Vec::with_capacity(events.len());
+    for event in events {
+        if can_see(&headers, &state, &event, false).await {
+            visible.push(event);
+        }
+    }
+
+    match attach_tags(&state.db, &mut visible).await {
+        Ok(()) => (StatusCode::OK, Json(ApiResponse::success("Sacuvani dogadjaji", visible))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// GET /follows - praceni organizatori i kategorije sa brojem predstojecih dogadjaja
+pub async fn list_follows(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+) -> (StatusCode, Json<ApiResponse<Follows>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+    let now = local_now();
+
+    let result = async {
+        let organizers = sqlx::query_as::<_, FollowedOrganizer>(
+            "SELECT f.organizer_id, f.created_at AS followed_at,
+                    (SELECT COUNT(*) FROM events e WHERE e.organizer_id = f.organizer_id AND e.deleted_at IS NULL
+                       AND e.status = 'published' AND e.visibility = 'public' AND e.date_time >= $2) AS upcoming_events
+             FROM organizer_follows f WHERE f.user_id = $1 ORDER BY f.created_at DESC",
+        )
+        .bind(user_id)
+        .bind(now)
+        .fetch_all(&state.db)
+        .await?;
+        let categories = sqlx::query_as::<_, FollowedCategory>(
+            "SELECT c.slug, c.name, f.created_at AS followed_at,
+                    (SELECT COUNT(*) FROM events e WHERE e.category = c.name AND e.deleted_at IS NULL
+                       AND e.status = 'published' AND e.visibility = 'public' AND e.date_time >= $2) AS upcoming_events
+             FROM category_follows f JOIN categories c ON c.slug = f.category_slug
+             WHERE f.user_id = $1 ORDER BY c.sort_order, c.name",
+        )
+        .bind(user_id)
+        .bind(now)
+        .fetch_all(&state.db)
+        .await?;
+        Ok::<_, sqlx::Error>(Follows { organizers, categories })
+    }
+    .await;
+
+    match result {
+        Ok(follows) => (StatusCode::OK, Json(ApiResponse::success("Pracenja", follows))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// POST /organizers/:id/follow - prati organizatora (mora imati bar jedan dogadjaj)
+pub async fn follow_organizer(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(organizer_id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+    if user_id == organizer_id {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error("Ne mozete pratiti sebe")));
+    }
+
+    let exists = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM events WHERE organizer_id = $1 AND deleted_at IS NULL)",
+    )
+    .bind(organizer_id)
+    .fetch_one(&state.db)
+    .await;
+    match exists {
+        Ok(true) => {}
+        Ok(false) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Organizator ne postoji"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+
+    let result = sqlx::query(
+        "INSERT INTO organizer_follows (user_id, organizer_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+    )
+    .bind(user_id)
+    .bind(organizer_id)
+    .execute(&state.db)
+    .await;
+
+    match result {
+        Ok(_) => (StatusCode::OK, Json(ApiResponse::success("Pratite organizatora", ()))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// DELETE /organizers/:id/follow
+pub async fn unfollow_organizer(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(organizer_id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+
+    let result = sqlx::query("DELETE FROM organizer_follows WHERE user_id = $1 AND organizer_id = $2")
+        .bind(Uuid::parse_str(&claims.sub).unwrap_or_default())
+        .bind(organizer_id)
+        .execute(&state.db)
+        .await;
+
+    match result {
+        Ok(r) if r.rows_affected() > 0 => (StatusCode::OK, Json(ApiResponse::success("Vise ne pratite organizatora", ()))),
+        Ok(_) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Ne pratite ovog organizatora"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// POST /categories/:slug/follow
+pub async fn follow_category(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+
+    let result = sqlx::query(
+        "INSERT INTO category_follows (user_id, category_slug) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+    )
+    .bind(Uuid::parse_str(&claims.sub).unwrap_or_default())
+    .bind(&slug)
+    .execute(&state.db)
+    .await;
+
+    match result {
+        Ok(_) => (StatusCode::OK, Json(ApiResponse::success("Pratite kategoriju", ()))),
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            (StatusCode::NOT_FOUND, Json(ApiResponse::error("Kategorija ne postoji")))
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// DELETE /categories/:slug/follow
+pub async fn unfollow_category(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+
+    let result = sqlx::query("DELETE FROM category_follows WHERE user_id = $1 AND category_slug = $2")
+        .bind(Uuid::parse_str(&claims.sub).unwrap_or_default())
+        .bind(&slug)
+        .execute(&state.db)
+        .await;
+
+    match result {
+        Ok(r) if r.rows_affected() > 0 => (StatusCode::OK, Json(ApiResponse::success("Vise ne pratite kategoriju", ()))),
+        Ok(_) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Ne pratite ovu kategoriju"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+// ---- Preporuke ----
+
+/// koliko predstojecih dogadjaja najvise uzimamo u obzir
+const RECOMMENDATION_CANDIDATES: i64 = 500;
+
+/// GET /events/recommended - predstojeci dogadjaji rangirani za korisnika (bez JWT-a najpopularniji)
+pub async fn recommended_events(
+    headers: HeaderMap,
+    Query(params): Query<RecommendationQuery>,
+    State(state): State<AppState>,
+) -> (StatusCode, Json<ApiResponse<Vec<RecommendedEvent>>>) {
+    let user_id = extract_claims(&headers, &state.jwt_secret)
+        .ok()
+        .and_then(|c| Uuid::parse_str(&c.sub).ok());
+    let limit = params.limit.unwrap_or(10).clamp(1, 50);
+
+    let result = async {
+        let signals = load_signals(&state, user_id).await?;
+        // svoje dogadjaje organizator ne dobija kao preporuku
+        let mut candidates = sqlx::query_as::<_, Event>(
+            "SELECT * FROM events
+             WHERE deleted_at IS NULL AND status = 'published' AND visibility = 'public' AND date_time >= $1
+               AND ($2::uuid IS NULL OR organizer_id <> $2)
+             ORDER BY date_time LIMIT $3",
+        )
+        .bind(local_now())
+        .bind(user_id)
+        .bind(RECOMMENDATION_CANDIDATES)
+        .fetch_all(&state.db)
+        .await?;
+        attach_tags(&state.db, &mut candidates).await?;
+        Ok::<_, sqlx::Error>(rank(candidates, &signals, limit))
+    }
+    .await;
+
+    match result {
+        Ok(ranked) => (StatusCode::OK, Json(ApiResponse::success("Preporuke", ranked))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// POST /recommendations/refresh (Admin) - preracunava slicnost i popularnost odmah, bez cekanja noci
+pub async fn refresh_recommendations(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+) -> (StatusCode, Json<ApiResponse<RecommendationStats>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    if let Err((status, msg)) = require_role(&claims, "Admin") {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+
+    match precompute(&state).await {
+        Ok(stats) => (StatusCode::OK, Json(ApiResponse::success("Preporuke preracunate", stats))),
+        Err(e) => (StatusCode::SERVICE_UNAVAILABLE, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+// ---- Prevodi ----
+
+/// Jezik iz putanje; podrazumevani jezik se menja izmenom samog dogadjaja
+fn translation_locale(state: &AppState, raw: &str) -> Result<String, &'static str> {
+    match normalize_locale(raw) {
+        Some(locale) if locale == state.default_locale => {
+            Err("Podrazumevani jezik se menja izmenom dogadjaja, ne prevodom")
+        }
+        Some(locale) => Ok(locale),
+        None => Err("Jezik mora biti kod od 2-3 slova (npr. en)"),
+    }
+}
+
+/// GET /events/:id/translations - svi prevodi dogadjaja
+pub async fn list_translations(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<EventTranslations>>) {
+    match sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id = $1 AND deleted_at IS NULL")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+    {
+        Ok(Some(e)) if can_see(&headers, &state, &e, false).await => {}
+        Ok(_) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Dogadjaj ne postoji"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+
+    let result = sqlx::query_as::<_, EventTranslation>(
+        "SELECT * FROM event_translations WHERE event_id = $1 ORDER BY locale",
+    )
+    .bind(id)
+    .fetch_all(&state.db)
+    .await;
+
+    match result {
+        Ok(translations) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(
+                "Prevodi",
+                EventTranslations { default_locale: state.default_locale.clone(), translations },
+            )),
+        ),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// PUT /events/:id/translations/:locale - dodaje ili menja prevod (ko sme da menja dogadjaj)
+pub async fn upsert_translation(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((id, locale)): Path<(Uuid, String)>,
+    Json(req): Json<TranslationRequest>,
+) -> (StatusCode, Json<ApiResponse<EventTranslation>>) {
+    let (event, _) = match load_managed_event(&state, &headers, id, Permission::Edit).await {
+        Ok(found) => found,
+        Err(err) => return err,
+    };
+    let locale = match translation_locale(&state, &locale) {
+        Ok(l) => l,
+        Err(msg) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(msg))),
+    };
+
+    let title = req.title.trim();
+    if title.is_empty() || title.chars().count() > 255 {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error("Naziv mora imati od 1 do 255 karaktera")));
+    }
+    let location = req.location.as_deref().map(str::trim).filter(|l| !l.is_empty());
+    if location.is_some_and(|l| l.chars().count() > 255) {
+        return (StatusCode::BAD_REQUEST, Json(ApiResponse::error("Lokacija moze imati najvise 255 karaktera")));
+    }
+    let description = req.description.as_deref().map(str::trim).filter(|d| !d.is_empty());
+
+    let result = sqlx::query_as::<_, EventTranslation>(
+        "INSERT INTO event_translations (event_id, locale, title, description, description_html, location)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         ON CONFLICT (event_id, locale) DO UPDATE SET
+             title = EXCLUDED.title, description = EXCLUDED.description,
+             description_html = EXCLUDED.description_html, location = EXCLUDED.location, updated_at = NOW()
+         RETURNING *",
+    )
+    .bind(event.id)
+    .bind(&locale)
+    .bind(title)
+    .bind(description)
+    .bind(description.map(render_markdown))
+    .bind(location)
+    .fetch_one(&state.db)
+    .await;
+
+    match result {
+        Ok(t) => (StatusCode::OK, Json(ApiResponse::success("Prevod sacuvan", t))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// DELETE /events/:id/translations/:locale
+pub async fn delete_translation(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path((id, locale)): Path<(Uuid, String)>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    if let Err(err) = load_managed_event(&state, &headers, id, Permission::Edit).await {
+        return err;
+    }
+    let locale = match translation_locale(&state, &locale) {
+        Ok(l) => l,
+        Err(msg) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(msg))),
+    };
+
+    let result = sqlx::query("DELETE FROM event_translations WHERE event_id = $1 AND locale = $2")
+        .bind(id)
+        .bind(&locale)
+        .execute(&state.db)
+        .await;
+
+    match result {
+        Ok(r) if r.rows_affected() > 0 => (StatusCode::OK, Json(ApiResponse::success("Prevod obrisan", ()))),
+        Ok(_) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Prevod ne postoji"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+// ---- Uslovi za prijavu ----
+
+/// Proverava i sredjuje pravila: domeni mala slova bez "@", bez duplikata
+fn normalize_rules(event: &Event, mut rules: EligibilityRules) -> Result<EligibilityRules, String> {
+    if rules.min_age.is_some_and(|a| !(1..=120).contains(&a)) {
+        return Err("Minimalan broj godina mora biti od 1 do 120".to_string());
+    }
+
+    let mut domains: Vec<String> = Vec::new();
+    for raw in &rules.allowed_email_domains {
+        let domain = raw.trim().trim_start_matches('@').to_lowercase();
+        let valid = domain.contains('.')
+            && domain.len() <= 255
+            && domain.split('.').all(|part| {
+                !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            });
+        if !valid {
+            return Err(format!("Neispravan domen: {}", raw));
+        }
+        if !domains.contains(&domain) {
+            domains.push(domain);
+        }
+    }
+    rules.allowed_email_domains = domains;
+
+    let mut attributes: Vec<String> = Vec::new();
+    for raw in &rules.required_attributes {
+        let key = raw.trim().to_lowercase();
+        if !PROFILE_FIELDS.contains(&key.as_str()) && !valid_attribute_key(&key) {
+            return Err(format!("Neispravan naziv atributa: {}", raw));
+        }
+        if !attributes.contains(&key) {
+            attributes.push(key);
+        }
+    }
+    rules.required_attributes = attributes;
+
+    if rules.allowed_email_domains.len() > 20 || rules.required_attributes.len() > 20 {
+        return Err("Najvise 20 domena i 20 obaveznih atributa".to_string());
+    }
+    if let Some(max) = rules.max_per_series {
+        if max < 1 {
+            return Err("Limit po seriji mora biti najmanje 1".to_string());
+        }
+        if event.series_id.is_none() {
+            return Err("Limit po seriji vazi samo za termine serije".to_string());
+        }
+    }
+    Ok(rules)
+}
+
+async fn load_rules(db: &PgPool, event_id: Uuid) -> Result<EligibilityRules, sqlx::Error> {
+    let rules = sqlx::query_scalar::<_, sqlx::types::Json<EligibilityRules>>(
+        "SELECT rules FROM event_eligibility WHERE event_id = $1",
+    )
+    .bind(event_id)
+    .fetch_optional(db)
+    .await?;
+    Ok(rules.map(|r| r.0).unwrap_or_default())
+}
+
+/// GET /events/:id/eligibility - uslovi za prijavu (prazno = nema uslova)
+pub async fn get_eligibility(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<EligibilityRules>>) {
+    match sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id = $1 AND deleted_at IS NULL")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+    {
+        Ok(Some(e)) if can_see(&headers, &state, &e, false).await => {}
+        Ok(_) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Dogadjaj ne postoji"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+
+    match load_rules(&state.db, id).await {
+        Ok(rules) => (StatusCode::OK, Json(ApiResponse::success("Uslovi za prijavu", rules))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// PUT /events/:id/eligibility - postavlja uslove (ko sme da menja dogadjaj);
+/// sa apply_to_series i za sve buduce termine iste serije
+pub async fn update_eligibility(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<EligibilityRequest>,
+) -> (StatusCode, Json<ApiResponse<EligibilityRules>>) {
+    let (event, _) = match load_managed_event(&state, &headers, id, Permission::Edit).await {
+        Ok(found) => found,
+        Err(err) => return err,
+    };
+    let rules = match normalize_rules(&event, req.rules) {
+        Ok(r) => r,
+        Err(msg) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(&msg))),
+    };
+
+    let mut targets = vec![event.id];
+    if let (true, Some(series_id)) = (req.apply_to_series, event.series_id) {
+        match sqlx::query_scalar::<_, Uuid>(
+            "SELECT id FROM events WHERE series_id = $1 AND id <> $2 AND date_time >= $3 AND deleted_at IS NULL",
+        )
+        .bind(series_id)
+        .bind(event.id)
+        .bind(local_now())
+        .fetch_all(&state.db)
+        .await
+        {
+            Ok(ids) => targets.extend(ids),
+            Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+        }
+    }
+
+    let result = async {
+        let mut tx = state.db.begin().await?;
+        if rules.is_empty() {
+            sqlx::query("DELETE FROM event_eligibility WHERE event_id = ANY($1)")
+                .bind(&targets)
+                .execute(&mut *tx)
+                .await?;
+        } else {
+            sqlx::query(
+                "INSERT INTO event_eligibility (event_id, rules)
+                 SELECT id, $2 FROM UNNEST($1::uuid[]) AS id
+                 ON CONFLICT (event_id) DO UPDATE SET rules = EXCLUDED.rules, updated_at = NOW()",
+            )
+            .bind(&targets)
+            .bind(sqlx::types::Json(&rules))
+            .execute(&mut *tx)
+            .await?;
+        }
+        // termini koje serija tek napravi preuzimaju uslove od serije
+        if let (true, Some(series_id)) = (req.apply_to_series, event.series_id) {
+            sqlx::query("UPDATE event_series SET eligibility = $2, updated_at = NOW() WHERE id = $1")
+                .bind(series_id)
+                .bind((!rules.is_empty()).then_some(sqlx::types::Json(&rules)))
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await
+    }
+    .await;
+
+    match result {
+        Ok(_) => (StatusCode::OK, Json(ApiResponse::success("Uslovi sacuvani", rules))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// DELETE /events/:id/eligibility - svi mogu da se prijave
+pub async fn delete_eligibility(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    if let Err(err) = load_managed_event(&state, &headers, id, Permission::Edit).await {
+        return err;
+    }
+
+    let result = sqlx::query("DELETE FROM event_eligibility WHERE event_id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await;
+
+    match result {
+        Ok(r) if r.rows_affected() > 0 => (StatusCode::OK, Json(ApiResponse::success("Uslovi obrisani", ()))),
+        Ok(_) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Dogadjaj nema uslove za prijavu"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// Internal: uslovi za prijavu sa datumom dogadjaja i ostalim terminima serije (registration-service)
+pub async fn internal_eligibility(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<EventEligibility>>) {
+    let event = match sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id = $1 AND deleted_at IS NULL")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+    {
+        Ok(Some(e)) => e,
+        Ok(None) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Dogadjaj ne postoji"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+
+    let rules = match load_rules(&state.db, id).await {
+        Ok(r) => r,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+
+    let series_event_ids = match (rules.max_per_series, event.series_id) {
+        (Some(_), Some(series_id)) => {
+            match sqlx::query_scalar::<_, Uuid>(
+                "SELECT id FROM events WHERE series_id = $1 AND id <> $2 AND deleted_at IS NULL",
+            )
+            .bind(series_id)
+            .bind(id)
+            .fetch_all(&state.db)
+            .await
+            {
+                Ok(ids) => ids,
+                Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+            }
+        }
+        _ => Vec::new(),
+    };
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            "Uslovi za prijavu",
+            EventEligibility { rules, date_time: event.date_time, series_event_ids },
+        )),
+    )
+}
+
+// ---- Izdvojeno i kolekcije ----
+
+/// PUT /events/:id/featured (Admin) - izdvaja dogadjaj na pocetnu ili ga skida
+pub async fn set_featured(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<FeaturedRequest>,
+) -> Response {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::<()>::error(&msg))).into_response(),
+    };
+    if let Err((status, msg)) = require_role(&claims, "Admin") {
+        return (status, Json(ApiResponse::<()>::error(&msg))).into_response();
+    }
+
+    let result = sqlx::query_as::<_, Event>(
+        "UPDATE events SET featured = $2, featured_priority = CASE WHEN $2 THEN COALESCE($3, featured_priority) ELSE 0 END
+         WHERE id = $1 AND deleted_at IS NULL RETURNING *",
+    )
+    .bind(id)
+    .bind(req.featured)
+    .bind(req.priority)
+    .fetch_optional(&state.db)
+    .await;
+
+    match result {
+        Ok(Some(mut event)) => {
+            let _ = attach_tags(&state.db, std::slice::from_mut(&mut event)).await;
+            let msg = if event.featured { "Dogadjaj izdvojen" } else { "Dogadjaj vise nije izdvojen" };
+            event_response(StatusCode::OK, msg, event)
+        }
+        Ok(None) => (StatusCode::NOT_FOUND, Json(ApiResponse::<()>::error("Dogadjaj ne postoji"))).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::<()>::error(&format!("Greska: {}", e)))).into_response(),
+    }
+}
+
+/// Najvise dogadjaja u jednoj kolekciji
+const MAX_COLLECTION_EVENTS: usize = 100;
+
+/// Uslov za kolekciju koja je trenutno aktivna ($1 = sada)
+const ACTIVE_COLLECTION_SQL: &str =
+    "(c.active_from IS NULL OR c.active_from <= $1) AND (c.active_until IS NULL OR c.active_until > $1)";
+
+/// Proverava zahtev i vraca slug (iz zadatog sluga ili naziva)
+fn validate_collection(req: &CollectionRequest) -> Result<String, &'static str> {
+    let title = req.title.trim();
+    if title.is_empty() || title.chars().count() > 255 {
+        return Err("Naziv kolekcije mora imati od 1 do 255 karaktera");
+    }
+    let slug = slugify(req.slug.as_deref().unwrap_or(title));
+    if slug.is_empty() || slug.len() > 100 {
+        return Err("Slug mora imati od 1 do 100 slova ili brojeva");
+    }
+    if let (Some(from), Some(until)) = (req.active_from, req.active_until) {
+        if until <= from {
+            return Err("Kraj perioda mora biti posle pocetka");
+        }
+    }
+    if req.event_ids.as_ref().is_some_and(|ids| ids.len() > MAX_COLLECTION_EVENTS) {
+        return Err("Kolekcija moze imati najvise 100 dogadjaja");
+    }
+    Ok(slug)
+}
+
+/// Dogadjaji bez duplikata, po redosledu; greska ako neki ne postoji (ili je u kanti)
+async fn collection_event_ids(db: &PgPool, ids: &[Uuid]) -> Result<Vec<Uuid>, (StatusCode, String)> {
+    let mut unique: Vec<Uuid> = Vec::with_capacity(ids.len());
+    for id in ids {
+        if !unique.contains(id) {
+            unique.push(*id);
+        }
+    }
+
+    let existing = sqlx::query_scalar::<_, Uuid>("SELECT id FROM events WHERE id = ANY($1) AND deleted_at IS NULL")
+        .bind(&unique)
+        .fetch_all(db)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Greska: {}", e)))?;
+
+    match unique.iter().find(|id| !existing.contains(id)) {
+        Some(missing) => Err((StatusCode::BAD_REQUEST, format!("Dogadjaj {} ne postoji", missing))),
+        None => Ok(unique),
+    }
+}
+
+/// Menja dogadjaje kolekcije; redosled je redosled u listi
+async fn replace_collection_events(
+    tx: &mut Transaction<'_, Postgres>,
+    collection_id: Uuid,
+    event_ids: &[Uuid],
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM collection_events WHERE collection_id = $1")
+        .bind(collection_id)
+        .execute(&mut **tx)
+        .await?;
+    sqlx::query(
+        "INSERT INTO collection_events (collection_id, event_id, position)
+         SELECT $1, id, pos FROM UNNEST($2::uuid[]) WITH ORDINALITY AS t(id, pos)",
+    )
+    .bind(collection_id)
+    .bind(event_ids)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Objavljeni javni dogadjaji kolekcije po redosledu, sa tagovima i prevodima
+async fn collection_details(
+    state: &AppState,
+    collection: Collection,
+    locales: &[String],
+) -> Result<CollectionDetails, sqlx::Error> {
+    let mut events = sqlx::query_as::<_, Event>(
+        "SELECT e.* FROM collection_events ce JOIN events e ON e.id = ce.event_id
+         WHERE ce.collection_id = $1 AND e.status = 'published' AND e.visibility = 'public' AND e.deleted_at IS NULL
+         ORDER BY ce.position",
+    )
+    .bind(collection.id)
+    .fetch_all(&state.db)
+    .await?;
+    attach_tags(&state.db, &mut events).await?;
+    apply_translations(&state.db, &mut events, locales, &state.default_locale).await?;
+    Ok(CollectionDetails { collection, events })
+}
+
+fn collection_error(e: sqlx::Error) -> (StatusCode, Json<ApiResponse<CollectionDetails>>) {
+    match e {
+        sqlx::Error::Database(e) if e.is_unique_violation() => {
+            (StatusCode::CONFLICT, Json(ApiResponse::error("Kolekcija sa tim slugom vec postoji")))
+        }
+        e => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// GET /collections - aktivne kolekcije; Admin sa all=true vidi i neaktivne
+pub async fn list_collections(
+    headers: HeaderMap,
+    Query(query): Query<CollectionQuery>,
+    State(state): State<AppState>,
+) -> (StatusCode, Json<ApiResponse<Vec<CollectionSummary>>>) {
+    if query.all {
+        let claims = match extract_claims(&headers, &state.jwt_secret) {
+            Ok(c) => c,
+            Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+        };
+        if let Err((status, msg)) = require_role(&claims, "Admin") {
+            return (status, Json(ApiResponse::error(&msg)));
+        }
+    }
+
+    let filter = if query.all { "($1::timestamp IS NOT NULL)" } else { ACTIVE_COLLECTION_SQL };
+    let result = sqlx::query_as::<_, CollectionSummary>(&format!(
+        "SELECT c.*,
+                (SELECT COUNT(*) FROM collection_events ce JOIN events e ON e.id = ce.event_id
+                 WHERE ce.collection_id = c.id AND e.status = 'published' AND e.visibility = 'public'
+                   AND e.deleted_at IS NULL) AS event_count
+         FROM collections c WHERE {}
+         ORDER BY c.active_from DESC NULLS LAST, c.created_at DESC",
+        filter
+    ))
+    .bind(local_now())
+    .fetch_all(&state.db)
+    .await;
+
+    match result {
+        Ok(list) => (StatusCode::OK, Json(ApiResponse::success("Kolekcije", list))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// GET /collections/:slug?lang=en - kolekcija sa dogadjajima; neaktivnu vidi samo Admin
+pub async fn get_collection(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    Query(query): Query<LangQuery>,
+) -> (StatusCode, Json<ApiResponse<CollectionDetails>>) {
+    let is_admin = extract_claims(&headers, &state.jwt_secret).is_ok_and(|c| c.role == "Admin");
+
+    let found = sqlx::query_as::<_, Collection>(&format!(
+        "SELECT c.* FROM collections c WHERE c.slug = $2 AND ($3 OR {})",
+        ACTIVE_COLLECTION_SQL
+    ))
+    .bind(local_now())
+    .bind(&slug)
+    .bind(is_admin)
+    .fetch_optional(&state.db)
+    .await;
+
+    let collection = match found {
+        Ok(Some(c)) => c,
+        Ok(None) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Kolekcija ne postoji"))),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    };
+
+    let locales = preferred_locales(&headers, query.lang.as_deref());
+    match collection_details(&state, collection, &locales).await {
+        Ok(details) => (StatusCode::OK, Json(ApiResponse::success("Kolekcija", details))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// POST /collections (Admin)
+pub async fn create_collection(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Json(req): Json<CollectionRequest>,
+) -> (StatusCode, Json<ApiResponse<CollectionDetails>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    if let Err((status, msg)) = require_role(&claims, "Admin") {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+    let slug = match validate_collection(&req) {
+        Ok(s) => s,
+        Err(msg) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(msg))),
+    };
+    let event_ids = match collection_event_ids(&state.db, req.event_ids.as_deref().unwrap_or_default()).await {
+        Ok(ids) => ids,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+
+    let result = async {
+        let mut tx = state.db.begin().await?;
+        let collection = sqlx::query_as::<_, Collection>(
+            "INSERT INTO collections (slug, title, description, active_from, active_until, created_by)
+             VALUES ($1, $2, $3, $4, $5, $6) RETURNING *",
+        )
+        .bind(&slug)
+        .bind(req.title.trim())
+        .bind(req.description.trim())
+        .bind(req.active_from)
+        .bind(req.active_until)
+        .bind(Uuid::parse_str(&claims.sub).unwrap_or_default())
+        .fetch_one(&mut *tx)
+        .await?;
+        replace_collection_events(&mut tx, collection.id, &event_ids).await?;
+        tx.commit().await?;
+        collection_details(&state, collection, &[]).await
+    };
+
+    match result.await {
+        Ok(details) => (StatusCode::CREATED, Json(ApiResponse::success("Kolekcija kreirana", details))),
+        Err(e) => collection_error(e),
+    }
+}
+
+/// PUT /collections/:slug (Admin) - menja naziv, opis i period; dogadjaje samo ako je poslat event_ids
+pub async fn update_collection(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    Json(req): Json<CollectionRequest>,
+) -> (StatusCode, Json<ApiResponse<CollectionDetails>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    if let Err((status, msg)) = require_role(&claims, "Admin") {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+    // bez novog sluga ostaje stari (ne pravi se iz novog naziva)
+    let new_slug = match validate_collection(&req) {
+        Ok(s) if req.slug.is_some() => s,
+        Ok(_) => slug.clone(),
+        Err(msg) => return (StatusCode::BAD_REQUEST, Json(ApiResponse::error(msg))),
+    };
+    let event_ids = match &req.event_ids {
+        Some(ids) => match collection_event_ids(&state.db, ids).await {
+            Ok(ids) => Some(ids),
+            Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+        },
+        None => None,
+    };
+
+    let result = async {
+        let mut tx = state.db.begin().await?;
+        let collection = sqlx::query_as::<_, Collection>(
+            "UPDATE collections SET slug = $2, title = $3, description = $4, active_from = $5, active_until = $6,
+                                    updated_at = NOW()
+             WHERE slug = $1 RETURNING *",
+        )
+        .bind(&slug)
+        .bind(&new_slug)
+        .bind(req.title.trim())
+        .bind(req.description.trim())
+        .bind(req.active_from)
+        .bind(req.active_until)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(collection) = collection else {
+            return Ok(None);
+        };
+        if let Some(ids) = &event_ids {
+            replace_collection_events(&mut tx, collection.id, ids).await?;
+        }
+        tx.commit().await?;
+        collection_details(&state, collection, &[]).await.map(Some)
+    };
+
+    match result.await {
+        Ok(Some(details)) => (StatusCode::OK, Json(ApiResponse::success("Kolekcija azurirana", details))),
+        Ok(None) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Kolekcija ne postoji"))),
+        Err(e) => collection_error(e),
+    }
+}
+
+/// DELETE /collections/:slug (Admin) - dogadjaji ostaju, brise se samo kolekcija
+pub async fn delete_collection(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    if let Err((status, msg)) = require_role(&claims, "Admin") {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+
+    match sqlx::query("DELETE FROM collections WHERE slug = $1").bind(&slug).execute(&state.db).await {
+        Ok(r) if r.rows_affected() > 0 => (StatusCode::OK, Json(ApiResponse::success("Kolekcija obrisana", ()))),
+        Ok(_) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Kolekcija ne postoji"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn parse_near_accepts_lat_lng_pair() {
+        assert_eq!(parse_near("45.2671,19.8335"), Some((45.2671, 19.8335)));
+        assert_eq!(parse_near(" -33.9 , 151.2 "), Some((-33.9, 151.2)));
+
+        assert_eq!(parse_near("45.2671"), None);
+        assert_eq!(parse_near("sever,jug"), None);
+        assert_eq!(parse_near("91,0"), None);
+        assert_eq!(parse_near("0,180.5"), None);
+    }
+
+    #[test]
+    fn bounding_box_stays_in_one_range_away_from_edges() {
+        let ((south, north), lngs) = bounding_box(45.0, 20.0, 10.0);
+        assert!(south < 45.0 && north > 45.0);
+        assert!((north - south - 20.0 / KM_PER_DEGREE).abs() < 1e-9);
+        assert_eq!(lngs.len(), 1);
+        // na 45. paraleli stepen duzine je kraci, pa je opseg siri nego za sirinu
+        let (west, east) = lngs[0];
+        assert!(east - west > north - south);
+    }
+
+    #[test]
+    fn bounding_box_splits_at_antimeridian() {
+        let (_, lngs) = bounding_box(-17.0, 179.9, 50.0);
+        assert_eq!(lngs.len(), 2);
+        assert!(lngs[0].0 > 179.0 && lngs[0].1 == 180.0);
+        assert!(lngs[1].0 == -180.0 && lngs[1].1 < -179.0);
+
+        let (_, lngs) = bounding_box(-17.0, -179.9, 50.0);
+        assert_eq!(lngs.len(), 2);
+        assert!(lngs[0].0 > 179.0 && lngs[0].1 == 180.0);
+        assert!(lngs[1].0 == -180.0 && lngs[1].1 < -179.0);
+    }
+
+    #[test]
+    fn bounding_box_around_pole_takes_all_longitudes() {
+        let ((south, north), lngs) = bounding_box(89.9, 10.0, 50.0);
+        assert!(south < 89.9);
+        assert_eq!(north, 90.0);
+        assert_eq!(lngs, vec![(-180.0, 180.0)]);
+
+        let ((south, _), lngs) = bounding_box(-89.95, 0.0, 20.0);
+        assert_eq!(south, -90.0);
+        assert_eq!(lngs, vec![(-180.0, 180.0)]);
+    }
+
+    #[test]
+    fn session_must_end_after_start_with_positive_capacity() {
+        let (start, end) = (at("2027-03-01 10:00"), at("2027-03-01 11:30"));
+        assert!(validate_session(start, end, None).is_ok());
+        assert!(validate_session(start, end, Some(40)).is_ok());
+
+        assert!(validate_session(start, start, None).is_err());
+        assert!(validate_session(end, start, None).is_err());
+        assert!(validate_session(start, end, Some(0)).is_err());
+        assert!(validate_session(start, end, Some(-5)).is_err());
+    }
+
+    #[test]
+    fn ticket_type_rules() {
+        let start = Some(at("2027-02-01 00:00"));
+        let end = Some(at("2027-02-28 23:59"));
+        assert!(validate_ticket_type(0, "RSD", 100, None, None, "public").is_ok());
+        assert!(validate_ticket_type(150_000, "EUR", 20, start, end, "hidden").is_ok());
+
+        assert!(validate_ticket_type(1000, "RSD", 0, None, None, "public").is_err());
+        assert!(validate_ticket_type(-1, "RSD", 10, None, None, "public").is_err());
+        assert!(validate_ticket_type(1000, "rsd", 10, None, None, "public").is_err());
+        assert!(validate_ticket_type(1000, "DINAR", 10, None, None, "public").is_err());
+        assert!(validate_ticket_type(1000, "RSD", 10, end, start, "public").is_err());
+        assert!(validate_ticket_type(1000, "RSD", 10, start, start, "public").is_err());
+        assert!(validate_ticket_type(1000, "RSD", 10, None, None, "secret").is_err());
+    }
+
+    #[test]
+    fn access_settings() {
+        assert!(validate_access(None, None).is_ok());
+        assert!(validate_access(Some("private"), Some("tajna123")).is_ok());
+        assert!(validate_access(Some("unlisted"), None).is_ok());
+
+        assert!(validate_access(Some("secret"), None).is_err());
+        assert!(validate_access(Some("public"), Some(&"x".repeat(65))).is_err());
+    }
+
+    #[test]
+    fn capacity_bounds() {
+        assert!(validate_capacity(1).is_ok());
+        assert!(validate_capacity(MAX_CAPACITY).is_ok());
+
+        assert!(validate_capacity(0).is_err());
+        assert!(validate_capacity(-10).is_err());
+        assert!(validate_capacity(MAX_CAPACITY + 1).is_err());
+    }
+
+    #[test]
+    fn updated_message_reports_moved_registrations() {
+        let sync = |moved, promoted| CapacitySync { moved, promoted };
+        assert_eq!(updated_message("Izmenjeno", &sync(0, 0), None), "Izmenjeno");
+        assert_eq!(
+            updated_message("Izmenjeno", &sync(0, 3), None),
+            "Izmenjeno; sa liste cekanja potvrdjeno: 3"
+        );
+        assert_eq!(
+            updated_message("Izmenjeno", &sync(2, 0), Some(CapacityStrategy::Waitlist)),
+            "Izmenjeno; na listu cekanja premesteno: 2"
+        );
+        assert_eq!(
+            updated_message("Izmenjeno", &sync(2, 0), Some(CapacityStrategy::Cancel)),
+            "Izmenjeno; otkazano prijava: 2"
+        );
+    }
+
+    #[test]
+    fn category_name_and_slug_limits() {
+        let req = |name: Option<&str>, slug: Option<&str>| CategoryRequest {
+            name: name.map(str::to_string),
+            slug: slug.map(str::to_string),
+            translations: None,
+            sort_order: None,
+        };
+        assert!(validate_category(&req(Some("Radionica"), None)).is_ok());
+        assert!(validate_category(&req(None, Some("Muzički festival"))).is_ok());
+
+        assert!(validate_category(&req(Some("   "), None)).is_err());
+        assert!(validate_category(&req(Some(&"a".repeat(51)), None)).is_err());
+        assert!(validate_category(&req(None, Some("???"))).is_err());
+    }
+
+    fn question(parent: Option<&Question>, created: &str) -> Question {
+        let created_at = at(created);
+        Question {
+            id: Uuid::new_v4(),
+            event_id: Uuid::nil(),
+            parent_id: parent.map(|p| p.id),
+            user_id: Uuid::new_v4(),
+            author_name: "Ana".to_string(),
+            body: "Pitanje".to_string(),
+            from_staff: false,
+            answered_at: None,
+            pinned: false,
+            hidden: false,
+            upvotes: 0,
+            deleted_at: None,
+            created_at,
+            updated_at: created_at,
+            voted: false,
+        }
+    }
+
+    #[test]
+    fn threads_put_pinned_and_answered_first_then_votes() {
+        let plain = question(None, "2027-01-01 10:00");
+        let mut popular = question(None, "2027-01-01 09:00");
+        popular.upvotes = 5;
+        let mut answered = question(None, "2027-01-01 08:00");
+        answered.answered_at = Some(at("2027-01-01 12:00"));
+        let mut pinned = question(None, "2027-01-01 07:00");
+        pinned.pinned = true;
+        let ids = [pinned.id, answered.id, popular.id, plain.id];
+
+        let threads = build_threads(vec![plain, popular, answered, pinned], false);
+        assert_eq!(threads.iter().map(|t| t.question.id).collect::<Vec<_>>(), ids);
+    }
+
+    #[test]
+    fn threads_attach_replies_oldest_first() {
+        let root = question(None, "2027-01-01 10:00");
+        let late = question(Some(&root), "2027-01-01 12:00");
+        let early = question(Some(&root), "2027-01-01 11:00");
+        let orphan = question(Some(&question(None, "2027-01-01 09:00")), "2027-01-01 11:30");
+        let (root_id, early_id, late_id) = (root.id, early.id, late.id);
+
+        let threads = build_threads(vec![late, root, orphan, early], false);
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].question.id, root_id);
+        assert_eq!(threads[0].replies.iter().map(|r| r.id).collect::<Vec<_>>(), [early_id, late_id]);
+    }
+
+    /// sakriveno pitanje, obrisano bez odgovora i obrisano sa jednim vidljivim i jednim sakrivenim odgovorom
+    fn moderated_questions() -> (Vec<Question>, Uuid) {
+        let mut hidden = question(None, "2027-01-01 10:00");
+        hidden.hidden = true;
+        let mut deleted = question(None, "2027-01-01 10:00");
+        deleted.deleted_at = Some(at("2027-01-02 10:00"));
+        let mut deleted_answered = question(None, "2027-01-01 10:00");
+        deleted_answered.deleted_at = Some(at("2027-01-02 10:00"));
+        let reply = question(Some(&deleted_answered), "2027-01-01 11:00");
+        let mut hidden_reply = question(Some(&deleted_answered), "2027-01-01 12:00");
+        hidden_reply.hidden = true;
+        let kept = deleted_answered.id;
+        (vec![hidden, deleted, deleted_answered, reply, hidden_reply], kept)
+    }
+
+    #[test]
+    fn threads_hide_hidden_and_deleted_without_replies() {
+        let (questions, kept) = moderated_questions();
+        let visitor = build_threads(questions, false);
+        assert_eq!(visitor.len(), 1);
+        assert_eq!(visitor[0].question.id, kept);
+        assert_eq!(visitor[0].replies.len(), 1);
+
+        let (questions, kept) = moderated_questions();
+        let moderator = build_threads(questions, true);
+        assert_eq!(moderator.len(), 2);
+        assert_eq!(moderator.iter().find(|t| t.question.id == kept).unwrap().replies.len(), 2);
+    }
+
+    #[test]
+    fn collection_slug_and_period() {
+        let req = |title: &str, slug: Option<&str>| CollectionRequest {
+            title: title.to_string(),
+            slug: slug.map(str::to_string),
+            description: String::new(),
+            active_from: None,
+            active_until: None,
+            event_ids: None,
+        };
+        assert_eq!(validate_collection(&req("Letnji festivali", None)), Ok("letnji-festivali".to_string()));
+        assert_eq!(validate_collection(&req("Leto", Some("Izbor urednika"))), Ok("izbor-urednika".to_string()));
+
+        assert!(validate_collection(&req("  ", None)).is_err());
+        assert!(validate_collection(&req("!!!", None)).is_err());
+        assert!(validate_collection(&req("Leto", Some(&"a".repeat(101)))).is_err());
+
+        let mut period = req("Leto", None);
+        period.active_from = Some(at("2027-06-01 00:00"));
+        period.active_until = Some(at("2027-06-01 00:00"));
+        assert!(validate_collection(&period).is_err());
+        period.active_until = Some(at("2027-09-01 00:00"));
+        assert!(validate_collection(&period).is_ok());
+
+        period.event_ids = Some(vec![Uuid::nil(); MAX_COLLECTION_EVENTS + 1]);
+        assert!(validate_collection(&period).is_err());
+    }
 }
@@ -0,0 +1,153 @@
+// Jezici - izbor jezika iz ?lang= ili Accept-Language i prevodi dogadjaja
+
+use std::collections::HashMap;
+
+use axum::http::{header, HeaderMap};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{Event, EventTranslation};
+
+/// "en-US" -> "en"; jezik je 2-3 slova, ostalo se odbacuje
+pub fn normalize_locale(raw: &str) -> Option<String> {
+    let primary = raw.trim().split(['-', '_']).next()?.to_lowercase();
+    let valid = (2..=3).contains(&primary.len()) && primary.chars().all(|c| c.is_ascii_lowercase());
+    valid.then_some(primary)
+}
+
+/// Jezici po redu zelje: ?lang= ima prednost, inace Accept-Language po q vrednosti
+pub fn preferred_locales(headers: &HeaderMap, lang: Option<&str>) -> Vec<String> {
+    if let Some(locale) = lang.and_then(normalize_locale) {
+        return vec![locale];
+    }
+
+    let accept = headers
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let mut weighted: Vec<(f32, String)> = accept
+        .split(',')
+        .filter_map(|part| {
+            let mut pieces = part.split(';');
+            let locale = normalize_locale(pieces.next()?)?;
+            let q = pieces
+                .find_map(|p| p.trim().strip_prefix("q="))
+                .map(|q| q.parse().unwrap_or(0.0))
+                .unwrap_or(1.0);
+            (q > 0.0).then_some((q, locale))
+        })
+        .collect();
+    // sort je stabilan - isti q zadrzava redosled iz zaglavlja
+    weighted.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut locales: Vec<String> = Vec::new();
+    for (_, locale) in weighted {
+        if !locales.contains(&locale) {
+            locales.push(locale);
+        }
+    }
+    locales
+}
+
+/// Prvi jezik iz zelja koji dogadjaj ima; podrazumevani jezik su polja samog dogadjaja
+fn pick<'a>(
+    prefs: &[String],
+    default_locale: &str,
+    available: Option<&'a HashMap<String, EventTranslation>>,
+) -> Option<&'a EventTranslation> {
+    for locale in prefs {
+        if locale == default_locale {
+            return None;
+        }
+        if let Some(t) = available.and_then(|a| a.get(locale)) {
+            return Some(t);
+        }
+    }
+    None
+}
+
+/// Menja naziv, opis i lokaciju prevodom na najpozeljniji dostupan jezik
+/// i puni `locale`; bez prevoda ostaje podrazumevani jezik
+pub async fn apply_translations(
+    db: &PgPool,
+    events: &mut [Event],
+    prefs: &[String],
+    default_locale: &str,
+) -> Result<(), sqlx::Error> {
+    let wanted: Vec<String> = prefs.iter().take_while(|l| *l != default_locale).cloned().collect();
+    let mut by_event: HashMap<Uuid, HashMap<String, EventTranslation>> = HashMap::new();
+
+    if !wanted.is_empty() && !events.is_empty() {
+        let ids: Vec<Uuid> = events.iter().map(|e| e.id).collect();
+        let rows = sqlx::query_as::<_, EventTranslation>(
+            "SELECT * FROM event_translations WHERE event_id = ANY($1) AND locale = ANY($2)",
+        )
+        .bind(&ids)
+        .bind(&wanted)
+        .fetch_all(db)
+        .await?;
+
+        for t in rows {
+            by_event.entry(t.event_id).or_default().insert(t.locale.clone(), t);
+        }
+    }
+
+    for event in events.iter_mut() {
+        match pick(prefs, default_locale, by_event.get(&event.id)) {
+            Some(t) => {
+                event.title = t.title.clone();
+                if let (Some(description), Some(html)) = (&t.description, &t.description_html) {
+                    event.description = description.clone();
+                    event.description_html = html.clone();
+                }
+                if let Some(location) = &t.location {
+                    event.location = location.clone();
+                }
+                event.locale = Some(t.locale.clone());
+            }
+            None => event.locale = Some(default_locale.to_string()),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn accept(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT_LANGUAGE, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn normalize_locale_keeps_primary_language() {
+        assert_eq!(normalize_locale("en-US").as_deref(), Some("en"));
+        assert_eq!(normalize_locale(" sr_Latn_RS ").as_deref(), Some("sr"));
+        assert_eq!(normalize_locale("FIL").as_deref(), Some("fil"));
+
+        assert_eq!(normalize_locale(""), None);
+        assert_eq!(normalize_locale("*"), None);
+        assert_eq!(normalize_locale("e"), None);
+        assert_eq!(normalize_locale("engl"), None);
+        assert_eq!(normalize_locale("e1"), None);
+    }
+
+    #[test]
+    fn preferred_locales_follow_q_values() {
+        let prefs = preferred_locales(&accept("de;q=0.5, en-GB, en;q=0.9, sr;q=0.9, fr;q=0"), None);
+        assert_eq!(prefs, ["en", "sr", "de"]);
+
+        assert!(preferred_locales(&HeaderMap::new(), None).is_empty());
+        assert!(preferred_locales(&accept("*"), None).is_empty());
+    }
+
+    #[test]
+    fn lang_param_overrides_header() {
+        assert_eq!(preferred_locales(&accept("en"), Some("sr-RS")), ["sr"]);
+        // neispravan ?lang= se ignorise
+        assert_eq!(preferred_locales(&accept("en"), Some("??")), ["en"]);
+    }
+}
@@ -0,0 +1,316 @@
+// iCalendar (RFC 5545) - izvoz dogadjaja u .ics (pojedinacno i kao feed) i citanje VEVENT-a za uvoz
+
+use chrono::{Duration, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::markdown::plain_text;
+use crate::models::Event;
+use crate::rrule::{format_ical_datetime, parse_ical_datetime};
+
+/// Vremena u bazi su lokalna (Srbija)
+pub const TIMEZONE: &str = "Europe/Belgrade";
+const PRODID: &str = "-//Event Management//Events//SR";
+/// Dogadjaji nemaju kraj - u kalendaru traju 2h
+pub const DEFAULT_DURATION_HOURS: i64 = 2;
+
+/// CET/CEST pravila (poslednja nedelja u martu/oktobru)
+const VTIMEZONE: &[&str] = &[
+    "BEGIN:VTIMEZONE",
+    "TZID:Europe/Belgrade",
+    "BEGIN:DAYLIGHT",
+    "TZOFFSETFROM:+0100",
+    "TZOFFSETTO:+0200",
+    "TZNAME:CEST",
+    "DTSTART:19700329T020000",
+    "RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=-1SU",
+    "END:DAYLIGHT",
+    "BEGIN:STANDARD",
+    "TZOFFSETFROM:+0200",
+    "TZOFFSETTO:+0100",
+    "TZNAME:CET",
+    "DTSTART:19701025T030000",
+    "RRULE:FREQ=YEARLY;BYMONTH=10;BYDAY=-1SU",
+    "END:STANDARD",
+    "END:VTIMEZONE",
+];
+
+/// Escape za TEXT vrednosti (\, ; , i novi redovi)
+pub fn escape_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Prelama liniju na 75 okteta (nastavak pocinje razmakom), ne sece UTF-8 karaktere
+fn fold_line(line: &str, out: &mut String) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+fn utc_stamp(dt: &NaiveDateTime) -> String {
+    format!("{}Z", format_ical_datetime(dt))
+}
+
+fn push_event(lines: &mut Vec<String>, event: &Event, app_url: &str, now: &NaiveDateTime) {
+    let end = event.date_time + Duration::hours(DEFAULT_DURATION_HOURS);
+
+    lines.push("BEGIN:VEVENT".to_string());
+    lines.push(format!("UID:{}@event-management", event.id));
+    lines.push(format!("DTSTAMP:{}", utc_stamp(now)));
+    lines.push(format!("DTSTART;TZID={}:{}", TIMEZONE, format_ical_datetime(&event.date_time)));
+    lines.push(format!("DTEND;TZID={}:{}", TIMEZONE, format_ical_datetime(&end)));
+    lines.push(format!("SUMMARY:{}", escape_text(&event.title)));
+    lines.push(format!("DESCRIPTION:{}", escape_text(&plain_text(&event.description))));
+    lines.push(format!("LOCATION:{}", escape_text(&event.location)));
+    if let (Some(lat), Some(lng)) = (event.latitude, event.longitude) {
+        lines.push(format!("GEO:{:.6};{:.6}", lat, lng));
+    }
+    lines.push(format!("CATEGORIES:{}", escape_text(&event.category)));
+    lines.push(format!("URL:{}/?event={}", app_url, event.id));
+    lines.push(format!("CREATED:{}", utc_stamp(&event.created_at)));
+    lines.push(format!("LAST-MODIFIED:{}", utc_stamp(&event.updated_at)));
+    lines.push("STATUS:CONFIRMED".to_string());
+    lines.push("END:VEVENT".to_string());
+}
+
+/// Ceo VCALENDAR sa datim dogadjajima; `name` je ime kalendara za feed-ove
+pub fn render_calendar(events: &[Event], name: Option<&str>, app_url: &str) -> String {
+    let now = Utc::now().naive_utc();
+    let mut lines: Vec<String> = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODID),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+    ];
+    if let Some(name) = name {
+        lines.push(format!("X-WR-CALNAME:{}", escape_text(name)));
+        lines.push(format!("X-WR-TIMEZONE:{}", TIMEZONE));
+        // klijenti koji ovo postuju osvezavaju pretplatu na sat
+        lines.push("REFRESH-INTERVAL;VALUE=DURATION:PT1H".to_string());
+        lines.push("X-PUBLISHED-TTL:PT1H".to_string());
+    }
+    lines.extend(VTIMEZONE.iter().map(|l| l.to_string()));
+    for event in events {
+        push_event(&mut lines, event, app_url, &now);
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut out = String::new();
+    for line in &lines {
+        fold_line(line, &mut out);
+    }
+    out
+}
+
+// ---- Citanje (uvoz) ----
+
+/// Jedna linija iz .ics fajla: NAME;PARAM=VAL:value
+#[derive(Debug)]
+pub struct Property {
+    pub name: String,
+    pub params: Vec<(String, String)>,
+    pub value: String,
+}
+
+impl Property {
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Spaja prelomljene linije (nastavak pocinje razmakom ili tabom)
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.split('\n') {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        match raw.strip_prefix([' ', '\t']) {
+            Some(rest) if !lines.is_empty() => lines.last_mut().unwrap().push_str(rest),
+            _ => lines.push(raw.to_string()),
+        }
+    }
+    lines
+}
+
+/// Razbija liniju na ime, parametre i vrednost (':' unutar navodnika ne deli)
+fn parse_line(line: &str) -> Option<Property> {
+    let mut in_quotes = false;
+    let split = line.char_indices().find(|(_, c)| {
+        if *c == '"' {
+            in_quotes = !in_quotes;
+        }
+        *c == ':' && !in_quotes
+    })?;
+    let (head, value) = (&line[..split.0], &line[split.0 + 1..]);
+
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_ascii_uppercase();
+    let params = parts
+        .filter_map(|p| p.split_once('='))
+        .map(|(k, v)| (k.trim().to_ascii_uppercase(), v.trim_matches('"').to_string()))
+        .collect();
+
+    Some(Property { name, params, value: value.to_string() })
+}
+
+/// Svi VEVENT-ovi iz fajla, svaki kao lista svojih property-ja (bez ugnjezdenih VALARM i sl.)
+pub fn parse_vevents(text: &str) -> Vec<Vec<Property>> {
+    let mut events = Vec::new();
+    let mut current: Option<Vec<Property>> = None;
+    let mut nested = 0;
+
+    for line in unfold(text) {
+        let Some(prop) = parse_line(&line) else { continue };
+        match (prop.name.as_str(), prop.value.to_ascii_uppercase().as_str()) {
+            ("BEGIN", "VEVENT") => current = Some(Vec::new()),
+            ("END", "VEVENT") => {
+                if let Some(ev) = current.take() {
+                    events.push(ev);
+                }
+                nested = 0;
+            }
+            ("BEGIN", _) if current.is_some() => nested += 1,
+            ("END", _) if current.is_some() => nested -= 1,
+            _ => {
+                if let (Some(ev), 0) = (current.as_mut(), nested) {
+                    ev.push(prop);
+                }
+            }
+        }
+    }
+    events
+}
+
+/// Obrnuto od escape_text
+pub fn unescape_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out
+}
+
+/// DTSTART -> lokalno vreme (Europe/Belgrade) kako se cuva u bazi.
+/// UTC (Z) i TZID vremena se preracunavaju, "floating" vreme ostaje kakvo jeste.
+pub fn parse_datetime(prop: &Property) -> Result<NaiveDateTime, String> {
+    let value = prop.value.trim();
+    let naive = parse_ical_datetime(value).ok_or_else(|| format!("Neispravan datum: {}", value))?;
+    let local: Tz = TIMEZONE.parse().expect("validna vremenska zona");
+
+    let converted = if value.ends_with('Z') {
+        Some(Utc.from_utc_datetime(&naive).with_timezone(&local).naive_local())
+    } else if let Some(tzid) = prop.param("TZID") {
+        let tz: Tz = tzid.parse().map_err(|_| format!("Nepoznata vremenska zona: {}", tzid))?;
+        tz.from_local_datetime(&naive)
+            .earliest()
+            .map(|dt| dt.with_timezone(&local).naive_local())
+    } else {
+        Some(naive)
+    };
+
+    converted.ok_or_else(|| format!("Vreme {} ne postoji u zoni", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn escape_text_escapes_special_characters() {
+        let raw = "C:\\put; a, b\r\nnovi red";
+        let escaped = escape_text(raw);
+        assert_eq!(escaped, "C:\\\\put\\; a\\, b\\nnovi red");
+        assert_eq!(unescape_text(&escaped), "C:\\put; a, b\nnovi red");
+    }
+
+    #[test]
+    fn fold_line_keeps_multibyte_characters_whole() {
+        // 2, 3 i 4-bajtni karakteri preko granice od 75 okteta
+        let line = format!("SUMMARY:{}", "šđ€🎉".repeat(20));
+        let mut out = String::new();
+        fold_line(&line, &mut out);
+
+        let parts: Vec<&str> = out.strip_suffix("\r\n").unwrap().split("\r\n").collect();
+        assert!(parts.len() > 1);
+        for part in &parts {
+            assert!(part.len() <= 75, "{} okteta", part.len());
+        }
+        assert!(parts[1..].iter().all(|p| p.starts_with(' ')));
+        assert_eq!(unfold(&out)[0], line);
+    }
+
+    #[test]
+    fn fold_line_leaves_short_lines() {
+        let line = "a".repeat(75);
+        let mut out = String::new();
+        fold_line(&line, &mut out);
+        assert_eq!(out, format!("{}\r\n", line));
+    }
+
+    #[test]
+    fn parse_vevents_skips_nested_components() {
+        let text = "BEGIN:VCALENDAR\r\n\
+                    BEGIN:VEVENT\r\n\
+                    SUMMARY:Radionica\\, uvod\r\n\
+                    DESCRIPTION:prvi \r\n deo\r\n\
+                    BEGIN:VALARM\r\n\
+                    DESCRIPTION:podsetnik\r\n\
+                    END:VALARM\r\n\
+                    DTSTART;TZID=\"America/New_York\":20270115T120000\r\n\
+                    END:VEVENT\r\n\
+                    END:VCALENDAR\r\n";
+        let events = parse_vevents(text);
+        assert_eq!(events.len(), 1);
+
+        let props = &events[0];
+        let names: Vec<&str> = props.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["SUMMARY", "DESCRIPTION", "DTSTART"]);
+        assert_eq!(unescape_text(&props[0].value), "Radionica, uvod");
+        assert_eq!(props[1].value, "prvi deo");
+        assert_eq!(props[2].param("tzid"), Some("America/New_York"));
+        assert_eq!(parse_datetime(&props[2]).unwrap(), at("2027-01-15 18:00"));
+    }
+
+    #[test]
+    fn parse_datetime_converts_utc_to_local() {
+        let prop = |value: &str| Property { name: "DTSTART".to_string(), params: Vec::new(), value: value.to_string() };
+        // zimsko (CET) i letnje (CEST) vreme
+        assert_eq!(parse_datetime(&prop("20270115T100000Z")).unwrap(), at("2027-01-15 11:00"));
+        assert_eq!(parse_datetime(&prop("20270715T100000Z")).unwrap(), at("2027-07-15 12:00"));
+        assert_eq!(parse_datetime(&prop("20270715T100000")).unwrap(), at("2027-07-15 10:00"));
+        assert!(parse_datetime(&prop("sutra")).is_err());
+    }
+}
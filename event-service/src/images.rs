@@ -0,0 +1,270 @@
+// Slike dogadjaja - provera upload-a, thumbnail-ovi i prebacivanje starih base64 slika u skladiste
+
+use std::collections::BTreeMap;
+use std::io::Cursor;
+
+use axum::http::StatusCode;
+use base64::Engine;
+use image::{imageops::FilterType, DynamicImage, ImageFormat, ImageReader, Limits};
+use uuid::Uuid;
+
+use crate::storage::Storage;
+use crate::AppState;
+
+/// Dozvoljeni tipovi: content-type, format, ekstenzija
+const ALLOWED_TYPES: &[(&str, ImageFormat, &str)] = &[
+    ("image/jpeg", ImageFormat::Jpeg, "jpg"),
+    ("image/png", ImageFormat::Png, "png"),
+    ("image/webp", ImageFormat::WebP, "webp"),
+    ("image/gif", ImageFormat::Gif, "gif"),
+];
+
+/// Thumbnail-ovi po sirini; manje slike se ne uvecavaju
+pub const THUMBNAIL_SIZES: &[(&str, u32)] = &[("small", 320), ("medium", 640), ("large", 1280)];
+
+/// Najveca dozvoljena sirina/visina originala (zastita od "decompression bomb")
+const MAX_DIMENSION: u32 = 10_000;
+
+/// Sacuvana slika - URL originala, URL-ovi thumbnail-ova i svi kljucevi (za brisanje)
+pub struct StoredImage {
+    pub url: String,
+    pub thumbnails: BTreeMap<String, String>,
+    pub keys: Vec<String>,
+}
+
+/// Ekstenzija za dozvoljeni content-type
+pub fn extension_for(content_type: &str) -> Option<&'static str> {
+    ALLOWED_TYPES
+        .iter()
+        .find(|(ct, _, _)| *ct == content_type)
+        .map(|(_, _, ext)| *ext)
+}
+
+/// Content-type po ekstenziji kljuca (za serviranje sa diska)
+pub fn content_type_for(key: &str) -> &'static str {
+    let ext = key.rsplit('.').next().unwrap_or_default();
+    ALLOWED_TYPES
+        .iter()
+        .find(|(_, _, e)| *e == ext)
+        .map(|(ct, _, _)| *ct)
+        .unwrap_or("application/octet-stream")
+}
+
+/// Gotov thumbnail: velicina, bajtovi, content-type
+type Thumbnail = (&'static str, Vec<u8>, &'static str);
+
+/// Dekodira sliku (uz proveru da sadrzaj odgovara tipu) i pravi thumbnail-ove.
+/// CPU posao - poziva se iz spawn_blocking.
+fn render_thumbnails(bytes: &[u8], content_type: &str) -> Result<Vec<Thumbnail>, String> {
+    let (_, format, _) = ALLOWED_TYPES
+        .iter()
+        .find(|(ct, _, _)| *ct == content_type)
+        .ok_or("Nepodrzan tip slike")?;
+
+    if image::guess_format(bytes).ok() != Some(*format) {
+        return Err("Sadrzaj fajla ne odgovara tipu slike".to_string());
+    }
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DIMENSION);
+    limits.max_image_height = Some(MAX_DIMENSION);
+
+    let mut reader = ImageReader::with_format(Cursor::new(bytes), *format);
+    reader.limits(limits);
+    let img = reader.decode().map_err(|e| format!("Neispravna slika: {}", e))?;
+
+    // providne slike ostaju PNG, ostalo ide u JPEG (manje)
+    let (out_format, out_type) = if img.color().has_alpha() {
+        (ImageFormat::Png, "image/png")
+    } else {
+        (ImageFormat::Jpeg, "image/jpeg")
+    };
+
+    let mut thumbnails = Vec::with_capacity(THUMBNAIL_SIZES.len());
+    for (name, width) in THUMBNAIL_SIZES {
+        let resized = if img.width() > *width {
+            img.resize(*width, u32::MAX, FilterType::Lanczos3)
+        } else {
+            img.clone()
+        };
+        let resized = match out_format {
+            ImageFormat::Jpeg => DynamicImage::ImageRgb8(resized.to_rgb8()),
+            _ => resized,
+        };
+
+        let mut out = Cursor::new(Vec::new());
+        resized
+            .write_to(&mut out, out_format)
+            .map_err(|e| format!("Greska pri pravljenju thumbnail-a: {}", e))?;
+        thumbnails.push((*name, out.into_inner(), out_type));
+    }
+
+    Ok(thumbnails)
+}
+
+/// Proverava sliku, pravi thumbnail-ove i sve cuva pod "<prefix>/<uuid>/..."
+pub async fn store_image(
+    storage: &dyn Storage,
+    prefix: &str,
+    bytes: Vec<u8>,
+    content_type: &str,
+) -> Result<StoredImage, (StatusCode, String)> {
+    let ext = extension_for(content_type).ok_or((
+        StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        "Dozvoljene su samo JPEG, PNG, WebP i GIF slike".to_string(),
+    ))?;
+
+    let ct = content_type.to_string();
+    let (bytes, thumbnails) = tokio::task::spawn_blocking(move || {
+        render_thumbnails(&bytes, &ct).map(|thumbs| (bytes, thumbs))
+    })
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Greska: {}", e)))?
+    .map_err(|msg| (StatusCode::BAD_REQUEST, msg))?;
+
+    let base = format!("{}/{}", prefix, Uuid::new_v4());
+    let original_key = format!("{}/original.{}", base, ext);
+
+    let mut stored = StoredImage {
+        url: storage.url(&original_key),
+        thumbnails: BTreeMap::new(),
+        keys: vec![original_key.clone()],
+    };
+
+    let storage_err = |e: String| (StatusCode::INTERNAL_SERVER_ERROR, format!("Greska pri cuvanju slike: {}", e));
+
+    if let Err(e) = storage.put(&original_key, bytes, content_type).await {
+        return Err(storage_err(e));
+    }
+    for (name, data, thumb_type) in thumbnails {
+        let key = format!("{}/{}.{}", base, name, extension_for(thumb_type).unwrap_or("jpg"));
+        if let Err(e) = storage.put(&key, data, thumb_type).await {
+            // ne ostavljamo polovicno sacuvanu sliku
+            delete_images(storage, &stored.keys).await;
+            return Err(storage_err(e));
+        }
+        stored.thumbnails.insert(name.to_string(), storage.url(&key));
+        stored.keys.push(key);
+    }
+
+    Ok(stored)
+}
+
+/// Brise fajlove slike; greske samo loguje (ostaje siroce u skladistu)
+pub async fn delete_images(storage: &dyn Storage, keys: &[String]) {
+    for key in keys {
+        if let Err(e) = storage.delete(key).await {
+            tracing::warn!("Nisam uspeo da obrisem {}: {}", key, e);
+        }
+    }
+}
+
+/// Brise fajlove koje vise ne koristi nijedan dogadjaj ni sablon
+/// (klonovi i sabloni dele fajlove slike sa originalom). Zove se posle izmene reda.
+pub async fn release_images(state: &AppState, keys: &[String]) {
+    if keys.is_empty() {
+        return;
+    }
+
+    let unused = sqlx::query_scalar::<_, String>(
+        "SELECT k FROM UNNEST($1::text[]) AS k
+         WHERE NOT EXISTS (SELECT 1 FROM events WHERE k = ANY(image_keys))
+           AND NOT EXISTS (SELECT 1 FROM event_templates WHERE k = ANY(image_keys))",
+    )
+    .bind(keys)
+    .fetch_all(&state.db)
+    .await;
+
+    match unused {
+        Ok(list) => delete_images(state.storage.as_ref(), &list).await,
+        Err(e) => tracing::warn!("Ne mogu da proverim da li se slika koristi: {}", e),
+    }
+}
+
+/// Razbija "data:image/png;base64,...." na tip i bajtove
+fn parse_data_url(url: &str) -> Option<(String, Vec<u8>)> {
+    let rest = url.strip_prefix("data:")?;
+    let (meta, data) = rest.split_once(',')?;
+    let content_type = meta.strip_suffix(";base64")?;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(data.trim())
+        .ok()?;
+    Some((content_type.to_ascii_lowercase(), bytes))
+}
+
+/// Jednokratno pri startu: base64 slike iz starih redova prebacuje u skladiste
+/// (prvo serije, pa njihove termine sa istom slikom, pa preostale dogadjaje)
+pub async fn migrate_inline_images(state: AppState) {
+    let series = sqlx::query_as::<_, (Uuid, String)>(
+        "SELECT id, image_url FROM event_series WHERE image_url LIKE 'data:%'",
+    )
+    .fetch_all(&state.db)
+    .await
+    .unwrap_or_default();
+
+    for (id, data_url) in series {
+        let Some(stored) = convert(&state, &format!("series/{}", id), &data_url).await else {
+            continue;
+        };
+        let _ = sqlx::query("UPDATE event_series SET image_url = $2 WHERE id = $1")
+            .bind(id)
+            .bind(&stored.url)
+            .execute(&state.db)
+            .await;
+        let _ = sqlx::query(
+            "UPDATE events SET image_url = $2, image_thumbnails = $3
+             WHERE series_id = $1 AND image_url = $4",
+        )
+        .bind(id)
+        .bind(&stored.url)
+        .bind(sqlx::types::Json(&stored.thumbnails))
+        .bind(&data_url)
+        .execute(&state.db)
+        .await;
+    }
+
+    let events = sqlx::query_as::<_, (Uuid, String)>(
+        "SELECT id, image_url FROM events WHERE image_url LIKE 'data:%'",
+    )
+    .fetch_all(&state.db)
+    .await
+    .unwrap_or_default();
+
+    let total = events.len();
+    let mut converted = 0;
+    for (id, data_url) in events {
+        let Some(stored) = convert(&state, &format!("events/{}", id), &data_url).await else {
+            continue;
+        };
+        let result = sqlx::query(
+            "UPDATE events SET image_url = $2, image_thumbnails = $3, image_keys = $4 WHERE id = $1",
+        )
+        .bind(id)
+        .bind(&stored.url)
+        .bind(sqlx::types::Json(&stored.thumbnails))
+        .bind(&stored.keys)
+        .execute(&state.db)
+        .await;
+        if result.is_ok() {
+            converted += 1;
+        }
+    }
+
+    if total > 0 {
+        tracing::info!("Base64 slike prebacene u skladiste: {}/{}", converted, total);
+    }
+}
+
+async fn convert(state: &AppState, prefix: &str, data_url: &str) -> Option<StoredImage> {
+    let Some((content_type, bytes)) = parse_data_url(data_url) else {
+        tracing::warn!("{}: neispravan data URL, preskacem", prefix);
+        return None;
+    };
+    match store_image(state.storage.as_ref(), prefix, bytes, &content_type).await {
+        Ok(stored) => Some(stored),
+        Err((_, msg)) => {
+            tracing::warn!("{}: slika nije prebacena: {}", prefix, msg);
+            None
+        }
+    }
+}
@@ -0,0 +1,291 @@
+// Uvoz dogadjaja iz CSV i .ics fajlova - pretvara redove u CreateEventRequest
+
+use chrono::NaiveDateTime;
+
+use crate::ical::{parse_datetime, parse_vevents, unescape_text, Property};
+use crate::models::CreateEventRequest;
+
+/// Najvise redova po uvozu
+pub const MAX_IMPORT_ROWS: usize = 1000;
+const DEFAULT_CATEGORY: &str = "Ostalo";
+
+/// Red iz fajla (broj reda za poruke o greskama) i rezultat parsiranja
+pub type ParsedRow = (usize, Result<CreateEventRequest, String>);
+
+/// CSV kolone i njihovi alijasi (zaglavlje je obavezno, velika/mala slova nisu bitna)
+const CSV_COLUMNS: &[(&str, &[&str])] = &[
+    ("title", &["naziv"]),
+    ("description", &["opis"]),
+    ("location", &["lokacija"]),
+    ("date_time", &["datum", "datetime"]),
+    ("capacity", &["kapacitet"]),
+    ("category", &["kategorija"]),
+    ("image_url", &["slika"]),
+    ("latitude", &["lat"]),
+    ("longitude", &["lng", "lon"]),
+    ("tags", &["tagovi"]),
+];
+const CSV_REQUIRED: &[&str] = &["title", "location", "date_time", "capacity"];
+
+/// Formati datuma koji se prihvataju u CSV-u
+const CSV_DATE_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %H:%M",
+    "%d.%m.%Y %H:%M",
+    "%d.%m.%Y. %H:%M",
+];
+
+fn parse_csv_date(s: &str) -> Option<NaiveDateTime> {
+    CSV_DATE_FORMATS
+        .iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(s, f).ok())
+}
+
+fn canonical_column(header: &str) -> Option<&'static str> {
+    let h = header.trim().to_lowercase();
+    CSV_COLUMNS
+        .iter()
+        .find(|(name, aliases)| *name == h || aliases.contains(&h.as_str()))
+        .map(|(name, _)| *name)
+}
+
+fn parse_optional<T: std::str::FromStr>(value: Option<&str>, column: &str) -> Result<Option<T>, String> {
+    match value {
+        None => Ok(None),
+        Some(v) => v
+            .replace(',', ".")
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("Neispravna vrednost za {}: {}", column, v)),
+    }
+}
+
+/// CSV -> redovi. Greska za ceo fajl (npr. nedostaje obavezna kolona) vraca Err.
+/// Separator je ',' ili ';' (Excel sa nasim podesavanjima pravi ';').
+pub fn parse_csv(text: &str) -> Result<Vec<ParsedRow>, String> {
+    let header_line = text.lines().next().unwrap_or_default();
+    let delimiter = if header_line.matches(';').count() > header_line.matches(',').count() { b';' } else { b',' };
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(text.trim_start_matches('\u{feff}').as_bytes());
+
+    let headers = reader.headers().map_err(|e| format!("Neispravno CSV zaglavlje: {}", e))?.clone();
+    let mut columns = Vec::with_capacity(headers.len());
+    for h in headers.iter() {
+        match canonical_column(h) {
+            Some(c) => columns.push(c),
+            None => return Err(format!("Nepoznata kolona: {}", h)),
+        }
+    }
+    for required in CSV_REQUIRED {
+        if !columns.contains(required) {
+            return Err(format!("Nedostaje obavezna kolona: {}", required));
+        }
+    }
+
+    let mut rows = Vec::new();
+    for (i, record) in reader.records().enumerate() {
+        // red 1 je zaglavlje
+        let row = i + 2;
+        if rows.len() >= MAX_IMPORT_ROWS {
+            return Err(format!("Najvise {} redova po uvozu", MAX_IMPORT_ROWS));
+        }
+        let record = match record {
+            Ok(r) => r,
+            Err(e) => {
+                rows.push((row, Err(format!("Neispravan CSV red: {}", e))));
+                continue;
+            }
+        };
+        if record.iter().all(|v| v.is_empty()) {
+            continue;
+        }
+
+        let get = |name: &str| {
+            columns
+                .iter()
+                .position(|c| *c == name)
+                .and_then(|i| record.get(i))
+                .filter(|v| !v.is_empty())
+        };
+        rows.push((row, csv_row(&get)));
+    }
+    Ok(rows)
+}
+
+fn csv_row<'a>(get: &dyn Fn(&str) -> Option<&'a str>) -> Result<CreateEventRequest, String> {
+    let date = get("date_time").ok_or("Nedostaje date_time")?;
+    let capacity = get("capacity").ok_or("Nedostaje capacity")?;
+
+    Ok(CreateEventRequest {
+        title: get("title").ok_or("Nedostaje title")?.to_string(),
+        description: get("description").unwrap_or_default().to_string(),
+        location: get("location").ok_or("Nedostaje location")?.to_string(),
+        date_time: parse_csv_date(date).ok_or_else(|| format!("Neispravan datum: {}", date))?,
+        capacity: capacity.parse().map_err(|_| format!("Neispravan kapacitet: {}", capacity))?,
+        category: get("category").unwrap_or(DEFAULT_CATEGORY).to_string(),
+        image_url: get("image_url").map(str::to_string),
+        latitude: parse_optional(get("latitude"), "latitude")?,
+        longitude: parse_optional(get("longitude"), "longitude")?,
+        status: None,
+        template_id: None,
+        visibility: None,
+        access_code: None,
+        // tagovi u jednoj celiji: "rust|web" ili "rust; web"
+        tags: get("tags")
+            .map(|t| t.split(['|', ';', ',']).map(|t| t.trim().to_string()).collect())
+            .unwrap_or_default(),
+    })
+}
+
+/// .ics -> redovi (broj reda = redni broj VEVENT-a). iCalendar nema kapacitet, pa se koristi zadati.
+pub fn parse_ics(text: &str, capacity: i32) -> Result<Vec<ParsedRow>, String> {
+    let events = parse_vevents(text);
+    if events.is_empty() {
+        return Err("Fajl ne sadrzi nijedan VEVENT".to_string());
+    }
+    if events.len() > MAX_IMPORT_ROWS {
+        return Err(format!("Najvise {} dogadjaja po uvozu", MAX_IMPORT_ROWS));
+    }
+
+    Ok(events
+        .iter()
+        .enumerate()
+        .map(|(i, props)| (i + 1, ics_event(props, capacity)))
+        .collect())
+}
+
+fn ics_event(props: &[Property], capacity: i32) -> Result<CreateEventRequest, String> {
+    let get = |name: &str| props.iter().find(|p| p.name == name);
+    let text = |name: &str| get(name).map(|p| unescape_text(&p.value)).filter(|v| !v.trim().is_empty());
+
+    if get("RRULE").is_some() {
+        return Err("Ponavljajuci dogadjaji (RRULE) se ne uvoze - napravite seriju preko /series".to_string());
+    }
+
+    let dtstart = get("DTSTART").ok_or("Nedostaje DTSTART")?;
+    let (latitude, longitude) = match get("GEO").and_then(|p| p.value.split_once(';')) {
+        Some((lat, lng)) => (
+            parse_optional(Some(lat), "GEO")?,
+            parse_optional(Some(lng), "GEO")?,
+        ),
+        None => (None, None),
+    };
+    // prva iz CATEGORIES je kategorija, ostale postaju tagovi
+    let mut categories = text("CATEGORIES")
+        .map(|c| c.split(',').map(|c| c.trim().to_string()).filter(|c| !c.is_empty()).collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter();
+    let category = categories.next().unwrap_or_else(|| DEFAULT_CATEGORY.to_string());
+
+    Ok(CreateEventRequest {
+        title: text("SUMMARY").ok_or("Nedostaje SUMMARY")?,
+        description: text("DESCRIPTION").unwrap_or_default(),
+        location: text("LOCATION").ok_or("Nedostaje LOCATION")?,
+        date_time: parse_datetime(dtstart)?,
+        capacity,
+        category,
+        image_url: None,
+        latitude,
+        longitude,
+        status: None,
+        template_id: None,
+        visibility: None,
+        access_code: None,
+        tags: categories.collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok(row: &ParsedRow) -> &CreateEventRequest {
+        row.1.as_ref().unwrap()
+    }
+
+    #[test]
+    fn csv_with_aliases_and_semicolons() {
+        let text = "\u{feff}Naziv;Lokacija;Datum;Kapacitet;Lat;Lng\n\
+                    Rust meetup;Novi Sad;15.03.2027. 18:00;40;45,2671;19,8335\n\
+                    ;;;;;\n\
+                    Radionica;Beograd;2027-04-01 10:00;25;;\n";
+        let rows = parse_csv(text).unwrap();
+        assert_eq!(rows.len(), 2);
+
+        let (line, _) = &rows[0];
+        assert_eq!(*line, 2);
+        let first = ok(&rows[0]);
+        assert_eq!(first.title, "Rust meetup");
+        assert_eq!(first.date_time, NaiveDateTime::parse_from_str("2027-03-15 18:00", "%Y-%m-%d %H:%M").unwrap());
+        assert_eq!(first.capacity, 40);
+        assert_eq!(first.category, DEFAULT_CATEGORY);
+        assert_eq!((first.latitude, first.longitude), (Some(45.2671), Some(19.8335)));
+
+        // prazan red se preskace, ali broj reda ostaje kao u fajlu
+        assert_eq!(rows[1].0, 4);
+        assert_eq!(ok(&rows[1]).latitude, None);
+    }
+
+    #[test]
+    fn csv_reports_bad_rows_and_bad_headers() {
+        let rows = parse_csv("title,location,date_time,capacity\nA,NS,sutra,10\nB,NS,2027-01-01 10:00,mnogo\n").unwrap();
+        assert!(rows[0].1.as_ref().unwrap_err().contains("datum"));
+        assert!(rows[1].1.as_ref().unwrap_err().contains("kapacitet"));
+
+        assert!(parse_csv("title,location,date_time\n").unwrap_err().contains("capacity"));
+        assert!(parse_csv("title,location,date_time,capacity,boja\n").unwrap_err().contains("boja"));
+    }
+
+    #[test]
+    fn ics_events_use_given_capacity_and_first_category() {
+        let text = "BEGIN:VCALENDAR\r\n\
+                    BEGIN:VEVENT\r\n\
+                    SUMMARY:Koncert\\, uvece\r\n\
+                    LOCATION:Dom omladine\r\n\
+                    DTSTART:20270501T190000\r\n\
+                    GEO:44.8125;20.4612\r\n\
+                    CATEGORIES:Muzika,Jazz\r\n\
+                    END:VEVENT\r\n\
+                    BEGIN:VEVENT\r\n\
+                    SUMMARY:Nedeljno\r\n\
+                    LOCATION:Park\r\n\
+                    DTSTART:20270502T100000\r\n\
+                    RRULE:FREQ=WEEKLY\r\n\
+                    END:VEVENT\r\n\
+                    BEGIN:VEVENT\r\n\
+                    SUMMARY:Bez mesta\r\n\
+                    DTSTART:20270503T100000\r\n\
+                    END:VEVENT\r\n\
+                    END:VCALENDAR\r\n";
+        let rows = parse_ics(text, 75).unwrap();
+        assert_eq!(rows.len(), 3);
+
+        let concert = ok(&rows[0]);
+        assert_eq!(concert.title, "Koncert, uvece");
+        assert_eq!(concert.capacity, 75);
+        assert_eq!(concert.category, "Muzika");
+        assert_eq!((concert.latitude, concert.longitude), (Some(44.8125), Some(20.4612)));
+
+        assert!(rows[1].1.as_ref().unwrap_err().contains("RRULE"));
+        assert!(rows[2].1.as_ref().unwrap_err().contains("LOCATION"));
+        assert!(parse_ics("BEGIN:VCALENDAR\r\nEND:VCALENDAR\r\n", 75).is_err());
+    }
+
+    #[test]
+    fn tags_from_csv_cell_and_extra_ics_categories() {
+        let rows = parse_csv("title,location,date_time,capacity,tagovi\nA,NS,2027-01-01 10:00,10,rust| web\n").unwrap();
+        assert_eq!(ok(&rows[0]).tags, ["rust", "web"]);
+
+        let text = "BEGIN:VEVENT\r\nSUMMARY:A\r\nLOCATION:NS\r\nDTSTART:20270501T190000\r\n\
+                    CATEGORIES:Muzika,Jazz,Uzivo\r\nEND:VEVENT\r\n";
+        let rows = parse_ics(text, 10).unwrap();
+        assert_eq!(ok(&rows[0]).category, "Muzika");
+        assert_eq!(ok(&rows[0]).tags, ["Jazz", "Uzivo"]);
+    }
+}
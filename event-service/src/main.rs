@@ -1,18 +1,55 @@
 
 
 mod db;
+mod facets;
 mod handlers;
+mod i18n;
+mod ical;
+mod images;
+mod import;
+mod markdown;
 mod models;
+mod recommendations;
+mod revisions;
+mod rrule;
+mod series;
+mod storage;
+mod syndication;
+mod taxonomy;
+mod trash;
 
-use axum::{routing::{get, post, put, delete}, Router};
+use std::sync::Arc;
+
+use axum::{extract::DefaultBodyLimit, routing::{get, post, put, delete}, Router};
 use sqlx::PgPool;
 
+use crate::storage::Storage;
+
 #[derive(Clone)]
 pub struct AppState {
     pub db: PgPool,
     pub jwt_secret: String,
     pub registration_service_url: String,
+    /// za trazenje korisnika po email-u (dodavanje osoblja)
+    pub auth_service_url: String,
+    /// servisni token za /internal/* rute auth servisa
+    pub internal_token: String,
     pub http_client: reqwest::Client,
+    /// koliko dana unapred se prave termini serija
+    pub series_horizon_days: i64,
+    /// koliko dana obrisani dogadjaji stoje u kanti
+    pub trash_retention_days: i64,
+    /// gde se cuvaju slike (lokalni disk ili S3)
+    pub storage: Arc<dyn Storage>,
+    pub max_image_bytes: usize,
+    /// adresa frontenda - za linkove ka dogadjajima (iCalendar URL)
+    pub public_app_url: String,
+    /// javna adresa API-ja (gateway) - linkovi u feed-ovima i stranice dogadjaja
+    pub public_api_url: String,
+    /// koliko pitanja/komentara korisnik sme da posalje za sat vremena
+    pub qa_posts_per_hour: i64,
+    /// jezik osnovnih polja dogadjaja; ostali jezici su u event_translations
+    pub default_locale: String,
 }
 
 #[tokio::main]
@@ -29,20 +66,151 @@ async fn main() {
     let registration_service_url = std::env::var("REGISTRATION_SERVICE_URL")
         .unwrap_or_else(|_| "http://localhost:3004".to_string());
 
+    let auth_service_url = std::env::var("AUTH_SERVICE_URL")
+        .unwrap_or_else(|_| "http://localhost:3001".to_string());
+
+    let internal_token = std::env::var("INTERNAL_API_TOKEN")
+        .expect("INTERNAL_API_TOKEN mora biti postavljen u .env");
+
+    let series_horizon_days = std::env::var("SERIES_HORIZON_DAYS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(90);
+
+    let trash_retention_days = std::env::var("EVENT_TRASH_RETENTION_DAYS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(30);
+
+    let max_image_bytes = std::env::var("MAX_IMAGE_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(4 * 1024 * 1024);
+
+    let public_app_url = std::env::var("PUBLIC_APP_URL")
+        .unwrap_or_else(|_| "http://localhost:8080".to_string())
+        .trim_end_matches('/')
+        .to_string();
+
+    let public_api_url = std::env::var("PUBLIC_API_URL")
+        .unwrap_or_else(|_| "http://localhost:3000/api".to_string())
+        .trim_end_matches('/')
+        .to_string();
+
+    let qa_posts_per_hour = std::env::var("QA_POSTS_PER_HOUR")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(20);
+
+    let default_locale = std::env::var("DEFAULT_LOCALE")
+        .ok()
+        .and_then(|v| i18n::normalize_locale(&v))
+        .unwrap_or_else(|| "sr".to_string());
+
     let pool = db::create_pool(&database_url).await;
     let http_client = reqwest::Client::new();
+    let storage = storage::from_env(http_client.clone());
 
     let state = AppState {
         db: pool,
         jwt_secret,
         registration_service_url,
+        auth_service_url,
+        internal_token,
         http_client,
+        series_horizon_days,
+        trash_retention_days,
+        storage,
+        max_image_bytes,
+        public_app_url,
+        public_api_url,
+        qa_posts_per_hour,
+        default_locale,
     };
 
+    // termini serija se prave unapred do horizonta, pa ga pomeramo u pozadini
+    tokio::spawn(series::run_materializer(state.clone()));
+    // kanta se prazni jednom na sat
+    tokio::spawn(trash::run_purge(state.clone()));
+    // slicnost i popularnost za preporuke se preracunavaju nocu
+    tokio::spawn(recommendations::run_precompute(state.clone()));
+    // stari redovi su imali sliku kao base64 u image_url
+    tokio::spawn(images::migrate_inline_images(state.clone()));
+    // opisi od pre Markdown-a nemaju HTML
+    tokio::spawn(markdown::render_missing(state.clone()));
+
+    // multipart ima malo overhead-a preko same slike
+    let image_body_limit = DefaultBodyLimit::max(max_image_bytes + 64 * 1024);
+
     let app = Router::new()
         .route("/health", get(handlers::health_check))
         .route("/events", get(handlers::list_events).post(handlers::create_event))
+        .route("/events/feed.ics", get(handlers::events_feed))
+        .route("/events/feed.atom", get(handlers::events_atom))
+        .route("/events/feed.rss", get(handlers::events_rss))
+        .route("/events/import", post(handlers::import_events))
+        .route("/events/trash", get(handlers::list_trash))
         .route("/events/:id", get(handlers::get_event).put(handlers::update_event).delete(handlers::delete_event))
+        .route("/events/link/:slug", get(handlers::get_event_by_slug))
+        .route("/events/bookmarked", get(handlers::list_bookmarks))
+        .route("/events/recommended", get(handlers::recommended_events))
+        .route("/recommendations/refresh", post(handlers::refresh_recommendations))
+        .route("/events/:id/jsonld", get(handlers::event_json_ld))
+        .route("/events/:id/page", get(handlers::event_page))
+        .route("/events/:id/clone", post(handlers::clone_event))
+        .route("/events/:id/restore", post(handlers::restore_event))
+        .route("/events/:id/revisions", get(handlers::list_revisions))
+        .route("/events/:id/revisions/:version/restore", post(handlers::restore_revision))
+        .route("/events/:id/access", get(handlers::get_event_access))
+        .route("/events/:id/access/slug", post(handlers::rotate_event_slug))
+        .route("/events/:id/invitations", post(handlers::invite_to_event))
+        .route("/events/:id/invitations/:email", delete(handlers::revoke_invitation))
+        .route("/events/:id/staff", get(handlers::list_staff).post(handlers::add_staff))
+        .route("/events/:id/staff/:user_id", delete(handlers::remove_staff))
+        .route("/events/:id/image", post(handlers::upload_event_image).layer(image_body_limit).delete(handlers::delete_event_image))
+        .route("/uploads/*key", get(handlers::serve_upload))
+        .route("/events/:id/sessions", get(handlers::list_sessions).post(handlers::create_session))
+        .route("/events/:id/sessions/:session_id", put(handlers::update_session).delete(handlers::delete_session))
+        .route("/events/:id/sessions/:session_id/attend", post(handlers::attend_session).delete(handlers::leave_session))
+        .route("/events/:id/speakers", get(handlers::list_speakers).post(handlers::create_speaker))
+        .route("/events/:id/speakers/:speaker_id", put(handlers::update_speaker).delete(handlers::delete_speaker))
+        .route("/events/:id/agenda", get(handlers::get_agenda))
+        .route("/events/:id/ticket-types", get(handlers::list_ticket_types).post(handlers::create_ticket_type))
+        .route("/events/:id/ticket-types/:type_id", get(handlers::get_ticket_type).put(handlers::update_ticket_type).delete(handlers::delete_ticket_type))
+        .route("/events/:id/reviews", get(handlers::list_reviews).post(handlers::create_review))
+        .route("/events/:id/reviews/:review_id", put(handlers::update_review).delete(handlers::delete_review))
+        .route("/events/:id/reviews/:review_id/reply", put(handlers::reply_to_review).delete(handlers::delete_review_reply))
+        .route("/events/:id/reviews/:review_id/report", post(handlers::report_review))
+        .route("/events/:id/reviews/:review_id/moderation", put(handlers::moderate_review))
+        .route("/reviews/reported", get(handlers::list_reported_reviews))
+        .route("/events/:id/questions", get(handlers::list_questions).post(handlers::create_question))
+        .route("/events/:id/questions/:question_id", put(handlers::update_question).delete(handlers::delete_question))
+        .route("/events/:id/questions/:question_id/vote", post(handlers::upvote_question).delete(handlers::remove_question_vote))
+        .route("/events/:id/questions/:question_id/moderation", put(handlers::moderate_question))
+        .route("/events/:id/translations", get(handlers::list_translations))
+        .route("/events/:id/translations/:locale", put(handlers::upsert_translation).delete(handlers::delete_translation))
+        .route("/events/:id/featured", put(handlers::set_featured))
+        .route("/events/:id/eligibility", get(handlers::get_eligibility).put(handlers::update_eligibility).delete(handlers::delete_eligibility))
+        .route("/events/:id/bookmark", post(handlers::bookmark_event).delete(handlers::remove_bookmark))
+        .route("/follows", get(handlers::list_follows))
+        .route("/organizers/:id/follow", post(handlers::follow_organizer).delete(handlers::unfollow_organizer))
+        .route("/categories/:slug/follow", post(handlers::follow_category).delete(handlers::unfollow_category))
+        .route("/internal/events/calendar", post(handlers::internal_calendar))
+        .route("/internal/events/:id/access/:user_id", get(handlers::internal_event_access))
+        .route("/internal/events/:id/admission", post(handlers::internal_admission))
+        .route("/internal/events/:id/ticket-types", get(handlers::internal_ticket_types))
+        .route("/internal/events/:id/eligibility", get(handlers::internal_eligibility))
+        .route("/internal/events/summary", get(handlers::internal_event_summary))
+        .route("/categories", get(handlers::list_categories).post(handlers::create_category))
+        .route("/categories/:slug", put(handlers::update_category).delete(handlers::delete_category))
+        .route("/collections", get(handlers::list_collections).post(handlers::create_collection))
+        .route("/collections/:slug", get(handlers::get_collection).put(handlers::update_collection).delete(handlers::delete_collection))
+        .route("/templates", get(handlers::list_templates).post(handlers::create_template))
+        .route("/templates/:id", get(handlers::get_template).put(handlers::update_template).delete(handlers::delete_template))
+        .route("/templates/:id/prefill", get(handlers::template_prefill))
+        .route("/series", post(handlers::create_series))
+        .route("/series/:id", get(handlers::get_series).delete(handlers::delete_series))
+        .route("/series/:id/occurrences/:event_id", put(handlers::update_occurrence))
         .with_state(state);
 
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3003")
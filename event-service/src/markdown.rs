@@ -0,0 +1,132 @@
+// Opis dogadjaja u Markdown-u - pretvaranje u bezbedan HTML (isti za sve klijente)
+
+use std::collections::HashSet;
+
+use ammonia::Builder;
+use pulldown_cmark::{html, Event, Options, Parser, TagEnd};
+use uuid::Uuid;
+
+use crate::AppState;
+
+/// Tagovi koji prezive ciscenje; sve ostalo (script, style, iframe, img, forme...) se izbacuje
+const ALLOWED_TAGS: &[&str] = &[
+    "p", "br", "hr", "strong", "em", "del", "code", "pre", "blockquote",
+    "h1", "h2", "h3", "h4", "ul", "ol", "li", "a", "table", "thead", "tbody", "tr", "th", "td",
+];
+
+/// Markdown -> HTML bez opasnih tagova i atributa; linkovi samo http(s) i mailto,
+/// otvaraju se sa rel="noopener noreferrer nofollow"
+pub fn render(source: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+
+    let mut raw = String::with_capacity(source.len() * 3 / 2);
+    html::push_html(&mut raw, Parser::new_ext(source, options));
+
+    Builder::empty()
+        .tags(ALLOWED_TAGS.iter().copied().collect())
+        .tag_attributes([("a", ["href", "title"].into_iter().collect())].into_iter().collect())
+        .url_schemes(HashSet::from(["http", "https", "mailto"]))
+        .link_rel(Some("noopener noreferrer nofollow"))
+        .clean_content_tags(HashSet::from(["script", "style"]))
+        .clean(&raw)
+        .to_string()
+}
+
+/// Markdown bez formatiranja - za feed-ove, meta tagove i kalendar, gde HTML ne ide
+pub fn plain_text(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    for event in Parser::new_ext(source, Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH) {
+        match event {
+            Event::Text(text) | Event::Code(text) => out.push_str(&text),
+            Event::SoftBreak => out.push(' '),
+            Event::HardBreak
+            | Event::End(TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::Item | TagEnd::TableRow) => out.push('\n'),
+            _ => {}
+        }
+    }
+    out.trim_end().to_string()
+}
+
+/// Pri pokretanju renderuje opise koji jos nemaju HTML (redovi od pre Markdown-a)
+pub async fn render_missing(state: AppState) {
+    let events = sqlx::query_as::<_, (Uuid, String)>(
+        "SELECT id, description FROM events WHERE description <> '' AND description_html = ''",
+    )
+    .fetch_all(&state.db)
+    .await
+    .unwrap_or_default();
+
+    for (id, description) in &events {
+        let _ = sqlx::query("UPDATE events SET description_html = $2 WHERE id = $1")
+            .bind(id)
+            .bind(render(description))
+            .execute(&state.db)
+            .await;
+    }
+
+    let translations = sqlx::query_as::<_, (Uuid, String, String)>(
+        "SELECT event_id, locale, description FROM event_translations
+         WHERE description IS NOT NULL AND description_html IS NULL",
+    )
+    .fetch_all(&state.db)
+    .await
+    .unwrap_or_default();
+
+    for (event_id, locale, description) in &translations {
+        let _ = sqlx::query("UPDATE event_translations SET description_html = $3 WHERE event_id = $1 AND locale = $2")
+            .bind(event_id)
+            .bind(locale)
+            .bind(render(description))
+            .execute(&state.db)
+            .await;
+    }
+
+    if !events.is_empty() || !translations.is_empty() {
+        tracing::info!("Opis u HTML-u napravljen za {} dogadjaja i {} prevoda", events.len(), translations.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_keeps_formatting() {
+        let html = render("# Naslov\n\n**jako** i ~~precrtano~~\n\n- stavka");
+        assert!(html.contains("<h1>Naslov</h1>"));
+        assert!(html.contains("<strong>jako</strong>"));
+        assert!(html.contains("<del>precrtano</del>"));
+        assert!(html.contains("<li>stavka</li>"));
+    }
+
+    #[test]
+    fn render_strips_scripts_and_event_handlers() {
+        let html = render("pre\n\n<script>alert(1)</script>\n\n<img src=x onerror=\"alert(1)\">\n\n<p onclick=\"alert(1)\">posle</p>");
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("alert"));
+        assert!(!html.contains("<img"));
+        assert!(!html.contains("onerror"));
+        assert!(!html.contains("onclick"));
+        assert!(html.contains("pre"));
+        assert!(html.contains("posle"));
+    }
+
+    #[test]
+    fn render_allows_only_safe_links() {
+        let html = render("[klik](javascript:alert(1)) [data](data:text/html,x) <a href=\"JaVaScRiPt:alert(1)\">a</a>");
+        assert!(!html.to_lowercase().contains("javascript:"));
+        assert!(!html.contains("data:"));
+
+        let html = render("[sajt](https://example.com) [mejl](mailto:info@example.com)");
+        assert!(html.contains("href=\"https://example.com\""));
+        assert!(html.contains("href=\"mailto:info@example.com\""));
+        assert!(html.contains("rel=\"noopener noreferrer nofollow\""));
+    }
+
+    #[test]
+    fn plain_text_drops_markup() {
+        assert_eq!(plain_text("# Naslov\n\n**jako** i `kod`\n\n- a\n- b"), "Naslov\njako i kod\na\nb");
+    }
+}
@@ -3,7 +3,11 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime};
+use sqlx::types::Json;
+use std::collections::BTreeMap;
+
+use shared::eligibility::EligibilityRules;
 
 /// event in db
 #[derive(Debug, FromRow, Serialize)]
@@ -11,14 +15,59 @@ pub struct Event {
     pub id: Uuid,
     pub organizer_id: Uuid,
     pub title: String,
+    /// Markdown izvor
     pub description: String,
+    /// ociscen HTML iz opisa - klijenti ga prikazuju direktno
+    pub description_html: String,
     pub location: String,
     pub date_time: NaiveDateTime,
     pub capacity: i32,
     pub category: String,
     pub image_url: Option<String>,
+    /// thumbnail-ovi po velicini (small/medium/large) -> URL
+    pub image_thumbnails: Option<Json<BTreeMap<String, String>>>,
+    /// kljucevi fajlova u skladistu (za brisanje kad se slika zameni)
+    #[serde(skip_serializing)]
+    pub image_keys: Vec<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub series_id: Option<Uuid>,
+    /// originalni termin iz pravila serije (RECURRENCE-ID)
+    pub occurrence_date: Option<NaiveDateTime>,
+    /// termin serije koji je rucno izmenjen - izmene serije ga ne diraju
+    pub is_exception: bool,
+    /// draft (vidi ga samo organizator) ili published
+    pub status: String,
+    /// public, unlisted (samo preko linka sa slug-om) ili private (pozivnica ili pristupni kod)
+    pub visibility: String,
+    /// tajni deo linka /events/link/:slug
+    pub slug: String,
+    #[serde(skip_serializing)]
+    pub access_code: Option<String>,
+    /// raste sa svakom izmenom; salje se kao ETag, PUT trazi If-Match
+    pub version: i32,
+    /// u kanti od (None = nije obrisan)
+    pub deleted_at: Option<NaiveDateTime>,
+    pub deleted_by: Option<Uuid>,
+    /// nazivi tagova (iz event_tags, puni se posebno - attach_tags)
+    #[sqlx(skip)]
+    pub tags: Vec<String>,
+    /// prosek vidljivih ocena (None dok niko nije ocenio)
+    pub rating_avg: Option<f64>,
+    pub rating_count: i32,
+    /// izdvojen na pocetnoj (postavlja Admin); veci prioritet ide ranije
+    pub featured: bool,
+    pub featured_priority: i32,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    /// udaljenost u km - popunjava se samo kod pretrage sa `near`
+    #[sqlx(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance_km: Option<f64>,
+    /// jezik na kom su naziv, opis i lokacija (get/list; puni se posebno - apply_translations)
+    #[sqlx(skip)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
 }
 
 /// create req
@@ -31,10 +80,21 @@ pub struct CreateEventRequest {
     pub capacity: i32,
     pub category: String,
     pub image_url: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// draft ili published (podrazumevano)
+    pub status: Option<String>,
+    /// sablon iz kog je popunjen (slika tada zadrzava thumbnail-ove)
+    pub template_id: Option<Uuid>,
+    /// public (podrazumevano), unlisted ili private
+    pub visibility: Option<String>,
+    pub access_code: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 /// update req - sva polja opciona
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct UpdateEventRequest {
     pub title: Option<String>,
     pub description: Option<String>,
@@ -43,11 +103,995 @@ pub struct UpdateEventRequest {
     pub capacity: Option<i32>,
     pub category: Option<String>,
     pub image_url: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub status: Option<String>,
+    pub visibility: Option<String>,
+    /// prazan string brise pristupni kod
+    pub access_code: Option<String>,
+    /// obavezno kad se kapacitet smanjuje ispod broja potvrdjenih prijava
+    pub capacity_strategy: Option<CapacityStrategy>,
+    /// napomena za prijave koje se premestaju/otkazuju (inace podrazumevana)
+    pub capacity_notice: Option<String>,
+    /// zamenjuje sve tagove ([] brise)
+    pub tags: Option<Vec<String>>,
+}
+
+/// Sta sa prijavama kad novi kapacitet ne moze da ih primi
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CapacityStrategy {
+    /// odbija izmenu (isto kao da nije poslato)
+    Reject,
+    /// poslednji prijavljeni idu na listu cekanja
+    Waitlist,
+    /// poslednji prijavljeni se otkazuju uz napomenu
+    Cancel,
 }
 
 /// Query params za search
+/// near=lat,lng&radius_km=10 - samo dogadjaji u krugu oko tacke
+/// sort=date|distance
 #[derive(Debug, Deserialize)]
 pub struct EventQuery {
+    /// naziv ili slug kategorije
     pub category: Option<String>,
+    /// tag=rust,web - dogadjaji sa bar jednim od tagova
+    pub tag: Option<String>,
     pub search: Option<String>,
+    pub near: Option<String>,
+    pub radius_km: Option<f64>,
+    pub sort: Option<String>,
+    /// grad = poslednji deo lokacije posle zareza ("Spens, Novi Sad" -> "Novi Sad")
+    pub city: Option<String>,
+    /// today, tomorrow, weekend, week, month, later ili past
+    pub when: Option<String>,
+    /// free ili paid (po najjeftinijoj javnoj karti)
+    pub price: Option<String>,
+    /// available=true - samo dogadjaji sa slobodnim mestima
+    #[serde(default)]
+    pub available: bool,
+    /// facets=true - uz listu i brojevi po kategoriji, tagu, gradu, datumu, ceni i dostupnosti
+    #[serde(default)]
+    pub facets: bool,
+    /// drafts=true - i nacrti koje korisnik sme da vidi (trazi JWT)
+    #[serde(default)]
+    pub drafts: bool,
+    /// following=true - samo predstojeci od pracenih organizatora i kategorija (trazi JWT)
+    #[serde(default)]
+    pub following: bool,
+    /// featured=true - samo izdvojeni dogadjaji
+    #[serde(default)]
+    pub featured: bool,
+    /// jezik prikaza (inace Accept-Language); pretraga uvek gleda sve prevode
+    pub lang: Option<String>,
+}
+
+/// GET /events/:id?lang=en
+#[derive(Debug, Deserialize)]
+pub struct LangQuery {
+    pub lang: Option<String>,
+}
+
+/// Kloniranje dogadjaja na novi datum; kopija je uvek draft
+#[derive(Debug, Deserialize)]
+pub struct CloneEventRequest {
+    pub date_time: NaiveDateTime,
+    /// novi naziv (inace isti kao original)
+    pub title: Option<String>,
+    /// kopiraj i sesije sa predavacima (pomerene za istu razliku u vremenu)
+    #[serde(default)]
+    pub include_sessions: bool,
+    /// kopiraj i tipove karata (periodi prodaje se pomeraju)
+    #[serde(default)]
+    pub include_ticket_types: bool,
+}
+
+/// sacuvan sablon organizatora - sva polja dogadjaja su opciona
+#[derive(Debug, FromRow, Serialize)]
+pub struct EventTemplate {
+    pub id: Uuid,
+    pub organizer_id: Uuid,
+    pub name: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub capacity: Option<i32>,
+    pub category: Option<String>,
+    pub image_url: Option<String>,
+    pub image_thumbnails: Option<Json<BTreeMap<String, String>>>,
+    #[serde(skip_serializing)]
+    pub image_keys: Vec<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// create/update template req; from_event_id - polja se preuzimaju iz postojeceg dogadjaja
+#[derive(Debug, Default, Deserialize)]
+pub struct TemplateRequest {
+    pub name: Option<String>,
+    pub from_event_id: Option<Uuid>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub capacity: Option<i32>,
+    pub category: Option<String>,
+    pub image_url: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+/// Popunjen CreateEventRequest iz sablona (datum bira organizator)
+#[derive(Debug, Serialize)]
+pub struct EventPrefill {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub date_time: Option<NaiveDateTime>,
+    pub capacity: Option<i32>,
+    pub category: Option<String>,
+    pub image_url: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub template_id: Uuid,
+}
+
+/// Query za uvoz: format=csv|ics, dry_run=true samo proverava,
+/// capacity je kapacitet za .ics dogadjaje (iCalendar ga nema)
+#[derive(Debug, Deserialize)]
+pub struct ImportQuery {
+    pub format: Option<String>,
+    #[serde(default)]
+    pub dry_run: bool,
+    pub capacity: Option<i32>,
+}
+
+/// Neispravan red iz uvoza
+#[derive(Debug, Serialize)]
+pub struct ImportRowError {
+    pub row: usize,
+    pub message: String,
+}
+
+/// Rezultat uvoza; events su upisani dogadjaji (prazno za dry_run)
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub total: usize,
+    pub valid: usize,
+    pub imported: usize,
+    pub errors: Vec<ImportRowError>,
+    pub events: Vec<Event>,
+}
+
+/// Internal: licni kalendar - dogadjaji na koje je korisnik prijavljen
+#[derive(Debug, Deserialize)]
+pub struct CalendarRequest {
+    pub ids: Vec<Uuid>,
+    pub name: String,
+}
+
+/// serija dogadjaja - sablon + RRULE, iz nje se prave pojedinacni Event redovi
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct EventSeries {
+    pub id: Uuid,
+    pub organizer_id: Uuid,
+    pub title: String,
+    pub description: String,
+    pub location: String,
+    pub capacity: i32,
+    pub category: String,
+    pub image_url: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub dtstart: NaiveDateTime,
+    pub rrule: String,
+    pub exdates: Vec<NaiveDateTime>,
+    /// uslovi za prijavu koje dobijaju novi termini (postavlja ih PUT eligibility sa apply_to_series)
+    pub eligibility: Option<Json<EligibilityRules>>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// create series req
+#[derive(Debug, Deserialize)]
+pub struct CreateSeriesRequest {
+    pub title: String,
+    pub description: String,
+    pub location: String,
+    pub capacity: i32,
+    pub category: String,
+    pub image_url: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub dtstart: NaiveDateTime,
+    /// npr. "FREQ=WEEKLY;BYDAY=TU"
+    pub rrule: String,
+    #[serde(default)]
+    pub exdates: Vec<NaiveDateTime>,
+}
+
+/// Opseg izmene termina serije
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EditScope {
+    /// samo ovaj termin
+    This,
+    /// ovaj i svi sledeci (serija se deli na dve)
+    Following,
+    /// svi termini serije
+    All,
+}
+
+/// izmena termina serije - polja kao kod UpdateEventRequest + opseg
+/// rrule/exdates se mogu menjati samo za "following" i "all"
+#[derive(Debug, Deserialize)]
+pub struct UpdateOccurrenceRequest {
+    pub scope: EditScope,
+    pub rrule: Option<String>,
+    pub exdates: Option<Vec<NaiveDateTime>>,
+    #[serde(flatten)]
+    pub changes: UpdateEventRequest,
+}
+
+/// serija + njeni termini
+#[derive(Debug, Serialize)]
+pub struct SeriesDetails {
+    pub series: EventSeries,
+    pub occurrences: Vec<Event>,
+}
+
+/// predavac na dogadjaju
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct Speaker {
+    pub id: Uuid,
+    pub event_id: Uuid,
+    pub name: String,
+    pub bio: String,
+    pub photo_url: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSpeakerRequest {
+    pub name: String,
+    #[serde(default)]
+    pub bio: String,
+    pub photo_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateSpeakerRequest {
+    pub name: Option<String>,
+    pub bio: Option<String>,
+    pub photo_url: Option<String>,
+}
+
+/// sesija (predavanje) unutar dogadjaja
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct Session {
+    pub id: Uuid,
+    pub event_id: Uuid,
+    pub title: String,
+    pub r#abstract: String,
+    pub room: Option<String>,
+    pub track: Option<String>,
+    pub start_time: NaiveDateTime,
+    pub end_time: NaiveDateTime,
+    /// None = bez ogranicenja (vazi samo kapacitet dogadjaja)
+    pub capacity: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// sesija sa predavacima i brojem prijavljenih
+#[derive(Debug, Serialize)]
+pub struct SessionDetails {
+    #[serde(flatten)]
+    pub session: Session,
+    pub speakers: Vec<Speaker>,
+    pub attendees: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSessionRequest {
+    pub title: String,
+    #[serde(default)]
+    pub r#abstract: String,
+    pub room: Option<String>,
+    pub track: Option<String>,
+    pub start_time: NaiveDateTime,
+    pub end_time: NaiveDateTime,
+    pub capacity: Option<i32>,
+    #[serde(default)]
+    pub speaker_ids: Vec<Uuid>,
+}
+
+/// update sesije - speaker_ids (ako je poslat) zamenjuje celu listu
+#[derive(Debug, Deserialize)]
+pub struct UpdateSessionRequest {
+    pub title: Option<String>,
+    pub r#abstract: Option<String>,
+    pub room: Option<String>,
+    pub track: Option<String>,
+    pub start_time: Option<NaiveDateTime>,
+    pub end_time: Option<NaiveDateTime>,
+    pub capacity: Option<i32>,
+    pub speaker_ids: Option<Vec<Uuid>>,
+}
+
+/// agenda: dani -> trake -> sesije
+#[derive(Debug, Serialize)]
+pub struct Agenda {
+    pub event_id: Uuid,
+    pub days: Vec<AgendaDay>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AgendaDay {
+    pub date: NaiveDate,
+    pub tracks: Vec<AgendaTrack>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AgendaTrack {
+    pub track: Option<String>,
+    pub sessions: Vec<SessionDetails>,
+}
+
+/// Prijava koju vraca registration-service (interni poziv)
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+pub struct RegistrationData {
+    pub id: Uuid,
+    pub event_id: Uuid,
+    pub user_id: Uuid,
+    pub status: String,
+    #[serde(default)]
+    pub checked_in_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RegistrationServiceResponse {
+    pub success: bool,
+    pub data: Option<RegistrationData>,
+}
+
+/// tip karte za dogadjaj - svaki ima svoju kvotu, cenu i period prodaje
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct TicketType {
+    pub id: Uuid,
+    pub event_id: Uuid,
+    pub name: String,
+    pub description: String,
+    /// cena u najmanjoj jedinici valute (para)
+    pub price_cents: i64,
+    pub currency: String,
+    pub quota: i32,
+    pub sales_start: Option<NaiveDateTime>,
+    pub sales_end: Option<NaiveDateTime>,
+    /// public ili hidden
+    pub visibility: String,
+    pub sort_order: i32,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTicketTypeRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub price_cents: i64,
+    pub currency: Option<String>,
+    pub quota: i32,
+    pub sales_start: Option<NaiveDateTime>,
+    pub sales_end: Option<NaiveDateTime>,
+    pub visibility: Option<String>,
+    #[serde(default)]
+    pub sort_order: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTicketTypeRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub price_cents: Option<i64>,
+    pub currency: Option<String>,
+    pub quota: Option<i32>,
+    /// izostavljeno = bez promene, null = brise granicu prodaje
+    #[serde(default, deserialize_with = "nullable")]
+    pub sales_start: Option<Option<NaiveDateTime>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub sales_end: Option<Option<NaiveDateTime>>,
+    pub visibility: Option<String>,
+    pub sort_order: Option<i32>,
+}
+
+/// Razlikuje polje koje nije poslato (None) od eksplicitnog null (Some(None))
+fn nullable<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// clan osoblja dogadjaja (organizator nije ovde - on je uvek "owner")
+#[derive(Debug, FromRow, Serialize)]
+pub struct StaffMember {
+    pub event_id: Uuid,
+    pub user_id: Uuid,
+    pub email: String,
+    /// co_organizer, editor, checkin ili viewer
+    pub role: String,
+    pub added_by: Uuid,
+    pub created_at: NaiveDateTime,
+}
+
+/// dodavanje osoblja po email-u (korisnik mora imati nalog); postojecem clanu menja ulogu
+#[derive(Debug, Deserialize)]
+pub struct AddStaffRequest {
+    pub email: String,
+    pub role: String,
+}
+
+/// Korisnik koga vraca auth-service (interni poziv)
+#[derive(Debug, Deserialize)]
+pub struct UserData {
+    pub id: Uuid,
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuthServiceResponse {
+    pub success: bool,
+    pub data: Option<UserData>,
+}
+
+/// pozivnica za privatni dogadjaj
+#[derive(Debug, FromRow, Serialize)]
+pub struct Invitation {
+    pub event_id: Uuid,
+    pub email: String,
+    pub invited_by: Uuid,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InviteRequest {
+    pub emails: Vec<String>,
+}
+
+/// podesavanja pristupa - vidi ih samo ko sme da menja dogadjaj
+#[derive(Debug, Serialize)]
+pub struct EventAccessSettings {
+    pub visibility: String,
+    pub slug: String,
+    pub access_code: Option<String>,
+    pub invitations: Vec<Invitation>,
+}
+
+/// Interna provera pre prijave (registration-service)
+#[derive(Debug, Deserialize)]
+pub struct AdmissionRequest {
+    pub user_id: Uuid,
+    pub email: String,
+    pub access_code: Option<String>,
+}
+
+/// polja dogadjaja koja se pamte u istoriji (slika ne - stari fajlovi se brisu pri zameni)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventSnapshot {
+    pub title: String,
+    pub description: String,
+    pub location: String,
+    pub date_time: NaiveDateTime,
+    pub capacity: i32,
+    pub category: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub status: String,
+    pub visibility: String,
+}
+
+impl From<&Event> for EventSnapshot {
+    fn from(e: &Event) -> Self {
+        EventSnapshot {
+            title: e.title.clone(),
+            description: e.description.clone(),
+            location: e.location.clone(),
+            date_time: e.date_time,
+            capacity: e.capacity,
+            category: e.category.clone(),
+            latitude: e.latitude,
+            longitude: e.longitude,
+            status: e.status.clone(),
+            visibility: e.visibility.clone(),
+        }
+    }
+}
+
+/// jedna promenjena vrednost u reviziji
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub old: serde_json::Value,
+    pub new: serde_json::Value,
+}
+
+/// revizija dogadjaja - stanje posle verzije `version` i razlika u odnosu na prethodnu
+#[derive(Debug, FromRow, Serialize)]
+pub struct EventRevision {
+    pub id: Uuid,
+    pub event_id: Uuid,
+    pub version: i32,
+    pub author_id: Option<Uuid>,
+    pub changes: Option<Json<BTreeMap<String, FieldChange>>>,
+    pub snapshot: Json<EventSnapshot>,
+    pub created_at: NaiveDateTime,
+}
+
+/// dogadjaj u kanti i kada ce biti trajno obrisan
+#[derive(Debug, Serialize)]
+pub struct TrashedEvent {
+    #[serde(flatten)]
+    pub event: Event,
+    pub purge_at: NaiveDateTime,
+}
+
+/// odgovor registration-service-a posle vracanja prijava
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct RestoredRegistrations {
+    pub restored: i64,
+    /// nije bilo mesta (kapacitet ili kvota tipa karte)
+    pub not_restored: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RestoredRegistrationsResponse {
+    pub success: bool,
+    pub data: Option<RestoredRegistrations>,
+}
+
+/// vraceni dogadjaj i sta je bilo sa prijavama
+#[derive(Debug, Serialize)]
+pub struct RestoreReport {
+    pub event: Event,
+    pub registrations: RestoredRegistrations,
+}
+
+/// broj prijava iz registration-service-a
+#[derive(Debug, Deserialize)]
+pub struct RegistrationCounts {
+    pub confirmed: i64,
+    #[serde(default)]
+    pub waitlisted: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RegistrationCountsResponse {
+    pub success: bool,
+    pub data: Option<RegistrationCounts>,
+}
+
+/// sta je registration-service uradio posle promene kapaciteta
+#[derive(Debug, Default, Deserialize)]
+pub struct CapacitySync {
+    /// premesteno na listu cekanja ili otkazano
+    pub moved: i64,
+    /// prebaceno sa liste cekanja u potvrdjene
+    pub promoted: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CapacitySyncResponse {
+    pub success: bool,
+    pub data: Option<CapacitySync>,
+}
+
+/// Query za vracanje verzije (kapacitet iz stare verzije moze biti manji od broja prijava)
+#[derive(Debug, Deserialize)]
+pub struct RestoreRevisionQuery {
+    pub capacity_strategy: Option<CapacityStrategy>,
+}
+
+/// kategorija dogadjaja (events.category cuva naziv)
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct Category {
+    pub slug: String,
+    pub name: String,
+    /// prevodi naziva po jeziku: {"en": "Conference"}
+    pub translations: Json<BTreeMap<String, String>>,
+    pub sort_order: i32,
+    pub created_at: NaiveDateTime,
+}
+
+/// kategorija u listi - naziv na trazenom jeziku i broj objavljenih dogadjaja
+#[derive(Debug, FromRow, Serialize)]
+pub struct CategoryWithCount {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub category: Category,
+    #[sqlx(skip)]
+    pub label: String,
+    pub event_count: i64,
+}
+
+/// GET /categories?lang=en
+#[derive(Debug, Deserialize)]
+pub struct CategoryQuery {
+    pub lang: Option<String>,
+}
+
+/// create/update kategorije (Admin); slug se pravi iz naziva ako nije zadat
+#[derive(Debug, Deserialize)]
+pub struct CategoryRequest {
+    pub name: Option<String>,
+    pub slug: Option<String>,
+    pub translations: Option<BTreeMap<String, String>>,
+    pub sort_order: Option<i32>,
+}
+
+/// vrednost filtera i broj dogadjaja sa njom
+#[derive(Debug, Serialize)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: usize,
+}
+
+/// cena po najjeftinijoj javnoj karti (bez tipova karata = besplatno)
+#[derive(Debug, Default, Serialize)]
+pub struct PriceFacet {
+    pub free: usize,
+    pub paid: usize,
+    pub min_cents: Option<i64>,
+    pub max_cents: Option<i64>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct AvailabilityFacet {
+    pub available: usize,
+    pub sold_out: usize,
+}
+
+/// brojevi za trenutne filtere liste
+#[derive(Debug, Serialize)]
+pub struct EventFacets {
+    pub categories: Vec<FacetCount>,
+    pub tags: Vec<FacetCount>,
+    pub cities: Vec<FacetCount>,
+    /// today, tomorrow, weekend, week, month, later, past (mogu da se preklapaju)
+    pub dates: Vec<FacetCount>,
+    pub price: PriceFacet,
+    /// None ako registration-service nije dostupan
+    pub availability: Option<AvailabilityFacet>,
+}
+
+/// GET /events?facets=true
+#[derive(Debug, Serialize)]
+pub struct EventListWithFacets {
+    pub events: Vec<Event>,
+    pub facets: EventFacets,
+}
+
+/// broj potvrdjenih prijava po dogadjaju (registration-service, vise dogadjaja odjednom)
+#[derive(Debug, Deserialize)]
+pub struct EventRegistrationCounts {
+    pub event_id: Uuid,
+    pub confirmed: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EventRegistrationCountsResponse {
+    pub success: bool,
+    pub data: Option<Vec<EventRegistrationCounts>>,
+}
+
+/// utisak posetioca: ocena 1-5, tekst i odgovor organizatora
+#[derive(Debug, FromRow, Serialize)]
+pub struct Review {
+    pub id: Uuid,
+    pub event_id: Uuid,
+    pub user_id: Uuid,
+    pub author_name: String,
+    pub rating: i16,
+    pub body: String,
+    pub reply: Option<String>,
+    pub replied_by: Option<Uuid>,
+    pub replied_at: Option<NaiveDateTime>,
+    pub hidden: bool,
+    pub hidden_reason: Option<String>,
+    pub report_count: i32,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateReviewRequest {
+    pub rating: i16,
+    #[serde(default)]
+    pub body: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateReviewRequest {
+    pub rating: Option<i16>,
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewReplyRequest {
+    pub reply: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReportReviewRequest {
+    #[serde(default)]
+    pub reason: String,
+}
+
+/// sakrivanje/vracanje utiska (Admin); vracanje odbacuje prijave
+#[derive(Debug, Deserialize)]
+pub struct ModerateReviewRequest {
+    pub hidden: bool,
+    pub reason: Option<String>,
+}
+
+/// prosek i raspodela vidljivih ocena
+#[derive(Debug, Serialize)]
+pub struct RatingSummary {
+    pub average: Option<f64>,
+    pub count: i64,
+    /// broj ocena 1, 2, 3, 4 i 5
+    pub distribution: [i64; 5],
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReviewList {
+    pub summary: RatingSummary,
+    pub reviews: Vec<Review>,
+}
+
+/// pitanje ili komentar na dogadjaju; odgovor ima parent_id
+#[derive(Debug, FromRow, Serialize)]
+pub struct Question {
+    pub id: Uuid,
+    pub event_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub user_id: Uuid,
+    pub author_name: String,
+    pub body: String,
+    pub from_staff: bool,
+    pub answered_at: Option<NaiveDateTime>,
+    pub pinned: bool,
+    pub hidden: bool,
+    pub upvotes: i32,
+    /// autor je obrisao pitanje koje vec ima odgovore - tekst je uklonjen
+    pub deleted_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    /// da li je trenutni korisnik glasao
+    #[sqlx(skip)]
+    pub voted: bool,
+}
+
+/// pitanje sa odgovorima (najstariji prvo)
+#[derive(Debug, Serialize)]
+pub struct QuestionThread {
+    #[serde(flatten)]
+    pub question: Question,
+    pub replies: Vec<Question>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateQuestionRequest {
+    pub body: String,
+    /// odgovor na pitanje (odgovor na odgovor ide u isti thread)
+    pub parent_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateQuestionRequest {
+    pub body: String,
+}
+
+/// moderacija (organizator, osoblje sa pravom izmene, Admin)
+#[derive(Debug, Deserialize)]
+pub struct ModerateQuestionRequest {
+    pub hidden: Option<bool>,
+    pub pinned: Option<bool>,
+}
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct FollowedOrganizer {
+    pub organizer_id: Uuid,
+    pub followed_at: NaiveDateTime,
+    pub upcoming_events: i64,
+}
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct FollowedCategory {
+    pub slug: String,
+    pub name: String,
+    pub followed_at: NaiveDateTime,
+    pub upcoming_events: i64,
+}
+
+/// sve sto korisnik prati
+#[derive(Debug, Serialize)]
+pub struct Follows {
+    pub organizers: Vec<FollowedOrganizer>,
+    pub categories: Vec<FollowedCategory>,
+}
+
+/// prijava iz registration-service-a kao par korisnik-dogadjaj (za preporuke)
+#[derive(Debug, Deserialize)]
+pub struct RegistrationActivity {
+    pub user_id: Uuid,
+    pub event_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RegistrationActivityResponse {
+    pub success: bool,
+    pub data: Option<Vec<RegistrationActivity>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecommendationQuery {
+    /// koliko preporuka (podrazumevano 10, najvise 50)
+    pub limit: Option<usize>,
+}
+
+/// preporucen dogadjaj sa ocenom i razlozima (similar_attendees, category, organizer, popular)
+#[derive(Debug, Serialize)]
+pub struct RecommendedEvent {
+    #[serde(flatten)]
+    pub event: Event,
+    pub score: f64,
+    pub reasons: Vec<&'static str>,
+}
+
+/// rezultat nocnog preracunavanja
+#[derive(Debug, Serialize)]
+pub struct RecommendationStats {
+    pub similar_pairs: usize,
+    pub popular_events: usize,
+}
+
+/// Internal: filter za kratke podatke o dogadjajima (analitika u registration-service-u)
+#[derive(Debug, Deserialize)]
+pub struct EventSummaryQuery {
+    pub id: Option<Uuid>,
+    pub organizer_id: Option<Uuid>,
+}
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct EventSummary {
+    pub id: Uuid,
+    pub title: String,
+    pub date_time: NaiveDateTime,
+    pub capacity: i32,
+    pub organizer_id: Uuid,
+    /// dogadjaj je poceo (po lokalnom vremenu) - tek tada ima smisla stopa nedolazaka
+    pub started: bool,
+}
+
+// ---- Prevodi ----
+
+/// prevod dogadjaja na jedan jezik; prazan opis ili lokacija - ostaje sa podrazumevanog jezika
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct EventTranslation {
+    #[serde(skip_serializing)]
+    pub event_id: Uuid,
+    pub locale: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub description_html: Option<String>,
+    pub location: Option<String>,
+    pub updated_at: NaiveDateTime,
+}
+
+/// PUT /events/:id/translations/:locale
+#[derive(Debug, Deserialize)]
+pub struct TranslationRequest {
+    pub title: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+}
+
+/// GET /events/:id/translations
+#[derive(Debug, Serialize)]
+pub struct EventTranslations {
+    /// jezik osnovnih polja dogadjaja
+    pub default_locale: String,
+    pub translations: Vec<EventTranslation>,
+}
+
+// ---- Uslovi za prijavu ----
+
+/// PUT /events/:id/eligibility - prazna pravila brisu uslove
+#[derive(Debug, Deserialize)]
+pub struct EligibilityRequest {
+    #[serde(flatten)]
+    pub rules: EligibilityRules,
+    /// ista pravila i za ostale buduce termine serije
+    #[serde(default)]
+    pub apply_to_series: bool,
+}
+
+/// Internal: uslovi + podaci koji su potrebni za proveru (godine na dan dogadjaja, termini serije)
+#[derive(Debug, Serialize)]
+pub struct EventEligibility {
+    pub rules: EligibilityRules,
+    pub date_time: NaiveDateTime,
+    /// ostali termini iste serije (bez ovog dogadjaja)
+    pub series_event_ids: Vec<Uuid>,
+}
+
+// ---- Izdvojeno i kolekcije ----
+
+/// PUT /events/:id/featured (Admin)
+#[derive(Debug, Deserialize)]
+pub struct FeaturedRequest {
+    pub featured: bool,
+    /// veci broj = ranije u listi (podrazumevano 0)
+    pub priority: Option<i32>,
+}
+
+/// rucno slozena kolekcija dogadjaja (npr. za pocetnu stranu)
+#[derive(Debug, FromRow, Serialize)]
+pub struct Collection {
+    pub id: Uuid,
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    /// vidljiva od/do (None = bez ogranicenja)
+    pub active_from: Option<NaiveDateTime>,
+    pub active_until: Option<NaiveDateTime>,
+    pub created_by: Uuid,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// kolekcija u listi - sa brojem vidljivih dogadjaja
+#[derive(Debug, FromRow, Serialize)]
+pub struct CollectionSummary {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub collection: Collection,
+    pub event_count: i64,
+}
+
+/// GET /collections/:slug - kolekcija sa dogadjajima po redosledu
+#[derive(Debug, Serialize)]
+pub struct CollectionDetails {
+    #[serde(flatten)]
+    pub collection: Collection,
+    /// objavljeni javni dogadjaji, po redosledu iz kolekcije
+    pub events: Vec<Event>,
+}
+
+/// create/update kolekcije (Admin); PUT menja sva polja, event_ids samo ako je poslat
+#[derive(Debug, Deserialize)]
+pub struct CollectionRequest {
+    pub title: String,
+    /// inace se pravi iz naziva
+    pub slug: Option<String>,
+    #[serde(default)]
+    pub description: String,
+    pub active_from: Option<NaiveDateTime>,
+    pub active_until: Option<NaiveDateTime>,
+    /// dogadjaji po redosledu prikaza
+    pub event_ids: Option<Vec<Uuid>>,
+}
+
+/// GET /collections?all=true - Admin vidi i neaktivne
+#[derive(Debug, Deserialize)]
+pub struct CollectionQuery {
+    #[serde(default)]
+    pub all: bool,
 }
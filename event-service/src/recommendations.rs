@@ -0,0 +1,301 @@
+// Preporuke - slicnost dogadjaja po zajednickim posetiocima, afinitet prema kategorijama i organizatorima,
+// a za nove korisnike popularnost. Slicnost i popularnost se racunaju nocu, ostalo na zahtev.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Duration, NaiveDateTime};
+use uuid::Uuid;
+
+use crate::facets::local_now;
+use crate::models::{Event, RecommendationStats, RecommendedEvent, RegistrationActivity, RegistrationActivityResponse};
+use crate::AppState;
+
+/// koliko slicnih dogadjaja cuvamo po dogadjaju
+const SIMILAR_PER_EVENT: usize = 20;
+/// korisnici sa vise dogadjaja se preskacu - broj parova raste kvadratno, a malo govore o ukusu
+const MAX_USER_EVENTS: usize = 200;
+/// sat (po lokalnom vremenu) kad se preracunava
+const PRECOMPUTE_HOUR: u32 = 3;
+/// sacuvan dogadjaj vredi pola prijave
+const BOOKMARK_WEIGHT: f64 = 0.5;
+/// pracena kategorija/organizator vredi kao dva dogadjaja iz istorije
+const FOLLOW_WEIGHT: f64 = 2.0;
+
+/// Pozadinski posao - preracunava slicnost i popularnost posle pokretanja, pa svake noci
+pub async fn run_precompute(state: AppState) {
+    // registration-service mozda jos nije podignut
+    tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+
+    loop {
+        match precompute(&state).await {
+            Ok(stats) => tracing::info!(
+                "Preporuke: {} parova slicnih dogadjaja, {} popularnih",
+                stats.similar_pairs,
+                stats.popular_events
+            ),
+            Err(e) => tracing::error!("Preracunavanje preporuka nije uspelo: {}", e),
+        }
+        tokio::time::sleep(until_next_run(local_now())).await;
+    }
+}
+
+fn until_next_run(now: NaiveDateTime) -> std::time::Duration {
+    let today = now.date().and_hms_opt(PRECOMPUTE_HOUR, 0, 0).expect("validan sat");
+    let next = if today > now { today } else { today + Duration::days(1) };
+    (next - now).to_std().unwrap_or_default()
+}
+
+/// Prijave iz registration-service-a (jednog korisnika ili svih)
+pub async fn fetch_activity(state: &AppState, user_id: Option<Uuid>) -> Result<Vec<RegistrationActivity>, String> {
+    let mut url = format!("{}/internal/registrations/activity", state.registration_service_url);
+    if let Some(user_id) = user_id {
+        url.push_str(&format!("?user_id={}", user_id));
+    }
+    let body = state
+        .http_client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .json::<RegistrationActivityResponse>()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    match body.data {
+        Some(list) if body.success => Ok(list),
+        _ => Err("Registration servis vratio gresku".to_string()),
+    }
+}
+
+/// Kosinusna slicnost po zajednickim korisnicima: za svaki dogadjaj do SIMILAR_PER_EVENT ciljeva
+fn similarity(by_user: &HashMap<Uuid, HashSet<Uuid>>, targets: &HashSet<Uuid>) -> Vec<(Uuid, Uuid, f64)> {
+    let mut counts: HashMap<Uuid, usize> = HashMap::new();
+    let mut together: HashMap<(Uuid, Uuid), usize> = HashMap::new();
+    for events in by_user.values().filter(|e| e.len() <= MAX_USER_EVENTS) {
+        for a in events {
+            *counts.entry(*a).or_default() += 1;
+            for b in events.iter().filter(|b| *b != a && targets.contains(b)) {
+                *together.entry((*a, *b)).or_default() += 1;
+            }
+        }
+    }
+
+    let mut by_event: HashMap<Uuid, Vec<(Uuid, f64)>> = HashMap::new();
+    for ((a, b), n) in together {
+        let score = n as f64 / ((counts[&a] * counts[&b]) as f64).sqrt();
+        by_event.entry(a).or_default().push((b, score));
+    }
+    by_event
+        .into_iter()
+        .flat_map(|(a, mut similar)| {
+            similar.sort_by(|x, y| y.1.total_cmp(&x.1));
+            similar.truncate(SIMILAR_PER_EVENT);
+            similar.into_iter().map(move |(b, score)| (a, b, score))
+        })
+        .collect()
+}
+
+/// Preracunava event_similarity i event_popularity iz svih prijava i sacuvanih dogadjaja
+pub async fn precompute(state: &AppState) -> Result<RecommendationStats, String> {
+    let activity = fetch_activity(state, None).await?;
+
+    let result = async {
+        let bookmarks = sqlx::query_as::<_, (Uuid, Uuid)>("SELECT user_id, event_id FROM event_bookmarks")
+            .fetch_all(&state.db)
+            .await?;
+        let existing: HashSet<Uuid> = sqlx::query_scalar::<_, Uuid>("SELECT id FROM events WHERE deleted_at IS NULL")
+            .fetch_all(&state.db)
+            .await?
+            .into_iter()
+            .collect();
+        let targets: HashSet<Uuid> = sqlx::query_scalar::<_, Uuid>(
+            "SELECT id FROM events WHERE deleted_at IS NULL AND status = 'published' AND visibility = 'public'
+               AND date_time >= $1",
+        )
+        .bind(local_now())
+        .fetch_all(&state.db)
+        .await?
+        .into_iter()
+        .collect();
+
+        // istorija po korisniku (prijave i sacuvani) i popularnost ciljeva
+        let mut by_user: HashMap<Uuid, HashSet<Uuid>> = HashMap::new();
+        let mut popularity: HashMap<Uuid, (i32, i32)> = HashMap::new();
+        for a in activity.iter().filter(|a| existing.contains(&a.event_id)) {
+            by_user.entry(a.user_id).or_default().insert(a.event_id);
+            if targets.contains(&a.event_id) {
+                popularity.entry(a.event_id).or_default().0 += 1;
+            }
+        }
+        for (user_id, event_id) in bookmarks.iter().filter(|(_, e)| existing.contains(e)) {
+            by_user.entry(*user_id).or_default().insert(*event_id);
+            if targets.contains(event_id) {
+                popularity.entry(*event_id).or_default().1 += 1;
+            }
+        }
+        let similar = similarity(&by_user, &targets);
+
+        let mut tx = state.db.begin().await?;
+        sqlx::query("DELETE FROM event_similarity").execute(&mut *tx).await?;
+        sqlx::query(
+            "INSERT INTO event_similarity (event_id, similar_event_id, score)
+             SELECT * FROM UNNEST($1::uuid[], $2::uuid[], $3::float8[])",
+        )
+        .bind(similar.iter().map(|s| s.0).collect::<Vec<_>>())
+        .bind(similar.iter().map(|s| s.1).collect::<Vec<_>>())
+        .bind(similar.iter().map(|s| s.2).collect::<Vec<_>>())
+        .execute(&mut *tx)
+        .await?;
+
+        let popular: Vec<(Uuid, i32, i32)> = popularity.into_iter().map(|(id, (r, b))| (id, r, b)).collect();
+        sqlx::query("DELETE FROM event_popularity").execute(&mut *tx).await?;
+        sqlx::query(
+            "INSERT INTO event_popularity (event_id, registrations, bookmarks, score)
+             SELECT id, r, b, r + $4 * b FROM UNNEST($1::uuid[], $2::int[], $3::int[]) AS t(id, r, b)",
+        )
+        .bind(popular.iter().map(|p| p.0).collect::<Vec<_>>())
+        .bind(popular.iter().map(|p| p.1).collect::<Vec<_>>())
+        .bind(popular.iter().map(|p| p.2).collect::<Vec<_>>())
+        .bind(BOOKMARK_WEIGHT)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok::<_, sqlx::Error>(RecommendationStats { similar_pairs: similar.len(), popular_events: popular.len() })
+    }
+    .await;
+
+    result.map_err(|e| e.to_string())
+}
+
+/// Sta znamo o korisniku: istorija, afiniteti (0-1) i slicnost kandidata sa istorijom
+#[derive(Default)]
+pub struct Signals {
+    pub history: HashSet<Uuid>,
+    pub categories: HashMap<String, f64>,
+    pub organizers: HashMap<Uuid, f64>,
+    pub similar: HashMap<Uuid, f64>,
+    pub popularity: HashMap<Uuid, f64>,
+}
+
+impl Signals {
+    fn cold(&self) -> bool {
+        self.history.is_empty() && self.categories.is_empty() && self.organizers.is_empty()
+    }
+}
+
+/// Deli sve vrednosti najvecom, da afinitet bude izmedju 0 i 1
+fn normalize<K>(map: &mut HashMap<K, f64>) {
+    let max = map.values().copied().fold(0.0, f64::max);
+    if max > 0.0 {
+        map.values_mut().for_each(|v| *v /= max);
+    }
+}
+
+/// Ucitava signale za korisnika. Bez registration-service-a ostaju sacuvani dogadjaji i pracenja.
+pub async fn load_signals(state: &AppState, user_id: Option<Uuid>) -> Result<Signals, sqlx::Error> {
+    let mut signals = Signals {
+        popularity: sqlx::query_as::<_, (Uuid, f64)>("SELECT event_id, score FROM event_popularity")
+            .fetch_all(&state.db)
+            .await?
+            .into_iter()
+            .collect(),
+        ..Default::default()
+    };
+    normalize(&mut signals.popularity);
+    let Some(user_id) = user_id else {
+        return Ok(signals);
+    };
+
+    let registered = match fetch_activity(state, Some(user_id)).await {
+        Ok(list) => list.into_iter().map(|a| a.event_id).collect(),
+        Err(e) => {
+            tracing::warn!("Prijave korisnika {} nisu dostupne za preporuke: {}", user_id, e);
+            Vec::new()
+        }
+    };
+    let bookmarked = sqlx::query_scalar::<_, Uuid>("SELECT event_id FROM event_bookmarks WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_all(&state.db)
+        .await?;
+    signals.history = registered.iter().chain(&bookmarked).copied().collect();
+    let history: Vec<Uuid> = signals.history.iter().copied().collect();
+
+    let seen = sqlx::query_as::<_, (Uuid, String, Uuid)>("SELECT id, category, organizer_id FROM events WHERE id = ANY($1)")
+        .bind(&history)
+        .fetch_all(&state.db)
+        .await?;
+    for (id, category, organizer_id) in seen {
+        let weight = if registered.contains(&id) { 1.0 } else { BOOKMARK_WEIGHT };
+        *signals.categories.entry(category).or_default() += weight;
+        *signals.organizers.entry(organizer_id).or_default() += weight;
+    }
+
+    let followed_categories = sqlx::query_scalar::<_, String>(
+        "SELECT c.name FROM category_follows f JOIN categories c ON c.slug = f.category_slug WHERE f.user_id = $1",
+    )
+    .bind(user_id)
+    .fetch_all(&state.db)
+    .await?;
+    for name in followed_categories {
+        *signals.categories.entry(name).or_default() += FOLLOW_WEIGHT;
+    }
+    let followed_organizers =
+        sqlx::query_scalar::<_, Uuid>("SELECT organizer_id FROM organizer_follows WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_all(&state.db)
+            .await?;
+    for id in followed_organizers {
+        *signals.organizers.entry(id).or_default() += FOLLOW_WEIGHT;
+    }
+
+    signals.similar = sqlx::query_as::<_, (Uuid, f64)>(
+        "SELECT similar_event_id, SUM(score) FROM event_similarity WHERE event_id = ANY($1) GROUP BY similar_event_id",
+    )
+    .bind(&history)
+    .fetch_all(&state.db)
+    .await?
+    .into_iter()
+    .collect();
+
+    normalize(&mut signals.categories);
+    normalize(&mut signals.organizers);
+    normalize(&mut signals.similar);
+    Ok(signals)
+}
+
+/// Rangira kandidate: slicni posetioci > kategorija > organizator > popularnost.
+/// Bez ikakve istorije (hladan start) samo po popularnosti, pa po datumu.
+pub fn rank(candidates: Vec<Event>, signals: &Signals, limit: usize) -> Vec<RecommendedEvent> {
+    let cold = signals.cold();
+    let mut ranked: Vec<RecommendedEvent> = candidates
+        .into_iter()
+        .filter(|e| !signals.history.contains(&e.id))
+        .map(|event| {
+            let similar = signals.similar.get(&event.id).copied().unwrap_or(0.0);
+            let category = signals.categories.get(&event.category).copied().unwrap_or(0.0);
+            let organizer = signals.organizers.get(&event.organizer_id).copied().unwrap_or(0.0);
+            let popular = signals.popularity.get(&event.id).copied().unwrap_or(0.0);
+
+            let score = if cold {
+                popular
+            } else {
+                0.5 * similar + 0.25 * category + 0.15 * organizer + 0.1 * popular
+            };
+            let reasons = [
+                (similar > 0.0, "similar_attendees"),
+                (category > 0.0, "category"),
+                (organizer > 0.0, "organizer"),
+                (popular > 0.0, "popular"),
+            ]
+            .into_iter()
+            .filter_map(|(hit, reason)| hit.then_some(reason))
+            .collect();
+            RecommendedEvent { event, score: (score * 1000.0).round() / 1000.0, reasons }
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.event.date_time.cmp(&b.event.date_time)));
+    ranked.truncate(limit);
+    ranked
+}
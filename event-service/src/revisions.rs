@@ -0,0 +1,167 @@
+// Istorija izmena dogadjaja - snimci stanja po verzijama i razlika izmedju njih
+
+use std::collections::BTreeMap;
+
+use axum::http::{header, HeaderMap, HeaderValue};
+use sqlx::types::Json;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::models::{Event, EventSnapshot, FieldChange, UpdateEventRequest};
+
+/// ETag za verziju dogadjaja: "3"
+pub fn etag(version: i32) -> HeaderValue {
+    HeaderValue::from_str(&format!("\"{}\"", version)).expect("broj je validan header")
+}
+
+/// If-Match -> Ok(None) ako ga nema ili je "*", Ok(Some(v)) za "3" / W/"3", Err za ostalo
+pub fn if_match_version(headers: &HeaderMap) -> Result<Option<i32>, &'static str> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let value = value.to_str().map_err(|_| "Neispravan If-Match header")?.trim();
+    if value == "*" {
+        return Ok(None);
+    }
+    value
+        .trim_start_matches("W/")
+        .trim_matches('"')
+        .parse()
+        .map(Some)
+        .map_err(|_| "Neispravan If-Match header - ocekuje se ETag dogadjaja, npr. \"3\"")
+}
+
+/// Polja koja se razlikuju izmedju dva snimka: {polje: {old, new}}
+pub fn diff(before: &EventSnapshot, after: &EventSnapshot) -> BTreeMap<String, FieldChange> {
+    let to_map = |s: &EventSnapshot| match serde_json::to_value(s) {
+        Ok(serde_json::Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    };
+    let (old, mut new) = (to_map(before), to_map(after));
+
+    old.into_iter()
+        .filter_map(|(field, old)| {
+            let new = new.remove(&field).unwrap_or(serde_json::Value::Null);
+            (old != new).then_some((field, FieldChange { old, new }))
+        })
+        .collect()
+}
+
+/// Upisuje reviziju za izmenu before -> after. Stanje pre prve izmene se cuva
+/// kao revizija bez autora (dogadjaji napravljeni pre istorije je nemaju).
+pub async fn record_revision(
+    tx: &mut Transaction<'_, Postgres>,
+    before: &Event,
+    after: &Event,
+    author_id: Uuid,
+) -> Result<(), sqlx::Error> {
+    let old = EventSnapshot::from(before);
+    let new = EventSnapshot::from(after);
+
+    sqlx::query(
+        "INSERT INTO event_revisions (event_id, version, snapshot, created_at)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (event_id, version) DO NOTHING",
+    )
+    .bind(before.id)
+    .bind(before.version)
+    .bind(Json(&old))
+    .bind(before.updated_at)
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query(
+        "INSERT INTO event_revisions (event_id, version, author_id, changes, snapshot)
+         VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(after.id)
+    .bind(after.version)
+    .bind(author_id)
+    .bind(Json(diff(&old, &new)))
+    .bind(Json(&new))
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Izmena koja vraca dogadjaj na stanje iz snimka (slika i pristupni kod ostaju kakvi jesu)
+pub fn restore_request(snapshot: EventSnapshot) -> UpdateEventRequest {
+    UpdateEventRequest {
+        title: Some(snapshot.title),
+        description: Some(snapshot.description),
+        location: Some(snapshot.location),
+        date_time: Some(snapshot.date_time),
+        capacity: Some(snapshot.capacity),
+        category: Some(snapshot.category),
+        latitude: snapshot.latitude,
+        longitude: snapshot.longitude,
+        status: Some(snapshot.status),
+        visibility: Some(snapshot.visibility),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+
+    fn if_match(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    fn snapshot() -> EventSnapshot {
+        EventSnapshot {
+            title: "Rust meetup".to_string(),
+            description: "Opis".to_string(),
+            location: "Novi Sad".to_string(),
+            date_time: NaiveDateTime::parse_from_str("2027-03-15 18:00", "%Y-%m-%d %H:%M").unwrap(),
+            capacity: 40,
+            category: "Tehnologija".to_string(),
+            latitude: None,
+            longitude: None,
+            status: "published".to_string(),
+            visibility: "public".to_string(),
+        }
+    }
+
+    #[test]
+    fn if_match_accepts_strong_and_weak_etags() {
+        assert_eq!(if_match_version(&HeaderMap::new()), Ok(None));
+        assert_eq!(if_match_version(&if_match("*")), Ok(None));
+        assert_eq!(if_match_version(&if_match("\"3\"")), Ok(Some(3)));
+        assert_eq!(if_match_version(&if_match("W/\"12\"")), Ok(Some(12)));
+        assert_eq!(if_match_version(&if_match("7")), Ok(Some(7)));
+        assert!(if_match_version(&if_match("\"abc\"")).is_err());
+
+        assert_eq!(etag(5), "\"5\"");
+    }
+
+    #[test]
+    fn diff_lists_only_changed_fields() {
+        let before = snapshot();
+        assert!(diff(&before, &before).is_empty());
+
+        let mut after = snapshot();
+        after.capacity = 60;
+        after.latitude = Some(45.25);
+        let changes = diff(&before, &after);
+
+        assert_eq!(changes.keys().collect::<Vec<_>>(), ["capacity", "latitude"]);
+        assert_eq!(changes["capacity"].old, 40);
+        assert_eq!(changes["capacity"].new, 60);
+        assert!(changes["latitude"].old.is_null());
+    }
+
+    #[test]
+    fn restore_request_keeps_snapshot_fields() {
+        let req = restore_request(snapshot());
+        assert_eq!(req.title.as_deref(), Some("Rust meetup"));
+        assert_eq!(req.capacity, Some(40));
+        assert_eq!(req.visibility.as_deref(), Some("public"));
+        assert!(req.image_url.is_none());
+    }
+}
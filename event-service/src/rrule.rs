@@ -0,0 +1,536 @@
+// RRULE (iCalendar, RFC 5545) - parsiranje i racunanje termina za serije dogadjaja
+//
+// Podrzano: FREQ (DAILY, WEEKLY, MONTHLY, YEARLY), INTERVAL, COUNT, UNTIL,
+// BYDAY (sa rednim brojem za MONTHLY/YEARLY, npr. 1MO ili -1FR), BYMONTHDAY, BYMONTH, WKST=MO.
+// Svi termini imaju isto vreme kao DTSTART.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Weekday};
+
+/// Zastita od beskonacne petlje kod pravila koja nikad ne daju termin
+const MAX_PERIODS: u32 = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// Jedan BYDAY element - npr. MO, 2TU, -1FR
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ByDay {
+    pub ordinal: Option<i32>,
+    pub weekday: Weekday,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RRule {
+    pub freq: Frequency,
+    pub interval: u32,
+    pub count: Option<u32>,
+    pub until: Option<NaiveDateTime>,
+    pub by_day: Vec<ByDay>,
+    pub by_month_day: Vec<i32>,
+    pub by_month: Vec<u32>,
+}
+
+fn parse_weekday(s: &str) -> Option<Weekday> {
+    match s {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+/// Parsira iCalendar datum/vreme: 20270101, 20270101T100000 ili 20270101T100000Z
+pub fn parse_ical_datetime(s: &str) -> Option<NaiveDateTime> {
+    let s = s.trim_end_matches('Z');
+    if s.len() == 8 {
+        return NaiveDate::parse_from_str(s, "%Y%m%d")
+            .ok()
+            .and_then(|d| d.and_hms_opt(0, 0, 0));
+    }
+    NaiveDateTime::parse_from_str(s, "%Y%m%dT%H%M%S").ok()
+}
+
+pub fn format_ical_datetime(dt: &NaiveDateTime) -> String {
+    dt.format("%Y%m%dT%H%M%S").to_string()
+}
+
+impl FromStr for RRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s.strip_prefix("RRULE:").unwrap_or(s);
+
+        let mut freq = None;
+        let mut rule = RRule {
+            freq: Frequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_month: Vec::new(),
+        };
+
+        for part in s.split(';').filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("Neispravan deo pravila: {}", part))?;
+            let value = value.to_uppercase();
+
+            match key.to_uppercase().as_str() {
+                "FREQ" => {
+                    freq = Some(match value.as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(format!("Nepodrzana frekvencija: {}", value)),
+                    })
+                }
+                "INTERVAL" => {
+                    rule.interval = value
+                        .parse()
+                        .ok()
+                        .filter(|i| *i > 0)
+                        .ok_or("INTERVAL mora biti pozitivan broj")?;
+                }
+                "COUNT" => {
+                    rule.count = Some(
+                        value
+                            .parse()
+                            .ok()
+                            .filter(|c| *c > 0)
+                            .ok_or("COUNT mora biti pozitivan broj")?,
+                    );
+                }
+                "UNTIL" => {
+                    rule.until = Some(parse_ical_datetime(&value).ok_or("Neispravan UNTIL datum")?);
+                }
+                "BYDAY" => {
+                    for item in value.split(',') {
+                        let (num, day) = item.split_at(item.len().saturating_sub(2));
+                        let weekday = parse_weekday(day)
+                            .ok_or_else(|| format!("Neispravan BYDAY: {}", item))?;
+                        let ordinal = if num.is_empty() {
+                            None
+                        } else {
+                            let n: i32 = num
+                                .trim_start_matches('+')
+                                .parse()
+                                .map_err(|_| format!("Neispravan BYDAY: {}", item))?;
+                            if n == 0 || n.abs() > 53 {
+                                return Err(format!("Neispravan BYDAY: {}", item));
+                            }
+                            Some(n)
+                        };
+                        rule.by_day.push(ByDay { ordinal, weekday });
+                    }
+                }
+                "BYMONTHDAY" => {
+                    for item in value.split(',') {
+                        let day: i32 = item
+                            .parse()
+                            .ok()
+                            .filter(|d: &i32| *d != 0 && d.abs() <= 31)
+                            .ok_or_else(|| format!("Neispravan BYMONTHDAY: {}", item))?;
+                        rule.by_month_day.push(day);
+                    }
+                }
+                "BYMONTH" => {
+                    for item in value.split(',') {
+                        let month: u32 = item
+                            .parse()
+                            .ok()
+                            .filter(|m| (1..=12).contains(m))
+                            .ok_or_else(|| format!("Neispravan BYMONTH: {}", item))?;
+                        rule.by_month.push(month);
+                    }
+                }
+                "WKST" if value == "MO" => {}
+                other => return Err(format!("Nepodrzan deo pravila: {}", other)),
+            }
+        }
+
+        rule.freq = freq.ok_or("FREQ je obavezan")?;
+        if rule.count.is_some() && rule.until.is_some() {
+            return Err("COUNT i UNTIL ne mogu zajedno".to_string());
+        }
+        if rule.by_day.iter().any(|d| d.ordinal.is_some())
+            && !matches!(rule.freq, Frequency::Monthly | Frequency::Yearly)
+        {
+            return Err("BYDAY sa rednim brojem vazi samo za MONTHLY i YEARLY".to_string());
+        }
+
+        Ok(rule)
+    }
+}
+
+impl fmt::Display for RRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let freq = match self.freq {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        };
+        write!(f, "FREQ={}", freq)?;
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        if let Some(until) = &self.until {
+            write!(f, ";UNTIL={}", format_ical_datetime(until))?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<String> = self
+                .by_day
+                .iter()
+                .map(|d| match d.ordinal {
+                    Some(n) => format!("{}{}", n, weekday_code(d.weekday)),
+                    None => weekday_code(d.weekday).to_string(),
+                })
+                .collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if !self.by_month_day.is_empty() {
+            let days: Vec<String> = self.by_month_day.iter().map(|d| d.to_string()).collect();
+            write!(f, ";BYMONTHDAY={}", days.join(","))?;
+        }
+        if !self.by_month.is_empty() {
+            let months: Vec<String> = self.by_month.iter().map(|m| m.to_string()).collect();
+            write!(f, ";BYMONTH={}", months.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|d| d.pred_opt())
+        .map(|d| d.day())
+        .unwrap_or(28)
+}
+
+/// Dodaje `months` meseci na (godina, mesec)
+fn add_months(year: i32, month: u32, months: u32) -> (i32, u32) {
+    let total = year as i64 * 12 + (month as i64 - 1) + months as i64;
+    ((total / 12) as i32, (total % 12) as u32 + 1)
+}
+
+/// Dan u nedelji pomeren za `days` dana (moze i unazad)
+fn shift_weekday(day: Weekday, days: i64) -> Weekday {
+    let mut shifted = day;
+    for _ in 0..days.rem_euclid(7) {
+        shifted = shifted.succ();
+    }
+    shifted
+}
+
+impl RRule {
+    /// Pravilo za seriju ciji se DTSTART pomera za `delta`: BYDAY dani idu zajedno sa terminima
+    /// (pomeranje ponedeljkom za dan - utorkom), UNTIL se pomera isto.
+    /// Greska kad se pomereni termini ne mogu opisati istim pravilom (BYDAY sa rednim brojem,
+    /// BYMONTHDAY, BYMONTH) - tada treba poslati novo pravilo.
+    pub fn shifted(&self, dtstart: NaiveDateTime, delta: Duration) -> Result<RRule, String> {
+        let mut rule = self.clone();
+        rule.until = self.until.map(|u| u + delta);
+
+        let days = ((dtstart + delta).date() - dtstart.date()).num_days();
+        if days == 0 {
+            return Ok(rule);
+        }
+        if self.by_day.iter().any(|d| d.ordinal.is_some()) || !self.by_month_day.is_empty() || !self.by_month.is_empty() {
+            return Err("Pomeranje na drugi dan nije podrzano za BYDAY sa rednim brojem, BYMONTHDAY i BYMONTH - posaljite novo pravilo".to_string());
+        }
+        for by_day in &mut rule.by_day {
+            by_day.weekday = shift_weekday(by_day.weekday, days);
+        }
+        Ok(rule)
+    }
+
+    /// Dani u jednom mesecu koji zadovoljavaju BYDAY/BYMONTHDAY (default je dan iz DTSTART)
+    fn month_days(&self, year: i32, month: u32, default_day: u32) -> Vec<NaiveDate> {
+        let last = days_in_month(year, month);
+        let all = (1..=last).filter_map(|d| NaiveDate::from_ymd_opt(year, month, d));
+
+        let by_month_day: Vec<u32> = self
+            .by_month_day
+            .iter()
+            .filter_map(|&d| {
+                let day = if d > 0 { d } else { last as i32 + d + 1 };
+                (day >= 1 && day <= last as i32).then_some(day as u32)
+            })
+            .collect();
+
+        if self.by_day.is_empty() && self.by_month_day.is_empty() {
+            return NaiveDate::from_ymd_opt(year, month, default_day).into_iter().collect();
+        }
+
+        all.filter(|date| {
+            let day_ok = self.by_day.is_empty()
+                || self.by_day.iter().any(|bd| {
+                    if date.weekday() != bd.weekday {
+                        return false;
+                    }
+                    match bd.ordinal {
+                        None => true,
+                        Some(n) if n > 0 => (date.day() - 1) / 7 + 1 == n as u32,
+                        Some(n) => (last - date.day()) / 7 + 1 == n.unsigned_abs(),
+                    }
+                });
+            let month_day_ok = self.by_month_day.is_empty() || by_month_day.contains(&date.day());
+            day_ok && month_day_ok
+        })
+        .collect()
+    }
+
+    /// Prvi dan perioda - sluzi da znamo kad smo presli kraj opsega
+    fn period_start(&self, start: NaiveDate, period: u32) -> NaiveDate {
+        let step = period * self.interval;
+        match self.freq {
+            Frequency::Daily => start + Duration::days(step as i64),
+            Frequency::Weekly => {
+                start - Duration::days(start.weekday().num_days_from_monday() as i64)
+                    + Duration::weeks(step as i64)
+            }
+            Frequency::Monthly => {
+                let (year, month) = add_months(start.year(), start.month(), step);
+                NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(start)
+            }
+            Frequency::Yearly => {
+                NaiveDate::from_ymd_opt(start.year() + step as i32, 1, 1).unwrap_or(start)
+            }
+        }
+    }
+
+    /// Kandidati za jedan period (dan/nedelja/mesec/godina), sortirani
+    fn period_dates(&self, dtstart: &NaiveDateTime, period: u32) -> Vec<NaiveDate> {
+        let start = dtstart.date();
+        let step = period * self.interval;
+
+        let dates = match self.freq {
+            Frequency::Daily => {
+                let date = start + Duration::days(step as i64);
+                let unfiltered = self.by_day.is_empty() && self.by_month_day.is_empty();
+                if unfiltered || self.month_days(date.year(), date.month(), date.day()).contains(&date) {
+                    vec![date]
+                } else {
+                    vec![]
+                }
+            }
+            Frequency::Weekly => {
+                let week_start = self.period_start(start, period);
+                let mut days: Vec<Weekday> = self.by_day.iter().map(|d| d.weekday).collect();
+                if days.is_empty() {
+                    days.push(start.weekday());
+                }
+                let mut dates: Vec<NaiveDate> = days
+                    .into_iter()
+                    .map(|wd| week_start + Duration::days(wd.num_days_from_monday() as i64))
+                    .collect();
+                dates.sort();
+                dates.dedup();
+                dates
+            }
+            Frequency::Monthly => {
+                let (year, month) = add_months(start.year(), start.month(), step);
+                self.month_days(year, month, start.day())
+            }
+            Frequency::Yearly => {
+                let year = start.year() + step as i32;
+                let months = if self.by_month.is_empty() {
+                    vec![start.month()]
+                } else {
+                    let mut months = self.by_month.clone();
+                    months.sort();
+                    months
+                };
+                months
+                    .into_iter()
+                    .flat_map(|m| self.month_days(year, m, start.day()))
+                    .collect()
+            }
+        };
+
+        dates
+            .into_iter()
+            .filter(|d| self.by_month.is_empty() || self.by_month.contains(&d.month()))
+            .collect()
+    }
+
+    /// Svi termini pravila od DTSTART do `end` (ukljucivo), bez EXDATE termina.
+    /// COUNT se racuna pre izbacivanja EXDATE-ova, kao u RFC 5545.
+    pub fn occurrences(
+        &self,
+        dtstart: NaiveDateTime,
+        exdates: &[NaiveDateTime],
+        end: NaiveDateTime,
+    ) -> Vec<NaiveDateTime> {
+        let mut result = Vec::new();
+        let mut produced = 0u32;
+        let limit = match self.until {
+            Some(until) if until < end => until,
+            _ => end,
+        };
+
+        for period in 0..MAX_PERIODS {
+            if self.period_start(dtstart.date(), period) > limit.date() {
+                break;
+            }
+            for date in self.period_dates(&dtstart, period) {
+                let occurrence = date.and_time(dtstart.time());
+                if occurrence < dtstart {
+                    continue;
+                }
+                if occurrence > limit {
+                    return result;
+                }
+                produced += 1;
+                if !exdates.contains(&occurrence) {
+                    result.push(occurrence);
+                }
+                if self.count.is_some_and(|c| produced >= c) {
+                    return result;
+                }
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn rule(s: &str) -> RRule {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn display_round_trips_parsed_rule() {
+        for s in [
+            "FREQ=DAILY",
+            "FREQ=WEEKLY;INTERVAL=2;COUNT=10;BYDAY=MO,WE,FR",
+            "FREQ=MONTHLY;UNTIL=20271231T235959;BYDAY=1MO,-1FR",
+            "FREQ=YEARLY;BYMONTHDAY=15,-1;BYMONTH=3,9",
+        ] {
+            let parsed = rule(s);
+            assert_eq!(parsed.to_string(), s);
+            assert_eq!(rule(&parsed.to_string()), parsed);
+        }
+
+        // prefiks, mala slova, INTERVAL=1 i WKST=MO se ne cuvaju
+        assert_eq!(rule("RRULE:freq=weekly;interval=1;byday=tu;wkst=mo").to_string(), "FREQ=WEEKLY;BYDAY=TU");
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        for s in [
+            "",
+            "INTERVAL=2",
+            "FREQ=HOURLY",
+            "FREQ=DAILY;COUNT=0",
+            "FREQ=DAILY;COUNT=3;UNTIL=20270101",
+            "FREQ=WEEKLY;BYDAY=1MO",
+            "FREQ=MONTHLY;BYDAY=0MO",
+            "FREQ=MONTHLY;BYMONTHDAY=32",
+            "FREQ=YEARLY;BYMONTH=13",
+            "FREQ=DAILY;WKST=SU",
+        ] {
+            assert!(s.parse::<RRule>().is_err(), "{} je prosao", s);
+        }
+    }
+
+    #[test]
+    fn count_includes_excluded_dates() {
+        let dtstart = at("2027-01-01 10:00");
+        let daily = rule("FREQ=DAILY;COUNT=3");
+
+        assert_eq!(
+            daily.occurrences(dtstart, &[at("2027-01-02 10:00")], at("2028-01-01 00:00")),
+            vec![at("2027-01-01 10:00"), at("2027-01-03 10:00")]
+        );
+        // EXDATE u drugo vreme ne pogadja termin
+        assert_eq!(daily.occurrences(dtstart, &[at("2027-01-02 11:00")], at("2028-01-01 00:00")).len(), 3);
+    }
+
+    #[test]
+    fn monthly_ordinal_weekdays() {
+        let dates = rule("FREQ=MONTHLY;COUNT=4;BYDAY=1MO,-1FR").occurrences(at("2027-01-01 18:00"), &[], at("2028-01-01 00:00"));
+        assert_eq!(
+            dates,
+            vec![at("2027-01-04 18:00"), at("2027-01-29 18:00"), at("2027-02-01 18:00"), at("2027-02-26 18:00")]
+        );
+    }
+
+    #[test]
+    fn periods_are_capped() {
+        let dtstart = at("2027-01-01 10:00");
+
+        // 31. februar ne postoji - petlja staje posle MAX_PERIODS dana umesto da ide do 2100.
+        let never = rule("FREQ=DAILY;BYMONTH=2;BYMONTHDAY=31");
+        assert!(never.occurrences(dtstart, &[], at("2100-01-01 00:00")).is_empty());
+
+        let daily = rule("FREQ=DAILY");
+        assert_eq!(daily.occurrences(dtstart, &[], at("2100-01-01 00:00")).len(), MAX_PERIODS as usize);
+    }
+
+    #[test]
+    fn shifted_moves_weekdays_and_until() {
+        let dtstart = at("2027-01-04 23:00"); // ponedeljak
+        let weekly = rule("FREQ=WEEKLY;UNTIL=20270301T230000;BYDAY=MO,SU");
+
+        let later = weekly.shifted(dtstart, Duration::hours(2)).unwrap();
+        assert_eq!(later.to_string(), "FREQ=WEEKLY;UNTIL=20270302T010000;BYDAY=TU,MO");
+
+        let earlier = weekly.shifted(dtstart, Duration::days(-1)).unwrap();
+        assert_eq!(earlier.to_string(), "FREQ=WEEKLY;UNTIL=20270228T230000;BYDAY=SU,SA");
+
+        // isti dan - menja se samo UNTIL
+        let same_day = weekly.shifted(dtstart, Duration::minutes(30)).unwrap();
+        assert_eq!(same_day.by_day, weekly.by_day);
+        assert_eq!(same_day.until, Some(at("2027-03-01 23:30")));
+    }
+
+    #[test]
+    fn shifted_rejects_rules_tied_to_dates() {
+        let dtstart = at("2027-01-04 10:00");
+        for s in ["FREQ=MONTHLY;BYDAY=1MO", "FREQ=MONTHLY;BYMONTHDAY=4", "FREQ=YEARLY;BYMONTH=1"] {
+            assert!(rule(s).shifted(dtstart, Duration::days(1)).is_err(), "{}", s);
+            assert!(rule(s).shifted(dtstart, Duration::hours(1)).is_ok(), "{}", s);
+        }
+    }
+}
@@ -0,0 +1,111 @@
+// Serije dogadjaja - pravi pojedinacne termine (Event redove) iz RRULE pravila
+
+use chrono::{Duration, NaiveDateTime, Utc};
+
+use crate::handlers::trash_event;
+use crate::markdown::render as render_markdown;
+use crate::models::{Event, EventSeries};
+use crate::rrule::RRule;
+use crate::AppState;
+
+/// Do kog trenutka se termini prave unapred (rolling horizon)
+pub fn horizon_end(state: &AppState) -> NaiveDateTime {
+    Utc::now().naive_utc() + Duration::days(state.series_horizon_days)
+}
+
+/// Uskladjuje buduce termine serije sa pravilom:
+/// termine koji su ispali iz pravila (osim rucno izmenjenih) salje u kantu i pravi nedostajuce do horizonta.
+/// Vraca broj novih termina.
+pub async fn sync_series(state: &AppState, series: &EventSeries) -> Result<u64, String> {
+    let rule: RRule = series.rrule.parse()?;
+    let now = Utc::now().naive_utc();
+    let horizon = horizon_end(state);
+
+    let wanted: Vec<NaiveDateTime> = rule
+        .occurrences(series.dtstart, &series.exdates, horizon)
+        .into_iter()
+        .filter(|dt| *dt >= now)
+        .collect();
+
+    let stale = sqlx::query_as::<_, Event>(
+        "SELECT * FROM events
+         WHERE series_id = $1 AND date_time >= $2 AND date_time <= $3
+           AND NOT is_exception AND deleted_at IS NULL AND NOT (occurrence_date = ANY($4))",
+    )
+    .bind(series.id)
+    .bind(now)
+    .bind(horizon)
+    .bind(&wanted)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    for event in stale {
+        trash_event(state, event.id, None).await.map_err(|e| e.to_string())?;
+    }
+
+    let created = sqlx::query_scalar::<_, uuid::Uuid>(
+        "INSERT INTO events (id, organizer_id, title, description, location, date_time, capacity, category, image_url, latitude, longitude, series_id, occurrence_date, description_html)
+         SELECT gen_random_uuid(), $1, $2, $3, $4, occ, $5, $6, $7, $8, $9, $10, occ, $12
+         FROM UNNEST($11::timestamp[]) AS occ
+         WHERE NOT EXISTS (SELECT 1 FROM events WHERE series_id = $10 AND occurrence_date = occ)
+         RETURNING id",
+    )
+    .bind(series.organizer_id)
+    .bind(&series.title)
+    .bind(&series.description)
+    .bind(&series.location)
+    .bind(series.capacity)
+    .bind(&series.category)
+    .bind(&series.image_url)
+    .bind(series.latitude)
+    .bind(series.longitude)
+    .bind(series.id)
+    .bind(&wanted)
+    .bind(render_markdown(&series.description))
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    // novi termini dobijaju uslove za prijavu od serije
+    if let (false, Some(rules)) = (created.is_empty(), &series.eligibility) {
+        sqlx::query(
+            "INSERT INTO event_eligibility (event_id, rules)
+             SELECT id, $2 FROM UNNEST($1::uuid[]) AS id
+             ON CONFLICT (event_id) DO NOTHING",
+        )
+        .bind(&created)
+        .bind(rules)
+        .execute(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(created.len() as u64)
+}
+
+/// Pozadinski posao - jednom na sat pomera horizont za sve serije
+pub async fn run_materializer(state: AppState) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+
+    loop {
+        interval.tick().await;
+
+        let all = sqlx::query_as::<_, EventSeries>("SELECT * FROM event_series")
+            .fetch_all(&state.db)
+            .await;
+
+        match all {
+            Ok(list) => {
+                for series in list {
+                    match sync_series(&state, &series).await {
+                        Ok(0) => {}
+                        Ok(n) => tracing::info!("Serija {}: napravljeno {} novih termina", series.id, n),
+                        Err(e) => tracing::warn!("Serija {}: greska pri pravljenju termina: {}", series.id, e),
+                    }
+                }
+            }
+            Err(e) => tracing::error!("Ne mogu da ucitam serije: {}", e),
+        }
+    }
+}
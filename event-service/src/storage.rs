@@ -0,0 +1,335 @@
+// Skladiste za fajlove (slike dogadjaja) - lokalni disk ili S3-kompatibilan servis
+
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+#[async_trait]
+pub trait Storage: Send + Sync {
+    /// Cuva fajl pod kljucem (npr. "events/<id>/<uuid>/original.jpg")
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<(), String>;
+
+    async fn delete(&self, key: &str) -> Result<(), String>;
+
+    /// Javni URL za kljuc
+    fn url(&self, key: &str) -> String;
+
+    /// Lokalni fajl za kljuc - samo za backend koji fajlove servira sam (/uploads)
+    fn local_path(&self, _key: &str) -> Option<PathBuf> {
+        None
+    }
+}
+
+/// Bira backend preko STORAGE_BACKEND (local | s3)
+pub fn from_env(http_client: reqwest::Client) -> Arc<dyn Storage> {
+    let public_url = std::env::var("UPLOAD_PUBLIC_URL").ok();
+
+    match std::env::var("STORAGE_BACKEND").as_deref() {
+        Ok("s3") => {
+            let endpoint = std::env::var("S3_ENDPOINT")
+                .expect("S3_ENDPOINT mora biti postavljen za STORAGE_BACKEND=s3");
+            let endpoint = endpoint.trim_end_matches('/').to_string();
+            let bucket = std::env::var("S3_BUCKET").unwrap_or_else(|_| "event-images".to_string());
+            let public_url = public_url.unwrap_or_else(|| format!("{}/{}", endpoint, bucket));
+
+            Arc::new(S3Storage {
+                endpoint,
+                bucket,
+                region: std::env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+                access_key: std::env::var("S3_ACCESS_KEY").expect("S3_ACCESS_KEY mora biti postavljen"),
+                secret_key: std::env::var("S3_SECRET_KEY").expect("S3_SECRET_KEY mora biti postavljen"),
+                public_url: public_url.trim_end_matches('/').to_string(),
+                client: http_client,
+            })
+        }
+        _ => {
+            let root = std::env::var("UPLOAD_DIR").unwrap_or_else(|_| "./uploads".to_string());
+            let public_url = public_url.unwrap_or_else(|| "http://localhost:3000/api/uploads".to_string());
+
+            Arc::new(LocalStorage {
+                root: PathBuf::from(root),
+                public_url: public_url.trim_end_matches('/').to_string(),
+            })
+        }
+    }
+}
+
+/// Kljuc ne sme da izadje iz root-a (.., apsolutne putanje)
+fn is_safe_key(key: &str) -> bool {
+    !key.is_empty() && Path::new(key).components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+// ---- Lokalni disk ----
+
+pub struct LocalStorage {
+    root: PathBuf,
+    public_url: String,
+}
+
+#[async_trait]
+impl Storage for LocalStorage {
+    async fn put(&self, key: &str, bytes: Vec<u8>, _content_type: &str) -> Result<(), String> {
+        let path = self.local_path(key).ok_or("Neispravan kljuc")?;
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await.map_err(|e| e.to_string())?;
+        }
+        tokio::fs::write(&path, bytes).await.map_err(|e| e.to_string())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        let path = self.local_path(key).ok_or("Neispravan kljuc")?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    fn url(&self, key: &str) -> String {
+        format!("{}/{}", self.public_url, key)
+    }
+
+    fn local_path(&self, key: &str) -> Option<PathBuf> {
+        is_safe_key(key).then(|| self.root.join(key))
+    }
+}
+
+// ---- S3 (AWS, MinIO, ...) ----
+
+/// Path-style pristup (endpoint/bucket/key) sa AWS Signature V4
+pub struct S3Storage {
+    endpoint: String,
+    bucket: String,
+    region: String,
+    access_key: String,
+    secret_key: String,
+    public_url: String,
+    client: reqwest::Client,
+}
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn hmac(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC prima kljuc bilo koje duzine");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// URI enkodiranje po SigV4 pravilima ('/' ostaje)
+fn uri_encode(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for b in path.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => out.push(b as char),
+            _ => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    out
+}
+
+impl S3Storage {
+    /// Salje potpisan zahtev za jedan objekat
+    async fn send(
+        &self,
+        method: reqwest::Method,
+        key: &str,
+        body: Vec<u8>,
+        content_type: Option<&str>,
+    ) -> Result<reqwest::Response, String> {
+        if !is_safe_key(key) {
+            return Err("Neispravan kljuc".to_string());
+        }
+
+        let path = uri_encode(&format!("/{}/{}", self.bucket, key));
+        let url = reqwest::Url::parse(&format!("{}{}", self.endpoint, path)).map_err(|e| e.to_string())?;
+        let host = match (url.host_str(), url.port()) {
+            (Some(h), Some(p)) => format!("{}:{}", h, p),
+            (Some(h), None) => h.to_string(),
+            _ => return Err("S3_ENDPOINT nema host".to_string()),
+        };
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = hex::encode(Sha256::digest(&body));
+
+        let canonical_headers = format!(
+            "host:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n",
+            host, payload_hash, amz_date
+        );
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "{}\n{}\n\n{}\n{}\n{}",
+            method.as_str(), path, canonical_headers, signed_headers, payload_hash
+        );
+
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let k_date = hmac(format!("AWS4{}", self.secret_key).as_bytes(), &date);
+        let k_region = hmac(&k_date, &self.region);
+        let k_service = hmac(&k_region, "s3");
+        let k_signing = hmac(&k_service, "aws4_request");
+        let signature = hex::encode(hmac(&k_signing, &string_to_sign));
+
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key, scope, signed_headers, signature
+        );
+
+        let mut req = self
+            .client
+            .request(method, url)
+            .header("x-amz-date", amz_date)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("authorization", authorization)
+            .body(body);
+        if let Some(ct) = content_type {
+            req = req.header("content-type", ct);
+        }
+
+        req.send().await.map_err(|e| e.to_string())
+    }
+}
+
+#[async_trait]
+impl Storage for S3Storage {
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<(), String> {
+        let resp = self.send(reqwest::Method::PUT, key, bytes, Some(content_type)).await?;
+        if resp.status().is_success() {
+            Ok(())
+        } else {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            Err(format!("S3 PUT {}: {}", status, text))
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        let resp = self.send(reqwest::Method::DELETE, key, Vec::new(), None).await?;
+        // S3 vraca 204 i za objekat koji ne postoji
+        if resp.status().is_success() || resp.status() == reqwest::StatusCode::NOT_FOUND {
+            Ok(())
+        } else {
+            Err(format!("S3 DELETE {}", resp.status()))
+        }
+    }
+
+    fn url(&self, key: &str) -> String {
+        format!("{}/{}", self.public_url, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Bytes, extract::State, http::{HeaderMap, Method, StatusCode, Uri}, Router};
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    const SECRET: &str = "test-secret";
+
+    type Objects = Arc<Mutex<HashMap<String, (String, Vec<u8>)>>>;
+
+    /// Lokalna zamena za S3: proverava potpis i cuva objekte u memoriji
+    async fn stand_in(State(objects): State<Objects>, method: Method, uri: Uri, headers: HeaderMap, body: Bytes) -> StatusCode {
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok()).unwrap_or_default().to_string();
+        let path = uri.path().to_string();
+
+        let payload_hash = header("x-amz-content-sha256");
+        if payload_hash != hex::encode(Sha256::digest(&body)) {
+            return StatusCode::BAD_REQUEST;
+        }
+        let amz_date = header("x-amz-date");
+        let canonical_request = format!(
+            "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\nhost;x-amz-content-sha256;x-amz-date\n{}",
+            method, path, header("host"), payload_hash, amz_date, payload_hash
+        );
+        let scope = format!("{}/us-east-1/s3/aws4_request", &amz_date[..8]);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+        let mut key = hmac(format!("AWS4{}", SECRET).as_bytes(), &amz_date[..8]);
+        for part in ["us-east-1", "s3", "aws4_request"] {
+            key = hmac(&key, part);
+        }
+        let expected = format!(
+            "AWS4-HMAC-SHA256 Credential=test-key/{}, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature={}",
+            scope,
+            hex::encode(hmac(&key, &string_to_sign))
+        );
+        if header("authorization") != expected {
+            return StatusCode::FORBIDDEN;
+        }
+
+        let mut objects = objects.lock().unwrap();
+        match method {
+            Method::PUT if path.contains("fail") => StatusCode::INTERNAL_SERVER_ERROR,
+            Method::PUT => {
+                objects.insert(path, (header("content-type"), body.to_vec()));
+                StatusCode::OK
+            }
+            Method::DELETE if objects.remove(&path).is_some() => StatusCode::NO_CONTENT,
+            Method::DELETE => StatusCode::NOT_FOUND,
+            _ => StatusCode::METHOD_NOT_ALLOWED,
+        }
+    }
+
+    async fn s3_with_stand_in() -> (S3Storage, Objects) {
+        let objects = Objects::default();
+        let app = Router::new().fallback(stand_in).with_state(objects.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let storage = S3Storage {
+            endpoint: endpoint.clone(),
+            bucket: "event-images".to_string(),
+            region: "us-east-1".to_string(),
+            access_key: "test-key".to_string(),
+            secret_key: SECRET.to_string(),
+            public_url: format!("{}/event-images", endpoint),
+            client: reqwest::Client::new(),
+        };
+        (storage, objects)
+    }
+
+    #[tokio::test]
+    async fn s3_put_and_delete_are_signed() {
+        let (storage, objects) = s3_with_stand_in().await;
+        let key = "events/abc/slika 1.jpg";
+
+        storage.put(key, b"jpeg".to_vec(), "image/jpeg").await.unwrap();
+        let stored = objects.lock().unwrap().get("/event-images/events/abc/slika%201.jpg").cloned();
+        assert_eq!(stored, Some(("image/jpeg".to_string(), b"jpeg".to_vec())));
+
+        storage.delete(key).await.unwrap();
+        assert!(objects.lock().unwrap().is_empty());
+        // objekat koji ne postoji nije greska
+        storage.delete(key).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn s3_reports_errors_and_rejects_unsafe_keys() {
+        let (storage, objects) = s3_with_stand_in().await;
+
+        let err = storage.put("events/fail.jpg", b"x".to_vec(), "image/jpeg").await.unwrap_err();
+        assert!(err.starts_with("S3 PUT 500"), "{}", err);
+        assert_eq!(storage.put("../x.jpg", Vec::new(), "image/jpeg").await.unwrap_err(), "Neispravan kljuc");
+        assert!(objects.lock().unwrap().is_empty());
+
+        assert_eq!(storage.url("events/a.jpg"), format!("{}/event-images/events/a.jpg", storage.endpoint));
+    }
+}
@@ -0,0 +1,300 @@
+// Dogadjaji van aplikacije - Atom i RSS feed, schema.org JSON-LD i HTML stranica sa Open Graph tagovima
+
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDateTime, TimeZone};
+use chrono_tz::Tz;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::ical::{DEFAULT_DURATION_HOURS, TIMEZONE};
+use crate::markdown::plain_text;
+use crate::models::{Event, TicketType};
+
+/// Ime sajta u feed-ovima i Open Graph tagovima
+const SITE_NAME: &str = "Event Management";
+/// Duzina opisa u feed-u i og:description
+const SUMMARY_CHARS: usize = 300;
+
+/// Escape za XML i HTML (tekst i vrednosti atributa)
+pub fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    let s = s.trim();
+    match s.char_indices().nth(max) {
+        Some((i, _)) => format!("{}...", s[..i].trim_end()),
+        None => s.to_string(),
+    }
+}
+
+/// Lokalno vreme iz baze -> "2030-05-01T19:00:00+02:00"
+pub fn local_rfc3339(dt: &NaiveDateTime) -> String {
+    let tz: Tz = TIMEZONE.parse().expect("validna vremenska zona");
+    match tz.from_local_datetime(dt).earliest() {
+        Some(local) => local.to_rfc3339(),
+        // sat koji ne postoji (prelazak na letnje vreme)
+        None => format!("{}", dt.format("%Y-%m-%dT%H:%M:%S")),
+    }
+}
+
+/// created_at/updated_at su UTC
+fn utc_rfc3339(dt: &NaiveDateTime) -> String {
+    dt.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+fn utc_rfc2822(dt: &NaiveDateTime) -> String {
+    dt.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// Javna stranica dogadjaja (deli se kao link)
+pub fn landing_url(api_url: &str, event: &Event) -> String {
+    format!("{}/events/{}/page", api_url, event.id)
+}
+
+/// Dogadjaj u aplikaciji (isto kao URL u iCalendar-u)
+pub fn app_url_for(app_url: &str, event: &Event) -> String {
+    format!("{}/?event={}", app_url, event.id)
+}
+
+/// Slika za deljenje: veliki thumbnail ako postoji, inace original
+fn share_image(event: &Event) -> Option<&str> {
+    let thumbs = event.image_thumbnails.as_ref();
+    thumbs
+        .and_then(|t| t.get("large").or_else(|| t.get("medium")))
+        .map(String::as_str)
+        .or(event.image_url.as_deref())
+}
+
+/// Kratak opis za feed: kada, gde i pocetak opisa
+fn summary(event: &Event) -> String {
+    format!(
+        "{} - {}. {}",
+        event.date_time.format("%d.%m.%Y. %H:%M"),
+        event.location,
+        truncate(&plain_text(&event.description), SUMMARY_CHARS)
+    )
+}
+
+/// Atom 1.0 feed
+pub fn render_atom(events: &[Event], title: &str, self_url: &str, api_url: &str) -> String {
+    let updated = events
+        .iter()
+        .map(|e| e.updated_at)
+        .max()
+        .unwrap_or_else(|| chrono::Utc::now().naive_utc());
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    out.push_str(&format!("  <title>{}</title>\n", xml_escape(title)));
+    out.push_str(&format!("  <id>{}</id>\n", xml_escape(self_url)));
+    out.push_str(&format!("  <link rel=\"self\" href=\"{}\"/>\n", xml_escape(self_url)));
+    out.push_str(&format!("  <updated>{}</updated>\n", utc_rfc3339(&updated)));
+    out.push_str(&format!("  <author><name>{}</name></author>\n", SITE_NAME));
+    for event in events {
+        out.push_str("  <entry>\n");
+        out.push_str(&format!("    <id>urn:uuid:{}</id>\n", event.id));
+        out.push_str(&format!("    <title>{}</title>\n", xml_escape(&event.title)));
+        out.push_str(&format!("    <link rel=\"alternate\" href=\"{}\"/>\n", xml_escape(&landing_url(api_url, event))));
+        out.push_str(&format!("    <published>{}</published>\n", utc_rfc3339(&event.created_at)));
+        out.push_str(&format!("    <updated>{}</updated>\n", utc_rfc3339(&event.updated_at)));
+        out.push_str(&format!("    <category term=\"{}\"/>\n", xml_escape(&event.category)));
+        for tag in &event.tags {
+            out.push_str(&format!("    <category term=\"{}\"/>\n", xml_escape(tag)));
+        }
+        out.push_str(&format!("    <summary>{}</summary>\n", xml_escape(&summary(event))));
+        out.push_str("  </entry>\n");
+    }
+    out.push_str("</feed>\n");
+    out
+}
+
+/// RSS 2.0 feed
+pub fn render_rss(events: &[Event], title: &str, self_url: &str, api_url: &str, app_url: &str) -> String {
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n<channel>\n",
+    );
+    out.push_str(&format!("  <title>{}</title>\n", xml_escape(title)));
+    out.push_str(&format!("  <link>{}</link>\n", xml_escape(app_url)));
+    out.push_str(&format!("  <description>{} - {}</description>\n", SITE_NAME, xml_escape(title)));
+    out.push_str(&format!("  <atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n", xml_escape(self_url)));
+    // klijenti osvezavaju na sat, kao i iCalendar feed
+    out.push_str("  <ttl>60</ttl>\n");
+    for event in events {
+        let link = xml_escape(&landing_url(api_url, event));
+        out.push_str("  <item>\n");
+        out.push_str(&format!("    <title>{}</title>\n", xml_escape(&event.title)));
+        out.push_str(&format!("    <link>{}</link>\n", link));
+        out.push_str(&format!("    <guid isPermaLink=\"false\">{}</guid>\n", event.id));
+        out.push_str(&format!("    <pubDate>{}</pubDate>\n", utc_rfc2822(&event.created_at)));
+        out.push_str(&format!("    <category>{}</category>\n", xml_escape(&event.category)));
+        out.push_str(&format!("    <description>{}</description>\n", xml_escape(&summary(event))));
+        out.push_str("  </item>\n");
+    }
+    out.push_str("</channel>\n</rss>\n");
+    out
+}
+
+/// schema.org Event. `remaining` = slobodna mesta (None ako registration-service nije dostupan).
+pub fn json_ld(event: &Event, ticket_types: &[TicketType], remaining: Option<i64>, api_url: &str) -> Value {
+    let url = landing_url(api_url, event);
+    let end = event.date_time + Duration::hours(DEFAULT_DURATION_HOURS);
+    let availability = match remaining {
+        Some(0) => "https://schema.org/SoldOut",
+        _ => "https://schema.org/InStock",
+    };
+
+    let mut place = json!({ "@type": "Place", "name": event.location, "address": event.location });
+    if let (Some(lat), Some(lng)) = (event.latitude, event.longitude) {
+        place["geo"] = json!({ "@type": "GeoCoordinates", "latitude": lat, "longitude": lng });
+    }
+
+    // bez tipova karata dogadjaj je besplatan
+    let offers: Vec<Value> = if ticket_types.is_empty() {
+        vec![json!({ "@type": "Offer", "price": "0", "priceCurrency": "RSD", "availability": availability, "url": url })]
+    } else {
+        ticket_types
+            .iter()
+            .map(|t| {
+                let mut offer = json!({
+                    "@type": "Offer",
+                    "name": t.name,
+                    "price": format!("{}.{:02}", t.price_cents / 100, t.price_cents % 100),
+                    "priceCurrency": t.currency,
+                    "availability": availability,
+                    "url": url,
+                });
+                if let Some(start) = &t.sales_start {
+                    offer["validFrom"] = json!(local_rfc3339(start));
+                }
+                if let Some(end) = &t.sales_end {
+                    offer["validThrough"] = json!(local_rfc3339(end));
+                }
+                offer
+            })
+            .collect()
+    };
+
+    let mut ld = json!({
+        "@context": "https://schema.org",
+        "@type": "Event",
+        "@id": url,
+        "name": event.title,
+        "description": plain_text(&event.description),
+        "startDate": local_rfc3339(&event.date_time),
+        "endDate": local_rfc3339(&end),
+        "eventStatus": "https://schema.org/EventScheduled",
+        "eventAttendanceMode": "https://schema.org/OfflineEventAttendanceMode",
+        "location": place,
+        "url": url,
+        "offers": offers,
+        "maximumAttendeeCapacity": event.capacity,
+    });
+    if let Some(remaining) = remaining {
+        ld["remainingAttendeeCapacity"] = json!(remaining);
+    }
+    if let Some(image) = share_image(event) {
+        ld["image"] = json!([image]);
+    }
+    if !event.tags.is_empty() {
+        ld["keywords"] = json!(event.tags.join(", "));
+    }
+    if let (Some(avg), true) = (event.rating_avg, event.rating_count > 0) {
+        ld["aggregateRating"] = json!({
+            "@type": "AggregateRating",
+            "ratingValue": (avg * 10.0).round() / 10.0,
+            "ratingCount": event.rating_count,
+            "bestRating": 5,
+            "worstRating": 1,
+        });
+    }
+    ld
+}
+
+/// Slobodna mesta iz broja potvrdjenih prijava
+pub fn remaining_seats(event: &Event, confirmed: &HashMap<Uuid, i64>) -> i64 {
+    (event.capacity as i64 - confirmed.get(&event.id).copied().unwrap_or(0)).max(0)
+}
+
+/// HTML stranica dogadjaja sa Open Graph / Twitter tagovima i JSON-LD-om (za deljenje linka)
+pub fn render_landing(event: &Event, json_ld: &Value, api_url: &str, app_url: &str) -> String {
+    let url = landing_url(api_url, event);
+    let description = truncate(&plain_text(&event.description), SUMMARY_CHARS);
+    let image = share_image(event);
+    // </script> u opisu ne sme da zatvori JSON-LD blok
+    let ld = json_ld.to_string().replace("</", "<\\/");
+
+    let mut meta = vec![
+        ("og:type", "website".to_string()),
+        ("og:site_name", SITE_NAME.to_string()),
+        ("og:title", event.title.clone()),
+        ("og:description", description.clone()),
+        ("og:url", url.clone()),
+        ("twitter:card", if image.is_some() { "summary_large_image" } else { "summary" }.to_string()),
+        ("twitter:title", event.title.clone()),
+        ("twitter:description", description.clone()),
+    ];
+    if let Some(image) = image {
+        meta.push(("og:image", image.to_string()));
+        meta.push(("twitter:image", image.to_string()));
+    }
+    let meta: String = meta
+        .iter()
+        .map(|(name, content)| {
+            let attr = if name.starts_with("og:") { "property" } else { "name" };
+            format!("    <meta {}=\"{}\" content=\"{}\">\n", attr, name, xml_escape(content))
+        })
+        .collect();
+
+    let image_tag = image
+        .map(|src| format!("    <img src=\"{}\" alt=\"{}\">\n", xml_escape(src), xml_escape(&event.title)))
+        .unwrap_or_default();
+
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"sr\">
+<head>
+    <meta charset=\"utf-8\">
+    <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">
+    <title>{title} - {site}</title>
+    <meta name=\"description\" content=\"{description}\">
+    <link rel=\"canonical\" href=\"{url}\">
+{meta}    <script type=\"application/ld+json\">{ld}</script>
+    <style>body{{font-family:sans-serif;max-width:720px;margin:2rem auto;padding:0 1rem;color:#333}}img{{max-width:100%;border-radius:8px}}.btn{{display:inline-block;padding:.6rem 1.2rem;background:#007bff;color:#fff;text-decoration:none;border-radius:4px}}</style>
+</head>
+<body>
+    <h1>{title}</h1>
+{image_tag}    <p><strong>Kada:</strong> {when}</p>
+    <p><strong>Gde:</strong> {location}</p>
+    <p><strong>Kategorija:</strong> {category}</p>
+    <div>{body}</div>
+    <p><a class=\"btn\" href=\"{app_link}\">Otvori u aplikaciji i prijavi se</a></p>
+</body>
+</html>
+",
+        title = xml_escape(&event.title),
+        site = SITE_NAME,
+        description = xml_escape(&description),
+        url = xml_escape(&url),
+        meta = meta,
+        ld = ld,
+        image_tag = image_tag,
+        when = event.date_time.format("%d.%m.%Y. %H:%M"),
+        location = xml_escape(&event.location),
+        category = xml_escape(&event.category),
+        // vec ociscen HTML iz Markdown-a
+        body = event.description_html,
+        app_link = xml_escape(&app_url_for(app_url, event)),
+    )
+}
@@ -0,0 +1,195 @@
+// Kategorije i tagovi - slugovi, provera kategorije i tagovi dogadjaja
+
+use std::collections::{BTreeMap, HashMap};
+
+use sqlx::types::Json;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::models::Event;
+
+/// Najvise tagova po dogadjaju i duzina jednog taga
+const MAX_TAGS: usize = 10;
+const MAX_TAG_LEN: usize = 30;
+
+/// "Muzički Festival!" -> "muzicki-festival"
+pub fn slugify(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.trim().to_lowercase().chars() {
+        match c {
+            'a'..='z' | '0'..='9' => out.push(c),
+            'č' | 'ć' => out.push('c'),
+            'š' => out.push('s'),
+            'ž' => out.push('z'),
+            'đ' => out.push_str("dj"),
+            _ if !out.ends_with('-') && !out.is_empty() => out.push('-'),
+            _ => {}
+        }
+    }
+    out.trim_end_matches('-').to_string()
+}
+
+/// Naziv kategorije kako je upisan u bazi - prihvata naziv (bez obzira na velika/mala slova),
+/// slug ili prevod naziva ("Workshop" -> "Radionica")
+pub async fn resolve_category(db: &PgPool, raw: &str) -> Result<Option<String>, sqlx::Error> {
+    let categories = category_names(db).await?;
+    Ok(match_category(&categories, raw))
+}
+
+/// Kategorija: (slug, naziv, prevodi)
+pub type CategoryNames = (String, String, Json<BTreeMap<String, String>>);
+
+/// Sve kategorije - ucitavaju se jednom i za uvoz vise redova
+pub async fn category_names(db: &PgPool) -> Result<Vec<CategoryNames>, sqlx::Error> {
+    sqlx::query_as::<_, CategoryNames>("SELECT slug, name, translations FROM categories ORDER BY sort_order")
+        .fetch_all(db)
+        .await
+}
+
+/// Isto kao resolve_category, nad vec ucitanom listom
+pub fn match_category(categories: &[CategoryNames], raw: &str) -> Option<String> {
+    let slug = slugify(raw);
+    categories
+        .iter()
+        .find(|(s, name, translations)| {
+            *s == slug || slugify(name) == slug || translations.values().any(|t| slugify(t) == slug)
+        })
+        .map(|(_, name, _)| name.clone())
+}
+
+/// Tagovi kao (slug, naziv), bez praznih i duplikata
+pub fn normalize_tags(tags: &[String]) -> Result<Vec<(String, String)>, &'static str> {
+    let mut out: Vec<(String, String)> = Vec::new();
+    for tag in tags {
+        let name = tag.trim().trim_start_matches('#').trim();
+        if name.is_empty() {
+            continue;
+        }
+        if name.chars().count() > MAX_TAG_LEN {
+            return Err("Tag moze imati najvise 30 karaktera");
+        }
+        let slug = slugify(name);
+        if slug.is_empty() {
+            return Err("Tag mora sadrzati bar jedno slovo ili broj");
+        }
+        if !out.iter().any(|(s, _)| *s == slug) {
+            out.push((slug, name.to_string()));
+        }
+    }
+    if out.len() > MAX_TAGS {
+        return Err("Najvise 10 tagova po dogadjaju");
+    }
+    Ok(out)
+}
+
+/// Zamenjuje tagove dogadjaja i vraca njihove nazive; novi tagovi se prave, postojeci zadrzavaju svoj naziv
+pub async fn set_event_tags(
+    conn: &mut PgConnection,
+    event_id: Uuid,
+    tags: &[(String, String)],
+) -> Result<Vec<String>, sqlx::Error> {
+    let (slugs, names): (Vec<String>, Vec<String>) = tags.iter().cloned().unzip();
+
+    sqlx::query("DELETE FROM event_tags WHERE event_id = $1")
+        .bind(event_id)
+        .execute(&mut *conn)
+        .await?;
+    if slugs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    sqlx::query(
+        "INSERT INTO tags (slug, name) SELECT * FROM UNNEST($1::text[], $2::text[])
+         ON CONFLICT (slug) DO NOTHING",
+    )
+    .bind(&slugs)
+    .bind(&names)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query_scalar::<_, String>(
+        "WITH ins AS (
+            INSERT INTO event_tags (event_id, tag_id) SELECT $1, id FROM tags WHERE slug = ANY($2)
+            RETURNING tag_id
+         )
+         SELECT t.name FROM ins JOIN tags t ON t.id = ins.tag_id ORDER BY t.name",
+    )
+    .bind(event_id)
+    .bind(&slugs)
+    .fetch_all(&mut *conn)
+    .await
+}
+
+/// Puni `tags` za listu dogadjaja jednim upitom
+pub async fn attach_tags(db: &PgPool, events: &mut [Event]) -> Result<(), sqlx::Error> {
+    if events.is_empty() {
+        return Ok(());
+    }
+    let ids: Vec<Uuid> = events.iter().map(|e| e.id).collect();
+
+    let rows = sqlx::query_as::<_, (Uuid, String)>(
+        "SELECT et.event_id, t.name FROM event_tags et JOIN tags t ON t.id = et.tag_id
+         WHERE et.event_id = ANY($1) ORDER BY t.name",
+    )
+    .bind(&ids)
+    .fetch_all(db)
+    .await?;
+
+    let mut by_event: HashMap<Uuid, Vec<String>> = HashMap::new();
+    for (event_id, name) in rows {
+        by_event.entry(event_id).or_default().push(name);
+    }
+    for event in events.iter_mut() {
+        event.tags = by_event.remove(&event.id).unwrap_or_default();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn category(slug: &str, name: &str, translations: &[(&str, &str)]) -> CategoryNames {
+        let translations = translations.iter().map(|(l, t)| (l.to_string(), t.to_string())).collect();
+        (slug.to_string(), name.to_string(), Json(translations))
+    }
+
+    #[test]
+    fn slugify_strips_diacritics_and_punctuation() {
+        assert_eq!(slugify("Muzički Festival!"), "muzicki-festival");
+        assert_eq!(slugify("  Đurđevdan -- žurka  "), "djurdjevdan-zurka");
+        assert_eq!(slugify("C++ & Rust 2027"), "c-rust-2027");
+        assert_eq!(slugify("!!!"), "");
+    }
+
+    #[test]
+    fn match_category_by_name_slug_or_translation() {
+        let categories = vec![
+            category("radionica", "Radionica", &[("en", "Workshop")]),
+            category("muzika", "Muzika", &[]),
+        ];
+        assert_eq!(match_category(&categories, "radionica").as_deref(), Some("Radionica"));
+        assert_eq!(match_category(&categories, "RADIONICA").as_deref(), Some("Radionica"));
+        assert_eq!(match_category(&categories, "workshop").as_deref(), Some("Radionica"));
+        assert_eq!(match_category(&categories, "Muzika").as_deref(), Some("Muzika"));
+        assert_eq!(match_category(&categories, "Sport"), None);
+    }
+
+    #[test]
+    fn normalize_tags_dedupes_by_slug() {
+        let tags = ["#Rust", "rust", " Web razvoj ", "", "Šah"].map(String::from);
+        assert_eq!(
+            normalize_tags(&tags).unwrap(),
+            vec![
+                ("rust".to_string(), "Rust".to_string()),
+                ("web-razvoj".to_string(), "Web razvoj".to_string()),
+                ("sah".to_string(), "Šah".to_string()),
+            ]
+        );
+
+        assert!(normalize_tags(&["x".repeat(31)]).is_err());
+        assert!(normalize_tags(&["???".to_string()]).is_err());
+        let many: Vec<String> = (0..11).map(|i| format!("tag{}", i)).collect();
+        assert!(normalize_tags(&many).is_err());
+    }
+}
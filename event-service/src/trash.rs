@@ -0,0 +1,34 @@
+// Kanta - obrisani dogadjaji se trajno brisu tek posle isteka roka
+
+use uuid::Uuid;
+
+use crate::handlers::remove_event;
+use crate::AppState;
+
+/// Pozadinski posao - jednom na sat trajno brise dogadjaje kojima je istekao rok u kanti
+pub async fn run_purge(state: AppState) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+
+    loop {
+        interval.tick().await;
+
+        let expired = sqlx::query_scalar::<_, Uuid>(
+            "SELECT id FROM events
+             WHERE deleted_at IS NOT NULL AND deleted_at < NOW() - $1 * INTERVAL '1 day'",
+        )
+        .bind(state.trash_retention_days as f64)
+        .fetch_all(&state.db)
+        .await;
+
+        match expired {
+            Ok(ids) if ids.is_empty() => {}
+            Ok(ids) => {
+                for id in &ids {
+                    remove_event(&state, *id).await;
+                }
+                tracing::info!("Kanta: trajno obrisano {} dogadjaja", ids.len());
+            }
+            Err(e) => tracing::error!("Ne mogu da ucitam dogadjaje iz kante: {}", e),
+        }
+    }
+}
@@ -1,5 +1,7 @@
 
 const API = "http://localhost:3000/api";
+// jezik osnovnih polja dogadjaja (DEFAULT_LOCALE na event servisu)
+const DEFAULT_LANG = "sr";
 
 // --- State ---
 let token = localStorage.getItem("token") || null;
@@ -10,6 +12,8 @@ document.addEventListener("DOMContentLoaded", () => {
     if (token) {
         fetchCurrentUser();
     }
+    document.getElementById("rss-link").href = API + "/events/feed.rss";
+    loadCategories();
     loadEvents();
     showSection("events");
 });
@@ -92,8 +96,11 @@ function updateNavbar() {
     toggle("nav-login", !isLoggedIn);
     toggle("nav-logout", isLoggedIn);
     toggle("nav-my-reg", isLoggedIn && !isAdmin);
+    toggle("nav-profile", isLoggedIn);
     toggle("nav-user", isLoggedIn);
     toggle("create-event-box", isAdmin);
+    toggle("list-mode", isLoggedIn);
+    if (isAdmin) loadTemplates();
 
     if (isLoggedIn) {
         document.getElementById("nav-user").textContent = currentUser.email + " (" + currentUser.role + ")";
@@ -102,19 +109,146 @@ function updateNavbar() {
 
 // EVENTS
 
+// aktivan filter po tagu (klik na tag u kartici)
+let tagFilter = "";
+// otvorena kolekcija (slug) - lista prikazuje samo njene dogadjaje, po redosledu kolekcije
+let collectionFilter = "";
+// filteri iz faceta (grad, datum, cena, slobodna mesta)
+let facetFilters = {};
+
+const DATE_LABELS = {
+    today: "Danas", tomorrow: "Sutra", weekend: "Ovaj vikend", week: "Narednih 7 dana",
+    month: "Ovaj mesec", later: "Kasnije", past: "Prosli",
+};
+
+function toggleFacet(name, value) {
+    if (facetFilters[name] === value) delete facetFilters[name];
+    else facetFilters[name] = value;
+    loadEvents();
+}
+
+function renderFacets(facets) {
+    const chip = (name, value, label, count) => count > 0 || facetFilters[name] === value
+        ? `<span class="badge badge-tag${facetFilters[name] === value ? " active" : ""}" onclick="toggleFacet('${name}', decodeURIComponent('${encodeURIComponent(value).replace(/'/g, "%27")}'))">${esc(label)} (${count})</span>`
+        : "";
+    const dates = facets.dates.filter(d => d.value !== "past").map(d => chip("when", d.value, DATE_LABELS[d.value], d.count)).join("");
+    const cities = facets.cities.map(c => chip("city", c.value, c.value, c.count)).join("");
+    const price = chip("price", "free", "Besplatno", facets.price.free) + chip("price", "paid", "Placa se", facets.price.paid);
+    const seats = facets.availability ? chip("available", "true", "Ima mesta", facets.availability.available) : "";
+    document.getElementById("facets").innerHTML = dates + cities + price + seats;
+}
+
+// Preporuke iznad liste - klik trazi dogadjaj po nazivu
+async function loadRecommendations() {
+    const res = await apiGet("/events/recommended?limit=5");
+    const box = document.getElementById("recommendations");
+    if (!box) return;
+    box.innerHTML = res.success && res.data.length
+        ? "<strong>Preporuceno:</strong> " + res.data.map(e =>
+            `<span class="badge badge-tag" title="${e.reasons.join(", ")}" onclick="showEventByTitle(decodeURIComponent('${encodeURIComponent(e.title).replace(/'/g, "%27")}'))">${esc(e.title)}</span>`
+        ).join("")
+        : "";
+}
+
+// Izdvojeni dogadjaji i aktivne kolekcije (bira ih admin)
+async function loadFeatured() {
+    const [featured, collections] = await Promise.all([
+        apiGet("/events?featured=true&when=upcoming"),
+        apiGet("/collections"),
+    ]);
+    const box = document.getElementById("featured");
+    if (!box) return;
+    const events = featured.success ? featured.data.slice(0, 5) : [];
+    const lists = collections.success ? collections.data.filter(c => c.event_count > 0) : [];
+    box.innerHTML = (events.length
+        ? "<strong>Izdvojeno:</strong> " + events.map(e =>
+            `<span class="badge badge-tag" onclick="showEventByTitle(decodeURIComponent('${encodeURIComponent(e.title).replace(/'/g, "%27")}'))">${esc(e.title)}</span>`
+        ).join("")
+        : "") + (lists.length
+        ? " <strong>Kolekcije:</strong> " + lists.map(c =>
+            `<span class="badge badge-tag" onclick="showCollection('${esc(c.slug)}', decodeURIComponent('${encodeURIComponent(c.title).replace(/'/g, "%27")}'))">${esc(c.title)} (${c.event_count})</span>`
+        ).join("")
+        : "");
+}
+
+function showCollection(slug, title = "") {
+    collectionFilter = slug;
+    const chip = document.getElementById("collection-filter");
+    chip.textContent = title + " \u00d7";
+    chip.classList.toggle("hidden", !slug);
+    loadEvents();
+}
+
+function showEventByTitle(title) {
+    document.getElementById("search-input").value = title;
+    loadEvents();
+}
+
+// Kategorije za filter i forme (filter koristi slug, forme naziv)
+async function loadCategories() {
+    const res = await apiGet("/categories");
+    if (!res.success) return;
+    const options = res.data.map(c => `<option value="${esc(c.name)}">${esc(c.label)}</option>`).join("");
+    document.getElementById("category-filter").innerHTML = `<option value="">Sve kategorije</option>` +
+        res.data.map(c => `<option value="${esc(c.slug)}">${esc(c.label)} (${c.event_count})</option>`).join("");
+    document.getElementById("evt-category").innerHTML = options;
+    document.getElementById("edit-evt-category").innerHTML = options;
+}
+
+function parseTags(value) {
+    return value.split(",").map(t => t.trim()).filter(t => t);
+}
+
+function filterByTag(tag) {
+    tagFilter = tag;
+    const chip = document.getElementById("tag-filter");
+    chip.textContent = "#" + tag + " \u00d7";
+    chip.classList.toggle("hidden", !tag);
+    loadEvents();
+}
+
 async function loadEvents() {
+    loadFeatured();
+    loadRecommendations();
     const search = document.getElementById("search-input")?.value || "";
     const category = document.getElementById("category-filter")?.value || "";
+    const mode = (token && document.getElementById("list-mode")?.value) || "";
 
     let url = "/events?";
     if (search) url += "search=" + encodeURIComponent(search) + "&";
-    if (category) url += "category=" + encodeURIComponent(category);
+    if (category) url += "category=" + encodeURIComponent(category) + "&";
+    if (tagFilter) url += "tag=" + encodeURIComponent(tagFilter) + "&";
+    for (const [name, value] of Object.entries(facetFilters)) url += name + "=" + encodeURIComponent(value) + "&";
+    if (mode === "following") url += "following=true&";
+    url += "facets=true&";
+    // ulogovani vide i draftove koje organizuju (ili su u osoblju)
+    if (token) url += "drafts=true";
+
+    // sacuvani dogadjaji i kolekcije idu sa posebnog endpoint-a, bez filtera
+    const res = collectionFilter
+        ? await apiGet("/collections/" + encodeURIComponent(collectionFilter))
+        : await apiGet(mode === "bookmarked" ? "/events/bookmarked" : url);
+    if (res.success && collectionFilter) {
+        res.data = res.data.events;
+    } else if (res.success && mode !== "bookmarked") {
+        renderFacets(res.data.facets);
+        res.data = res.data.events;
+    }
 
-    const res = await apiGet(url);
+    // dogadjaj otvoren preko tajnog linka (?link=...) ide na vrh liste
+    const slug = new URLSearchParams(window.location.search).get("link");
+    if (slug && res.success) {
+        const linked = await apiGet("/events/link/" + encodeURIComponent(slug));
+        if (linked.success && !res.data.some(e => e.id === linked.data.id)) res.data.unshift(linked.data);
+    }
 
     // load my registrations
     let myEventIds = [];
+    let bookmarkedIds = [];
     if (token) {
+        const saved = await apiGet("/events/bookmarked");
+        if (saved.success) bookmarkedIds = saved.data.map(e => e.id);
+
         const regRes = await apiGet("/registrations/my");
         if (regRes.success && regRes.data) {
             myEventIds = regRes.data
@@ -128,19 +262,32 @@ async function loadEvents() {
     if (res.success && res.data.length > 0) {
         container.innerHTML = res.data.map(evt => {
             const alreadyRegistered = myEventIds.includes(evt.id);
+            // description_html je vec ociscen na serveru (Markdown -> dozvoljeni tagovi)
             return `
             <div class="card">
-                ${evt.image_url ? `<div class="event-image-wrap"><img src="${evt.image_url}" alt="${esc(evt.title)}" class="event-image"></div>` : ""}
+                ${evt.image_url ? `<div class="event-image-wrap"><img src="${(evt.image_thumbnails && evt.image_thumbnails.medium) || evt.image_url}" loading="lazy" alt="${esc(evt.title)}" class="event-image"></div>` : ""}
                 <span class="badge">${esc(evt.category)}</span>
+                ${evt.status === "draft" ? `<span class="badge badge-draft">Draft</span>` : ""}
+                ${evt.featured ? `<span class="badge">Izdvojeno</span>` : ""}
+                ${evt.visibility === "private" ? `<span class="badge badge-private">Privatno</span>` : evt.visibility === "unlisted" ? `<span class="badge badge-private">Samo preko linka</span>` : ""}
                 <h3>${esc(evt.title)}</h3>
-                <p>${esc(evt.description)}</p>
+                <div class="description">${evt.description_html}</div>
+                ${(evt.tags || []).length ? `<p class="tags">${evt.tags.map(t => `<span class="badge badge-tag" onclick="filterByTag(decodeURIComponent('${encodeURIComponent(t).replace(/'/g, "%27")}'))">#${esc(t)}</span>`).join("")}</p>` : ""}
                 <p><strong>Lokacija:</strong> ${esc(evt.location)}</p>
                 <p><strong>Datum:</strong> ${formatDate(evt.date_time)}</p>
                 <div class="meta">
                     <span class="capacity">Kapacitet: ${evt.capacity}</span>
-                    ${token && !alreadyRegistered && currentUser && currentUser.role !== "Admin" ? `<button class="btn btn-primary btn-small" onclick="registerForEvent('${evt.id}')">Prijavi se</button>` : ""}
+                    ${evt.rating_count > 0 ? `<span class="rating" title="${evt.rating_count} ocena">★ ${evt.rating_avg.toFixed(1)} (${evt.rating_count})</span>` : ""}
+                    ${new Date(evt.date_time) < new Date() ? `<button class="btn btn-small" onclick="showReviews('${evt.id}')">Utisci</button>` : ""}
+                    <button class="btn btn-small" onclick="showQuestions('${evt.id}')">Pitanja</button>
+                    ${token ? `<button class="btn btn-small" onclick="toggleBookmark('${evt.id}', ${bookmarkedIds.includes(evt.id)})">${bookmarkedIds.includes(evt.id) ? "Sacuvano ✓" : "Sacuvaj"}</button>` : ""}
+                    ${token && currentUser && currentUser.user_id !== evt.organizer_id ? `<button class="btn btn-small" onclick="followOrganizer('${evt.organizer_id}')">Prati organizatora</button>` : ""}
+                    <a class="btn btn-small" href="${API}/events/${evt.id}.ics">Kalendar</a>
+                    ${evt.status === "published" && evt.visibility === "public" ? `<a class="btn btn-small" href="${API}/events/${evt.id}/page" target="_blank">Podeli</a>` : ""}
+                    ${token && !alreadyRegistered && currentUser && currentUser.role !== "Admin" ? `<button class="btn btn-primary btn-small" onclick="registerForEvent('${evt.id}', '${evt.visibility}')">Prijavi se</button>` : ""}
                     ${token && alreadyRegistered && currentUser && currentUser.role !== "Admin" ? `<span class="badge" style="background:#28a745;color:#fff;">Prijavljeni ste</span>` : ""}
-                    ${currentUser && currentUser.role === "Admin" ? `<button class="btn btn-small" onclick="openEditEvent('${evt.id}')" style="margin-right:4px;">Izmeni</button><button class="btn btn-danger btn-small" onclick="deleteEvent('${evt.id}')">Obrisi</button>` : ""}
+                    ${currentUser && currentUser.role === "Admin" ? `<button class="btn btn-small" onclick="openEditEvent('${evt.id}')" style="margin-right:4px;">Izmeni</button><button class="btn btn-small" onclick="cloneEvent('${evt.id}')" style="margin-right:4px;">Kloniraj</button><button class="btn btn-small" onclick="addStaff('${evt.id}')" style="margin-right:4px;">Osoblje</button><button class="btn btn-small" onclick="manageAccess('${evt.id}')" style="margin-right:4px;">Pristup</button><button class="btn btn-small" onclick="checkIn('${evt.id}')" style="margin-right:4px;">Check-in</button><button class="btn btn-small" onclick="showHistory('${evt.id}')" style="margin-right:4px;">Istorija</button><button class="btn btn-small" onclick="toggleFeatured('${evt.id}', ${!evt.featured})" style="margin-right:4px;">${evt.featured ? "Ukloni iz izdvojenih" : "Izdvoji"}</button><button class="btn btn-danger btn-small" onclick="deleteEvent('${evt.id}')">Obrisi</button>` : ""}
+                    ${currentUser && currentUser.role === "Admin" && evt.status === "draft" ? `<button class="btn btn-primary btn-small" onclick="publishEvent('${evt.id}')">Objavi</button>` : ""}
                 </div>
             </div>
             `;
@@ -151,19 +298,23 @@ async function loadEvents() {
 }
 
 async function openEditEvent(eventId) {
-    const res = await apiGet("/events/" + eventId);
+    // izmena ide na osnovna polja, ne na prevod po jeziku browsera
+    const res = await apiGet("/events/" + eventId + "?lang=" + DEFAULT_LANG);
     if (!res.success || !res.data) {
         toast(res.message || "Greska", "error");
         return;
     }
     const evt = res.data;
     document.getElementById("edit-evt-id").value = evt.id;
+    document.getElementById("edit-evt-version").value = evt.version;
     document.getElementById("edit-evt-title").value = evt.title;
     document.getElementById("edit-evt-description").value = evt.description;
     document.getElementById("edit-evt-location").value = evt.location;
     document.getElementById("edit-evt-datetime").value = (evt.date_time || "").slice(0, 16);
     document.getElementById("edit-evt-capacity").value = evt.capacity;
+    document.getElementById("edit-evt-capacity-strategy").value = "reject";
     document.getElementById("edit-evt-category").value = evt.category || "Ostalo";
+    document.getElementById("edit-evt-tags").value = (evt.tags || []).join(", ");
     document.getElementById("edit-evt-image").value = "";
     document.getElementById("edit-event-modal").classList.remove("hidden");
     document.body.style.overflow = "hidden";
@@ -189,19 +340,24 @@ async function handleUpdateEvent(e) {
         location: document.getElementById("edit-evt-location").value,
         date_time: document.getElementById("edit-evt-datetime").value + ":00",
         capacity: parseInt(document.getElementById("edit-evt-capacity").value),
+        capacity_strategy: document.getElementById("edit-evt-capacity-strategy").value,
         category: document.getElementById("edit-evt-category").value,
+        tags: parseTags(document.getElementById("edit-evt-tags").value),
     };
     const imageInput = document.getElementById("edit-evt-image");
-    if (imageInput && imageInput.files.length > 0) {
-        if (imageInput.files[0].size > 3 * 1024 * 1024) {
-            toast("Slika prevelika (max 3MB)", "error");
-            return;
-        }
-        data.image_url = await readFileAsBase64(imageInput.files[0]);
+    const file = imageInput && imageInput.files.length > 0 ? imageInput.files[0] : null;
+    if (file && file.size > MAX_IMAGE_SIZE) {
+        toast("Slika prevelika (max 4MB)", "error");
+        return;
+    }
+    const version = document.getElementById("edit-evt-version").value;
+    const res = await apiPut("/events/" + id, data, { "If-Match": '"' + version + '"' });
+    if (res.success && file) {
+        const upload = await uploadEventImage(id, file);
+        if (!upload.success) toast(upload.message || "Slika nije sacuvana", "error");
     }
-    const res = await apiPut("/events/" + id, data);
     if (res.success) {
-        toast("Dogadjaj izmenjen!", "success");
+        toast(res.message || "Dogadjaj izmenjen!", "success");
         closeEditEvent();
         loadEvents();
     } else {
@@ -210,7 +366,7 @@ async function handleUpdateEvent(e) {
 }
 
 async function deleteEvent(eventId) {
-    if (!confirm("Da li ste sigurni da zelite da obrisete ovaj dogadjaj?")) return;
+    if (!confirm("Dogadjaj ide u kantu, a prijave se otkazuju. Nastaviti?")) return;
 
     const res = await apiDelete("/events/" + eventId);
     if (res.success) {
@@ -221,17 +377,42 @@ async function deleteEvent(eventId) {
     }
 }
 
+async function toggleFeatured(eventId, featured) {
+    let priority = 0;
+    if (featured) {
+        const input = prompt("Prioritet (veci broj = ranije na pocetnoj):", "0");
+        if (input === null) return;
+        priority = parseInt(input, 10) || 0;
+    }
+    const res = await apiPut("/events/" + eventId + "/featured", { featured, priority });
+    if (res.success) {
+        toast(featured ? "Dogadjaj izdvojen" : "Dogadjaj vise nije izdvojen", "success");
+        loadEvents();
+    } else {
+        toast(res.message, "error");
+    }
+}
+
 function searchEvents() {
     loadEvents();
 }
 
-function readFileAsBase64(file) {
-    return new Promise((resolve, reject) => {
-        const reader = new FileReader();
-        reader.onload = () => resolve(reader.result); 
-        reader.onerror = () => reject(reader.error);
-        reader.readAsDataURL(file);
-    });
+const MAX_IMAGE_SIZE = 4 * 1024 * 1024;
+
+// Slika se salje posebno (multipart), server pravi thumbnail-ove
+async function uploadEventImage(eventId, file) {
+    const form = new FormData();
+    form.append("image", file);
+    try {
+        const res = await fetch(API + "/events/" + eventId + "/image", {
+            method: "POST",
+            headers: token ? { "Authorization": "Bearer " + token } : {},
+            body: form,
+        });
+        return await res.json();
+    } catch {
+        return { success: false, message: "Greska u komunikaciji sa serverom" };
+    }
 }
 
 async function handleCreateEvent(e) {
@@ -244,21 +425,33 @@ async function handleCreateEvent(e) {
         date_time: document.getElementById("evt-datetime").value + ":00",
         capacity: parseInt(document.getElementById("evt-capacity").value),
         category: document.getElementById("evt-category").value,
+        tags: parseTags(document.getElementById("evt-tags").value),
+        status: document.getElementById("evt-draft").checked ? "draft" : "published",
+        visibility: document.getElementById("evt-visibility").value,
     };
+    const accessCode = document.getElementById("evt-access-code").value.trim();
+    if (accessCode) data.access_code = accessCode;
+    // slika iz sablona (ako nije izabrana nova)
+    const templateId = document.getElementById("evt-template").value;
+    if (templateId && templatePrefill && templatePrefill.image_url) {
+        data.image_url = templatePrefill.image_url;
+        data.template_id = templateId;
+    }
 
-    // Ako je korisnik izabrao sliku, ucitaj je kao base64
+    // Slika se uploaduje posle kreiranja dogadjaja
     const imageInput = document.getElementById("evt-image");
-    if (imageInput && imageInput.files.length > 0) {
-        const file = imageInput.files[0];
-        // Ogranicenje: max 3MB
-        if (file.size > 3 * 1024 * 1024) {
-            toast("Slika je prevelika (max 3MB)", "error");
-            return;
-        }
-        data.image_url = await readFileAsBase64(file);
+    const file = imageInput && imageInput.files.length > 0 ? imageInput.files[0] : null;
+    // Ogranicenje: max 4MB
+    if (file && file.size > MAX_IMAGE_SIZE) {
+        toast("Slika je prevelika (max 4MB)", "error");
+        return;
     }
 
     const res = await apiPost("/events", data);
+    if (res.success && file) {
+        const upload = await uploadEventImage(res.data.id, file);
+        if (!upload.success) toast(upload.message || "Slika nije sacuvana", "error");
+    }
     if (res.success) {
         toast("Dogadjaj kreiran!", "success");
         loadEvents();
@@ -267,16 +460,316 @@ async function handleCreateEvent(e) {
         document.getElementById("evt-description").value = "";
         document.getElementById("evt-location").value = "";
         document.getElementById("evt-datetime").value = "";
+        document.getElementById("evt-template").value = "";
+        document.getElementById("evt-draft").checked = false;
+        document.getElementById("evt-access-code").value = "";
+        templatePrefill = null;
         if (imageInput) imageInput.value = "";
     } else {
         toast(res.message, "error");
     }
 }
 
+async function publishEvent(eventId) {
+    const current = await apiGet("/events/" + eventId);
+    if (!current.success) {
+        toast(current.message || "Greska", "error");
+        return;
+    }
+    const res = await apiPut("/events/" + eventId, { status: "published" }, { "If-Match": '"' + current.data.version + '"' });
+    if (res.success) {
+        toast("Dogadjaj objavljen", "success");
+        loadEvents();
+    } else {
+        toast(res.message || "Greska", "error");
+    }
+}
+
+// Kopija na novi datum, sa sesijama i tipovima karata - nastaje kao draft
+async function cloneEvent(eventId) {
+    const date = prompt("Novi datum (YYYY-MM-DD HH:MM):");
+    if (!date) return;
+    const res = await apiPost("/events/" + eventId + "/clone", {
+        date_time: date.trim().replace(" ", "T") + ":00",
+        include_sessions: true,
+        include_ticket_types: true,
+    });
+    if (res.success) {
+        toast("Kopija sacuvana kao draft", "success");
+        loadEvents();
+    } else {
+        toast(res.message || "Greska", "error");
+    }
+}
+
+// Dodaje osobu u osoblje dogadjaja (po email-u)
+async function addStaff(eventId) {
+    const email = prompt("Email osobe:");
+    if (!email) return;
+    const role = prompt("Uloga (co_organizer, editor, checkin, viewer):", "checkin");
+    if (!role) return;
+    const res = await apiPost("/events/" + eventId + "/staff", { email: email.trim(), role: role.trim() });
+    toast(res.message || "Greska", res.success ? "success" : "error");
+}
+
+// Tajni link za unlisted/private dogadjaje i pozivnice po email-u
+async function manageAccess(eventId) {
+    const res = await apiGet("/events/" + eventId + "/access");
+    if (!res.success) {
+        toast(res.message || "Greska", "error");
+        return;
+    }
+    const link = window.location.origin + window.location.pathname + "?link=" + res.data.slug;
+    const invited = res.data.invitations.map(i => i.email).join(", ") || "nema";
+    const emails = prompt(
+        "Link: " + link + "\nPristupni kod: " + (res.data.access_code || "nema") + "\nPozvani: " + invited +
+        "\n\nPozovi jos (email-ovi odvojeni zarezom):"
+    );
+    if (!emails) return;
+    const inv = await apiPost("/events/" + eventId + "/invitations", { emails: emails.split(",") });
+    toast(inv.message || "Greska", inv.success ? "success" : "error");
+}
+
+// Istorija izmena - lista verzija, unosom broja se vraca to stanje
+async function showHistory(eventId) {
+    const res = await apiGet("/events/" + eventId + "/revisions");
+    if (!res.success) {
+        toast(res.message || "Greska", "error");
+        return;
+    }
+    if (res.data.length === 0) {
+        toast("Dogadjaj jos nije menjan", "success");
+        return;
+    }
+    const lines = res.data.map(r =>
+        "v" + r.version + " (" + formatDate(r.created_at) + "): " +
+        (r.changes ? Object.keys(r.changes).join(", ") || "bez promena" : "pocetno stanje")
+    );
+    const version = prompt(lines.join("\n") + "\n\nVrati verziju (broj):");
+    if (!version) return;
+    const restored = await apiPost("/events/" + eventId + "/revisions/" + parseInt(version) + "/restore", {});
+    toast(restored.message || "Greska", restored.success ? "success" : "error");
+    if (restored.success) loadEvents();
+}
+
+async function checkIn(eventId) {
+    const code = prompt("Kod sa ulaznice:");
+    if (!code) return;
+    const res = await apiPost("/events/" + eventId + "/check-in", { ticket_code: code.trim() });
+    toast(res.message || "Greska", res.success ? "success" : "error");
+}
+
+async function toggleBookmark(eventId, saved) {
+    const res = saved
+        ? await apiDelete("/events/" + eventId + "/bookmark")
+        : await apiPost("/events/" + eventId + "/bookmark", {});
+    toast(res.message || "Greska", res.success ? "success" : "error");
+    if (res.success) loadEvents();
+}
+
+async function followOrganizer(organizerId) {
+    const res = await apiPost("/organizers/" + organizerId + "/follow", {});
+    toast(res.message || "Greska", res.success ? "success" : "error");
+}
+
+// Utisci - spisak, a prijavljeni korisnik moze da ostavi ocenu (backend proverava da li je bio na dogadjaju)
+async function showReviews(eventId) {
+    const res = await apiGet("/events/" + eventId + "/reviews");
+    if (!res.success) {
+        toast(res.message || "Greska", "error");
+        return;
+    }
+    const { summary, reviews } = res.data;
+    const lines = reviews.length === 0 ? ["Jos nema utisaka."] : [
+        "Prosek: " + summary.average.toFixed(1) + " (" + summary.count + " ocena)",
+        "",
+        ...reviews.map(r =>
+            "★".repeat(r.rating) + " " + r.author_name + (r.hidden ? " [sakriveno]" : "") + (r.body ? ": " + r.body : "") +
+            (r.reply ? "\n   Organizator: " + r.reply : "")
+        ),
+    ];
+    if (!token || (currentUser && currentUser.role === "Admin")) {
+        alert(lines.join("\n"));
+        return;
+    }
+    const rating = prompt(lines.join("\n") + "\n\nVasa ocena (1-5):");
+    if (!rating) return;
+    const body = prompt("Utisak (opciono):") || "";
+    const created = await apiPost("/events/" + eventId + "/reviews", { rating: parseInt(rating), body });
+    toast(created.message || "Greska", created.success ? "success" : "error");
+    if (created.success) loadEvents();
+}
+
+// Pitanja - spisak sa odgovorima; unos broja glasa za pitanje, a tekst postavlja novo pitanje
+async function showQuestions(eventId) {
+    const res = await apiGet("/events/" + eventId + "/questions");
+    if (!res.success) {
+        toast(res.message || "Greska", "error");
+        return;
+    }
+    const lines = res.data.length === 0 ? ["Jos nema pitanja."] : res.data.map((q, i) =>
+        (i + 1) + ". " + (q.pinned ? "[zakaceno] " : "") +
+        (q.deleted_at ? "[autor je obrisao pitanje]" : q.author_name + ": " + q.body + " (▲ " + q.upvotes + ")") +
+        q.replies.map(r => "\n   " + (r.from_staff ? "Organizator" : r.author_name) + ": " + r.body).join("")
+    );
+    if (!token) {
+        alert(lines.join("\n"));
+        return;
+    }
+    const input = prompt(lines.join("\n") + "\n\nBroj pitanja za glas ili tekst novog pitanja:");
+    if (!input || !input.trim()) return;
+    const picked = res.data[parseInt(input) - 1];
+    const result = /^\d+$/.test(input.trim()) && picked
+        ? await apiPost("/events/" + eventId + "/questions/" + picked.id + "/vote", {})
+        : await apiPost("/events/" + eventId + "/questions", { body: input });
+    toast(result.message || "Greska", result.success ? "success" : "error");
+}
+
+// SABLONI
+
+let templatePrefill = null;
+
+async function loadTemplates() {
+    const select = document.getElementById("evt-template");
+    const res = await apiGet("/templates");
+    if (!select || !res.success) return;
+    select.innerHTML = `<option value="">-- Bez sablona --</option>` +
+        res.data.map(t => `<option value="${t.id}">${esc(t.name)}</option>`).join("");
+}
+
+async function applyTemplate(templateId) {
+    templatePrefill = null;
+    if (!templateId) return;
+    const res = await apiGet("/templates/" + templateId + "/prefill");
+    if (!res.success) {
+        toast(res.message || "Greska", "error");
+        return;
+    }
+    templatePrefill = res.data;
+    const fields = { title: "evt-title", description: "evt-description", location: "evt-location", capacity: "evt-capacity", category: "evt-category" };
+    for (const [key, id] of Object.entries(fields)) {
+        if (templatePrefill[key] != null) document.getElementById(id).value = templatePrefill[key];
+    }
+}
+
+async function saveTemplate() {
+    const name = prompt("Naziv sablona:");
+    if (!name) return;
+    const capacity = parseInt(document.getElementById("evt-capacity").value);
+    const res = await apiPost("/templates", {
+        name,
+        title: document.getElementById("evt-title").value || null,
+        description: document.getElementById("evt-description").value || null,
+        location: document.getElementById("evt-location").value || null,
+        capacity: isNaN(capacity) ? null : capacity,
+        category: document.getElementById("evt-category").value,
+    });
+    if (res.success) {
+        toast("Sablon sacuvan", "success");
+        loadTemplates();
+    } else {
+        toast(res.message || "Greska", "error");
+    }
+}
+
+// Uvoz: dry run prikazuje greske po redovima, pravi uvoz upisuje samo ispravne redove
+async function importEvents(dryRun) {
+    const input = document.getElementById("import-file");
+    if (!input.files.length) {
+        toast("Izaberite fajl", "error");
+        return;
+    }
+    const file = input.files[0];
+    const format = file.name.toLowerCase().endsWith(".ics") ? "ics" : "csv";
+    let res;
+    try {
+        const r = await fetch(API + "/events/import?format=" + format + "&dry_run=" + dryRun, {
+            method: "POST",
+            headers: {
+                "Content-Type": format === "ics" ? "text/calendar" : "text/csv",
+                ...(token ? { "Authorization": "Bearer " + token } : {}),
+            },
+            body: await file.text(),
+        });
+        res = await r.json();
+    } catch {
+        res = { success: false, message: "Greska u komunikaciji sa serverom" };
+    }
+
+    const box = document.getElementById("import-result");
+    if (!res.success) {
+        box.innerHTML = "";
+        toast(res.message || "Greska", "error");
+        return;
+    }
+    const d = res.data;
+    box.innerHTML = `
+        <p>Redova: ${d.total}, ispravnih: ${d.valid}${d.dry_run ? "" : ", uvezeno: " + d.imported}</p>
+        ${d.errors.map(e => `<p class="import-error">Red ${e.row}: ${esc(e.message)}</p>`).join("")}
+    `;
+    if (!d.dry_run) {
+        toast(res.message, "success");
+        loadEvents();
+    }
+}
+
+// KANTA - obrisani dogadjaji se mogu vratiti dok ne isteknu
+
+async function loadTrash() {
+    const res = await apiGet("/events/trash");
+    const box = document.getElementById("trash-list");
+    if (!res.success) {
+        box.innerHTML = "";
+        toast(res.message || "Greska", "error");
+        return;
+    }
+    box.innerHTML = res.data.length === 0 ? "<p>Kanta je prazna.</p>" : res.data.map(evt => `
+        <p>${esc(evt.title)} (${formatDate(evt.date_time)}) - obrisan ${formatDate(evt.deleted_at)}, trajno brisanje ${formatDate(evt.purge_at)}
+        <button class="btn btn-small" onclick="restoreEvent('${evt.id}')">Vrati</button></p>
+    `).join("");
+}
+
+async function restoreEvent(eventId) {
+    const res = await apiPost("/events/" + eventId + "/restore", {});
+    toast(res.message || "Greska", res.success ? "success" : "error");
+    if (res.success) {
+        loadTrash();
+        loadEvents();
+    }
+}
+
 // REGISTRATIONS
 
-async function registerForEvent(eventId) {
-    const res = await apiPost("/registrations", { event_id: eventId });
+// Link za pretplatu (Google/Apple/Outlook kalendar) - pravi se jednom, posle se samo prikazuje
+async function subscribeCalendar() {
+    let res = await apiGet("/registrations/calendar-token");
+    if (!res.success) res = await apiPost("/registrations/calendar-token", {});
+    if (!res.success) {
+        toast(res.message || "Greska", "error");
+        return;
+    }
+    const input = document.getElementById("calendar-url");
+    input.value = res.data.url;
+    input.classList.remove("hidden");
+    input.select();
+    toast("Kopirajte link u svoju kalendar aplikaciju", "success");
+}
+
+// odakle je korisnik dosao (?ref= ili utm_source na linku) - za analitiku organizatora
+function registrationSource() {
+    const params = new URLSearchParams(window.location.search);
+    return params.get("ref") || params.get("utm_source") || undefined;
+}
+
+async function registerForEvent(eventId, visibility) {
+    const source = registrationSource();
+    let res = await apiPost("/registrations", { event_id: eventId, source });
+    // privatni dogadjaj bez pozivnice - trazimo pristupni kod
+    if (!res.success && visibility === "private") {
+        const code = prompt("Privatan dogadjaj - unesite pristupni kod:");
+        if (!code) return;
+        res = await apiPost("/registrations", { event_id: eventId, access_code: code.trim(), source });
+    }
     if (res.success) {
         toast("Uspesno prijavljeni! Kod karte: " + res.data.ticket_code, "success");
         loadEvents();
@@ -302,14 +795,15 @@ async function loadMyRegistrations() {
             const eventLabel = eventRemoved ? "Dogadjaj uklonjen" : esc(eventNames[reg.event_id]);
             return `
             <div class="card ${eventRemoved ? "card-event-removed" : ""}">
-                <span class="badge">${reg.status === "confirmed" ? "Potvrdjeno" : "Otkazano"}</span>
+                <span class="badge">${reg.status === "confirmed" ? "Potvrdjeno" : reg.status === "waitlisted" ? "Lista cekanja" : "Otkazano"}</span>
                 ${eventRemoved ? '<span class="badge badge-removed">Dogadjaj uklonjen</span>' : ""}
                 <p><strong>Dogadjaj:</strong> ${eventLabel}</p>
                 <p><strong>Karta:</strong> <span class="ticket-code">${esc(reg.ticket_code)}</span></p>
                 <p><strong>Datum prijave:</strong> ${formatDate(reg.created_at)}</p>
+                ${reg.status_note ? `<p class="status-note">${esc(reg.status_note)}</p>` : ""}
                 <div class="meta">
                     <button class="btn btn-secondary btn-small" onclick="downloadQR('${reg.id}')">QR Kod</button>
-                    ${reg.status === "confirmed" && !eventRemoved ? `<button class="btn btn-danger btn-small" onclick="cancelRegistration('${reg.id}')">Otkazi</button>` : ""}
+                    ${reg.status !== "cancelled" && !eventRemoved ? `<button class="btn btn-danger btn-small" onclick="cancelRegistration('${reg.id}')">Otkazi</button>` : ""}
                 </div>
             </div>
         `;
@@ -346,6 +840,37 @@ async function cancelRegistration(id) {
     }
 }
 
+// PROFIL
+
+async function loadProfile() {
+    const res = await apiGet("/auth/profile");
+    if (!res.success) return toast(res.message, "error");
+    const p = res.data;
+    document.getElementById("profile-full-name").value = p.full_name || "";
+    document.getElementById("profile-dob").value = p.date_of_birth || "";
+    document.getElementById("profile-organization").value = p.organization || "";
+    document.getElementById("profile-phone").value = p.phone || "";
+    document.getElementById("profile-attributes").value =
+        Object.entries(p.attributes || {}).map(([k, v]) => k + "=" + v).join("\n");
+}
+
+async function saveProfile(e) {
+    e.preventDefault();
+    const attributes = {};
+    document.getElementById("profile-attributes").value.split("\n").forEach(line => {
+        const i = line.indexOf("=");
+        if (i > 0) attributes[line.slice(0, i).trim()] = line.slice(i + 1).trim();
+    });
+    const res = await apiPut("/auth/profile", {
+        full_name: document.getElementById("profile-full-name").value,
+        date_of_birth: document.getElementById("profile-dob").value || null,
+        organization: document.getElementById("profile-organization").value,
+        phone: document.getElementById("profile-phone").value,
+        attributes,
+    });
+    toast(res.success ? "Profil sacuvan" : res.message, res.success ? "success" : "error");
+}
+
 // NAVIGACIJA
 
 function showSection(name) {
@@ -354,6 +879,7 @@ function showSection(name) {
 
     if (name === "events") loadEvents();
     if (name === "my-registrations") loadMyRegistrations();
+    if (name === "profile") loadProfile();
 }
 
 // HELPERS
@@ -397,13 +923,14 @@ async function apiPost(path, body) {
     }
 }
 
-async function apiPut(path, body) {
+async function apiPut(path, body, extraHeaders = {}) {
     try {
         const res = await fetch(API + path, {
             method: "PUT",
             headers: {
                 "Content-Type": "application/json",
                 ...(token ? { "Authorization": "Bearer " + token } : {}),
+                ...extraHeaders,
             },
             body: JSON.stringify(body),
         });
@@ -0,0 +1,5 @@
+
+-- prijava je za odredjeni tip karte (NULL za dogadjaje bez tipova karata)
+ALTER TABLE registrations ADD COLUMN IF NOT EXISTS ticket_type_id UUID;
+
+CREATE INDEX IF NOT EXISTS idx_registrations_ticket_type ON registrations(event_id, ticket_type_id);
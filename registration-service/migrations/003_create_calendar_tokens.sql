@@ -0,0 +1,6 @@
+-- tajni token za licni kalendar (pretplata na .ics feed prijava)
+CREATE TABLE IF NOT EXISTS calendar_tokens (
+    user_id UUID PRIMARY KEY,
+    token VARCHAR(64) NOT NULL UNIQUE,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,3 @@
+-- check-in na ulazu (ko je i kada skenirao ulaznicu)
+ALTER TABLE registrations ADD COLUMN IF NOT EXISTS checked_in_at TIMESTAMP;
+ALTER TABLE registrations ADD COLUMN IF NOT EXISTS checked_in_by UUID;
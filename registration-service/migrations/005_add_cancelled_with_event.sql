@@ -0,0 +1,2 @@
+-- prijave otkazane brisanjem dogadjaja - vracaju se ako se dogadjaj vrati iz kante
+ALTER TABLE registrations ADD COLUMN IF NOT EXISTS cancelled_with_event BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,3 @@
+-- status moze biti i 'waitlisted' (kapacitet smanjen ispod broja prijava);
+-- napomena objasnjava korisniku zasto mu se prijava promenila
+ALTER TABLE registrations ADD COLUMN IF NOT EXISTS status_note TEXT;
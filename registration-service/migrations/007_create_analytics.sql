@@ -0,0 +1,38 @@
+-- odakle je prijava dosla (?ref= / utm_source na linku), i kad je otkazana
+ALTER TABLE registrations ADD COLUMN IF NOT EXISTS source VARCHAR(50) NOT NULL DEFAULT 'direct';
+ALTER TABLE registrations ADD COLUMN IF NOT EXISTS cancelled_at TIMESTAMP;
+-- za stare otkazane ne znamo kad su otkazane, uzimamo datum prijave
+UPDATE registrations SET cancelled_at = created_at WHERE status = 'cancelled' AND cancelled_at IS NULL;
+
+-- zbirni brojevi po dogadjaju; preracunavaju se posle svake promene prijava
+CREATE TABLE IF NOT EXISTS event_stats (
+    event_id UUID PRIMARY KEY,
+    registrations INT NOT NULL DEFAULT 0,
+    confirmed INT NOT NULL DEFAULT 0,
+    waitlisted INT NOT NULL DEFAULT 0,
+    cancelled INT NOT NULL DEFAULT 0,
+    checked_in INT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- prijave, otkazivanja i check-in po danu
+CREATE TABLE IF NOT EXISTS event_daily_stats (
+    event_id UUID NOT NULL,
+    day DATE NOT NULL,
+    registrations INT NOT NULL DEFAULT 0,
+    cancellations INT NOT NULL DEFAULT 0,
+    check_ins INT NOT NULL DEFAULT 0,
+    PRIMARY KEY (event_id, day)
+);
+
+-- podela po tipu karte (value = id tipa, '' bez tipa) i po izvoru prijave
+CREATE TABLE IF NOT EXISTS event_breakdown_stats (
+    event_id UUID NOT NULL,
+    dimension VARCHAR(20) NOT NULL,
+    value VARCHAR(50) NOT NULL,
+    registrations INT NOT NULL DEFAULT 0,
+    confirmed INT NOT NULL DEFAULT 0,
+    cancelled INT NOT NULL DEFAULT 0,
+    checked_in INT NOT NULL DEFAULT 0,
+    PRIMARY KEY (event_id, dimension, value)
+);
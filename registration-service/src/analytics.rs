@@ -0,0 +1,464 @@
+// Analitika prijava - zbirne tabele (event_stats, event_daily_stats, event_breakdown_stats)
+// se menjaju za razliku (+1/-1) u istoj transakciji kao i prijava, pa citanje ne broji sve iznova
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::NaiveDate;
+use sqlx::{FromRow, PgConnection, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::models::{StatsRates, StatsTotals};
+
+/// Normalizuje izvor prijave: mala slova, samo [a-z0-9_.-], najvise 50 znakova; prazno je "direct"
+pub fn normalize_source(source: Option<&str>) -> String {
+    let cleaned: String = source
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+        .take(50)
+        .collect();
+    if cleaned.is_empty() {
+        "direct".to_string()
+    } else {
+        cleaned
+    }
+}
+
+/// Polja prijave od kojih zavisi statistika
+#[derive(Debug, Clone, FromRow)]
+pub struct StatsRow {
+    pub id: Uuid,
+    pub created_at: chrono::NaiveDateTime,
+    pub status: String,
+    pub cancelled_at: Option<chrono::NaiveDateTime>,
+    pub checked_in_at: Option<chrono::NaiveDateTime>,
+    pub ticket_type_id: Option<Uuid>,
+    pub source: String,
+}
+
+/// Razlika u zbirnim tabelama izmedju dva stanja prijava
+#[derive(Debug, Default, PartialEq)]
+pub struct StatsDelta {
+    /// registrations, confirmed, waitlisted, cancelled, checked_in
+    pub totals: [i32; 5],
+    /// po danu: registrations, cancellations, check_ins
+    pub daily: BTreeMap<NaiveDate, [i32; 3]>,
+    /// po (dimension, value): registrations, confirmed, cancelled, checked_in
+    pub breakdown: BTreeMap<(&'static str, String), [i32; 4]>,
+}
+
+impl StatsDelta {
+    /// Razlika za prijave koje su bile `before` a sada su `after` (nova prijava nema `before`)
+    pub fn between(before: &HashMap<Uuid, StatsRow>, after: &HashMap<Uuid, StatsRow>) -> StatsDelta {
+        let mut delta = StatsDelta::default();
+        for row in before.values() {
+            delta.add(row, -1);
+        }
+        for row in after.values() {
+            delta.add(row, 1);
+        }
+        delta.daily.retain(|_, counts| counts.iter().any(|n| *n != 0));
+        delta.breakdown.retain(|_, counts| counts.iter().any(|n| *n != 0));
+        delta
+    }
+
+    /// Isto sto racuna recompute, za jednu prijavu
+    fn add(&mut self, row: &StatsRow, sign: i32) {
+        let confirmed = row.status == "confirmed";
+        let cancelled = row.status == "cancelled";
+        let checked_in = row.checked_in_at.is_some();
+        let flag = |on: bool| if on { sign } else { 0 };
+
+        for (total, d) in self.totals.iter_mut().zip([
+            sign,
+            flag(confirmed),
+            flag(row.status == "waitlisted"),
+            flag(cancelled),
+            flag(checked_in),
+        ]) {
+            *total += d;
+        }
+
+        self.daily.entry(row.created_at.date()).or_default()[0] += sign;
+        if let (true, Some(at)) = (cancelled, row.cancelled_at) {
+            self.daily.entry(at.date()).or_default()[1] += sign;
+        }
+        if let Some(at) = row.checked_in_at {
+            self.daily.entry(at.date()).or_default()[2] += sign;
+        }
+
+        let ticket_type = row.ticket_type_id.map(|id| id.to_string()).unwrap_or_default();
+        for key in [("ticket_type", ticket_type), ("source", row.source.clone())] {
+            let counts = self.breakdown.entry(key).or_default();
+            counts[0] += sign;
+            counts[1] += flag(confirmed);
+            counts[2] += flag(cancelled);
+            counts[3] += flag(checked_in);
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.totals == [0; 5] && self.daily.is_empty() && self.breakdown.is_empty()
+    }
+}
+
+/// Transakcija za izmenu prijava jednog dogadjaja. Lock po dogadjaju serijalizuje izmene,
+/// pa provere kapaciteta i kvota i razlike u statistici ne mogu da se ispreplicu.
+pub async fn begin(db: &PgPool, event_id: Uuid) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+    let mut tx = db.begin().await?;
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1::text))")
+        .bind(event_id)
+        .execute(&mut *tx)
+        .await?;
+    Ok(tx)
+}
+
+/// Trenutno stanje prijava (za statistiku), zakljucano do kraja transakcije
+pub async fn snapshot(conn: &mut PgConnection, ids: &[Uuid]) -> Result<HashMap<Uuid, StatsRow>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, StatsRow>(
+        "SELECT id, created_at, status, cancelled_at, checked_in_at, ticket_type_id, source
+         FROM registrations WHERE id = ANY($1) FOR UPDATE",
+    )
+    .bind(ids)
+    .fetch_all(conn)
+    .await?;
+    Ok(rows.into_iter().map(|r| (r.id, r)).collect())
+}
+
+/// Posle izmene prijava `ids` upisuje razliku u odnosu na `before` (snapshot pre izmene).
+/// Poziva se u transakciji iz `begin`.
+pub async fn record(
+    conn: &mut PgConnection,
+    event_id: Uuid,
+    before: &HashMap<Uuid, StatsRow>,
+    ids: &[Uuid],
+) -> Result<(), sqlx::Error> {
+    let after = snapshot(&mut *conn, ids).await?;
+    let delta = StatsDelta::between(before, &after);
+    if delta.is_empty() {
+        return Ok(());
+    }
+
+    let [registrations, confirmed, waitlisted, cancelled, checked_in] = delta.totals;
+    let updated = sqlx::query(
+        "UPDATE event_stats SET registrations = registrations + $2, confirmed = confirmed + $3,
+             waitlisted = waitlisted + $4, cancelled = cancelled + $5, checked_in = checked_in + $6, updated_at = NOW()
+         WHERE event_id = $1",
+    )
+    .bind(event_id)
+    .bind(registrations)
+    .bind(confirmed)
+    .bind(waitlisted)
+    .bind(cancelled)
+    .bind(checked_in)
+    .execute(&mut *conn)
+    .await?;
+    // dogadjaj jos nema statistiku (prva prijava ili prijave od pre analitike)
+    if updated.rows_affected() == 0 {
+        return recompute(conn, event_id).await;
+    }
+
+    let days: Vec<NaiveDate> = delta.daily.keys().copied().collect();
+    let column = |i: usize| delta.daily.values().map(|c| c[i]).collect::<Vec<i32>>();
+    sqlx::query(
+        "INSERT INTO event_daily_stats (event_id, day, registrations, cancellations, check_ins)
+         SELECT $1, * FROM UNNEST($2::date[], $3::int[], $4::int[], $5::int[])
+         ON CONFLICT (event_id, day) DO UPDATE SET
+             registrations = event_daily_stats.registrations + EXCLUDED.registrations,
+             cancellations = event_daily_stats.cancellations + EXCLUDED.cancellations,
+             check_ins = event_daily_stats.check_ins + EXCLUDED.check_ins",
+    )
+    .bind(event_id)
+    .bind(&days)
+    .bind(column(0))
+    .bind(column(1))
+    .bind(column(2))
+    .execute(&mut *conn)
+    .await?;
+    sqlx::query(
+        "DELETE FROM event_daily_stats
+         WHERE event_id = $1 AND day = ANY($2) AND registrations = 0 AND cancellations = 0 AND check_ins = 0",
+    )
+    .bind(event_id)
+    .bind(&days)
+    .execute(&mut *conn)
+    .await?;
+
+    let dimensions: Vec<&str> = delta.breakdown.keys().map(|(d, _)| *d).collect();
+    let values: Vec<&str> = delta.breakdown.keys().map(|(_, v)| v.as_str()).collect();
+    let column = |i: usize| delta.breakdown.values().map(|c| c[i]).collect::<Vec<i32>>();
+    sqlx::query(
+        "INSERT INTO event_breakdown_stats (event_id, dimension, value, registrations, confirmed, cancelled, checked_in)
+         SELECT $1, * FROM UNNEST($2::varchar[], $3::varchar[], $4::int[], $5::int[], $6::int[], $7::int[])
+         ON CONFLICT (event_id, dimension, value) DO UPDATE SET
+             registrations = event_breakdown_stats.registrations + EXCLUDED.registrations,
+             confirmed = event_breakdown_stats.confirmed + EXCLUDED.confirmed,
+             cancelled = event_breakdown_stats.cancelled + EXCLUDED.cancelled,
+             checked_in = event_breakdown_stats.checked_in + EXCLUDED.checked_in",
+    )
+    .bind(event_id)
+    .bind(&dimensions)
+    .bind(&values)
+    .bind(column(0))
+    .bind(column(1))
+    .bind(column(2))
+    .bind(column(3))
+    .execute(&mut *conn)
+    .await?;
+    // registracije su nadskup ostalih brojeva, pa 0 znaci prazan red
+    sqlx::query("DELETE FROM event_breakdown_stats WHERE event_id = $1 AND registrations = 0")
+        .bind(event_id)
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(())
+}
+
+/// Preracunava sve zbirne tabele za jedan dogadjaj iz samih prijava (backfill)
+async fn recompute(conn: &mut PgConnection, event_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO event_stats (event_id, registrations, confirmed, waitlisted, cancelled, checked_in, updated_at)
+         SELECT $1, COUNT(*),
+                COUNT(*) FILTER (WHERE status = 'confirmed'),
+                COUNT(*) FILTER (WHERE status = 'waitlisted'),
+                COUNT(*) FILTER (WHERE status = 'cancelled'),
+                COUNT(*) FILTER (WHERE checked_in_at IS NOT NULL),
+                NOW()
+         FROM registrations WHERE event_id = $1
+         ON CONFLICT (event_id) DO UPDATE SET
+             registrations = EXCLUDED.registrations, confirmed = EXCLUDED.confirmed,
+             waitlisted = EXCLUDED.waitlisted, cancelled = EXCLUDED.cancelled,
+             checked_in = EXCLUDED.checked_in, updated_at = NOW()",
+    )
+    .bind(event_id)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query("DELETE FROM event_daily_stats WHERE event_id = $1")
+        .bind(event_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query(
+        "INSERT INTO event_daily_stats (event_id, day, registrations, cancellations, check_ins)
+         SELECT $1, day, COUNT(*) FILTER (WHERE kind = 'r'), COUNT(*) FILTER (WHERE kind = 'c'),
+                COUNT(*) FILTER (WHERE kind = 'i')
+         FROM (
+             SELECT created_at::date AS day, 'r' AS kind FROM registrations WHERE event_id = $1
+             UNION ALL
+             SELECT cancelled_at::date, 'c' FROM registrations
+             WHERE event_id = $1 AND status = 'cancelled' AND cancelled_at IS NOT NULL
+             UNION ALL
+             SELECT checked_in_at::date, 'i' FROM registrations WHERE event_id = $1 AND checked_in_at IS NOT NULL
+         ) e
+         GROUP BY day",
+    )
+    .bind(event_id)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query("DELETE FROM event_breakdown_stats WHERE event_id = $1")
+        .bind(event_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query(
+        "INSERT INTO event_breakdown_stats (event_id, dimension, value, registrations, confirmed, cancelled, checked_in)
+         SELECT $1, dimension, value, COUNT(*),
+                COUNT(*) FILTER (WHERE status = 'confirmed'),
+                COUNT(*) FILTER (WHERE status = 'cancelled'),
+                COUNT(*) FILTER (WHERE checked_in_at IS NOT NULL)
+         FROM (
+             SELECT 'ticket_type' AS dimension, COALESCE(ticket_type_id::text, '') AS value, status, checked_in_at
+             FROM registrations WHERE event_id = $1
+             UNION ALL
+             SELECT 'source', source, status, checked_in_at FROM registrations WHERE event_id = $1
+         ) r
+         GROUP BY dimension, value",
+    )
+    .bind(event_id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Pri pokretanju popunjava statistiku za dogadjaje koji je jos nemaju (stare prijave)
+pub async fn backfill(db: PgPool) {
+    let missing = sqlx::query_scalar::<_, Uuid>(
+        "SELECT DISTINCT event_id FROM registrations r
+         WHERE NOT EXISTS (SELECT 1 FROM event_stats s WHERE s.event_id = r.event_id)",
+    )
+    .fetch_all(&db)
+    .await;
+
+    match missing {
+        Ok(ids) => {
+            for event_id in &ids {
+                let result = async {
+                    let mut tx = begin(&db, *event_id).await?;
+                    recompute(&mut tx, *event_id).await?;
+                    tx.commit().await
+                }
+                .await;
+                if let Err(e) = result {
+                    tracing::warn!("Nisam uspeo da popunim analitiku za {}: {}", event_id, e);
+                }
+            }
+            if !ids.is_empty() {
+                tracing::info!("Analitika popunjena za {} dogadjaja", ids.len());
+            }
+        }
+        Err(e) => tracing::warn!("Ne mogu da popunim analitiku: {}", e),
+    }
+}
+
+fn ratio(part: i64, whole: i64) -> Option<f64> {
+    (whole > 0).then(|| (part as f64 / whole as f64 * 10000.0).round() / 10000.0)
+}
+
+/// Stope za dogadjaj ili zbir dogadjaja. `attended` su potvrdjeni i check-in sa dogadjaja
+/// koji su vec poceli - samo od njih se racuna stopa nedolazaka.
+pub fn rates(totals: &StatsTotals, capacity: i64, attended: Option<(i64, i64)>) -> StatsRates {
+    StatsRates {
+        cancellation_rate: ratio(totals.cancelled, totals.registrations).unwrap_or(0.0),
+        fill_rate: ratio(totals.confirmed, capacity),
+        check_in_rate: ratio(totals.checked_in, totals.confirmed),
+        no_show_rate: attended
+            .and_then(|(confirmed, checked_in)| ratio(confirmed - checked_in, confirmed)),
+    }
+}
+
+/// CSV sa zaglavljem; polja sa zarezom, navodnicima ili novim redom idu pod navodnike
+pub fn to_csv(header: &[&str], rows: Vec<Vec<String>>) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(header.iter().map(|h| csv_cell(h))).map_err(|e| e.to_string())?;
+    for row in rows {
+        writer.write_record(row.iter().map(|c| csv_cell(c))).map_err(|e| e.to_string())?;
+    }
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+/// Tabelarni programi izvrsavaju polje koje pocinje sa = + - @ (ili tab/CR) kao formulu,
+/// a naziv dogadjaja i izvor prijave unose korisnici - takvo polje dobija ' ispred
+fn csv_cell(value: &str) -> String {
+    if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Stopa za CSV - prazno polje kad nema smisla
+pub fn csv_rate(rate: Option<f64>) -> String {
+    rate.map(|r| r.to_string()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn row(id: Uuid, status: &str) -> StatsRow {
+        StatsRow {
+            id,
+            created_at: at("2026-05-01 10:00"),
+            status: status.to_string(),
+            cancelled_at: None,
+            checked_in_at: None,
+            ticket_type_id: None,
+            source: "direct".to_string(),
+        }
+    }
+
+    fn day(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn new_registration_adds_one_everywhere() {
+        let id = Uuid::new_v4();
+        let delta = StatsDelta::between(&HashMap::new(), &HashMap::from([(id, row(id, "confirmed"))]));
+
+        assert_eq!(delta.totals, [1, 1, 0, 0, 0]);
+        assert_eq!(delta.daily, BTreeMap::from([(day("2026-05-01"), [1, 0, 0])]));
+        assert_eq!(delta.breakdown.get(&("ticket_type", String::new())), Some(&[1, 1, 0, 0]));
+        assert_eq!(delta.breakdown.get(&("source", "direct".to_string())), Some(&[1, 1, 0, 0]));
+    }
+
+    #[test]
+    fn cancellation_moves_counts_and_keeps_registration() {
+        let id = Uuid::new_v4();
+        let before = HashMap::from([(id, row(id, "confirmed"))]);
+        let mut cancelled = row(id, "cancelled");
+        cancelled.cancelled_at = Some(at("2026-05-03 09:00"));
+        let delta = StatsDelta::between(&before, &HashMap::from([(id, cancelled)]));
+
+        assert_eq!(delta.totals, [0, -1, 0, 1, 0]);
+        // dan prijave se ne menja, pa ga nema u razlici
+        assert_eq!(delta.daily, BTreeMap::from([(day("2026-05-03"), [0, 1, 0])]));
+        assert_eq!(delta.breakdown.get(&("source", "direct".to_string())), Some(&[0, -1, 1, 0]));
+    }
+
+    #[test]
+    fn csv_quotes_and_neutralizes_formulas() {
+        let csv = to_csv(
+            &["title", "source"],
+            vec![
+                vec!["Koncert, veliki".to_string(), "=HYPERLINK(\"x\")".to_string()],
+                vec!["+381".to_string(), "@cmd".to_string()],
+                vec!["-1".to_string(), "\tskriveno".to_string()],
+                vec!["obican".to_string(), "direct".to_string()],
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            csv,
+            "title,source\n\
+             \"Koncert, veliki\",\"'=HYPERLINK(\"\"x\"\")\"\n\
+             '+381,'@cmd\n\
+             '-1,'\tskriveno\n\
+             obican,direct\n"
+        );
+    }
+
+    #[test]
+    fn csv_with_only_header() {
+        assert_eq!(to_csv(&["day", "registrations"], Vec::new()).unwrap(), "day,registrations\n");
+    }
+
+    #[test]
+    fn rates_are_rounded_and_skip_empty_bases() {
+        let totals = StatsTotals { registrations: 3, confirmed: 2, waitlisted: 0, cancelled: 1, checked_in: 1 };
+        let filled = rates(&totals, 3, Some((2, 1)));
+        assert_eq!(filled.cancellation_rate, 0.3333);
+        assert_eq!(filled.fill_rate, Some(0.6667));
+        assert_eq!(filled.check_in_rate, Some(0.5));
+        assert_eq!(filled.no_show_rate, Some(0.5));
+
+        let empty = StatsTotals { registrations: 0, confirmed: 0, waitlisted: 0, cancelled: 0, checked_in: 0 };
+        let none = rates(&empty, 0, None);
+        assert_eq!(none.cancellation_rate, 0.0);
+        assert_eq!(none.fill_rate, None);
+        assert_eq!(none.check_in_rate, None);
+        // dogadjaj jos nije poceo
+        assert_eq!(none.no_show_rate, None);
+    }
+
+    #[test]
+    fn unchanged_rows_give_empty_delta() {
+        let id = Uuid::new_v4();
+        let mut checked_in = row(id, "confirmed");
+        checked_in.checked_in_at = Some(at("2026-05-10 18:00"));
+        let rows = HashMap::from([(id, checked_in)]);
+
+        assert!(StatsDelta::between(&rows, &rows).is_empty());
+        assert!(StatsDelta::between(&HashMap::new(), &HashMap::new()).is_empty());
+    }
+}
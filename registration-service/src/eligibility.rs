@@ -0,0 +1,150 @@
+// Uslovi za prijavu - pravila cuva event servis, profil auth servis, ovde se samo proverava
+
+use uuid::Uuid;
+
+use crate::models::{
+    EligibilityCheck, EventEligibilityData, EventEligibilityResponse, IneligibilityReason, ProfileData,
+    ProfileResponse,
+};
+use crate::AppState;
+use shared::auth::INTERNAL_TOKEN_HEADER;
+
+/// None - dogadjaj ne postoji
+async fn fetch_rules(state: &AppState, event_id: Uuid) -> Result<Option<EventEligibilityData>, String> {
+    let url = format!("{}/internal/events/{}/eligibility", state.event_service_url, event_id);
+    let resp = reqwest::get(&url).await.map_err(|e| e.to_string())?;
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let body = resp
+        .json::<EventEligibilityResponse>()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    match body.data {
+        Some(data) if body.success => Ok(Some(data)),
+        _ => Err("Event servis vratio gresku".to_string()),
+    }
+}
+
+async fn fetch_profile(state: &AppState, user_id: Uuid) -> Result<ProfileData, String> {
+    let url = format!("{}/internal/users/{}/profile", state.auth_service_url, user_id);
+    let resp = reqwest::Client::new()
+        .get(&url)
+        .header(INTERNAL_TOKEN_HEADER, &state.internal_token)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    let body = resp.json::<ProfileResponse>().await.map_err(|e| e.to_string())?;
+
+    match body.data {
+        Some(profile) if body.success => Ok(profile),
+        _ => Err("Auth servis vratio gresku".to_string()),
+    }
+}
+
+/// "ana@dev.firma.rs" prolazi za "firma.rs", "ana@losafirma.rs" ne prolazi.
+/// Auth servis ne potvrdjuje vlasnistvo nad email adresom (nema verifikacije), pa je ovo
+/// filter za posteni slucaj, a ne kontrola pristupa - svako moze da se registruje sa tudjim domenom.
+fn domain_allowed(email: &str, allowed: &[String]) -> bool {
+    let Some((_, domain)) = email.rsplit_once('@') else {
+        return false;
+    };
+    let domain = domain.to_lowercase();
+    allowed
+        .iter()
+        .any(|a| domain == *a || domain.strip_suffix(a.as_str()).is_some_and(|rest| rest.ends_with('.')))
+}
+
+fn attribute_filled(profile: &ProfileData, key: &str) -> bool {
+    let filled = |v: &Option<String>| v.as_deref().is_some_and(|s| !s.trim().is_empty());
+    match key {
+        "full_name" => filled(&profile.full_name),
+        "date_of_birth" => profile.date_of_birth.is_some(),
+        "organization" => filled(&profile.organization),
+        "phone" => filled(&profile.phone),
+        other => profile.attributes.get(other).is_some_and(|v| !v.trim().is_empty()),
+    }
+}
+
+/// Proverava sve uslove dogadjaja za korisnika; prazna lista razloga = moze da se prijavi.
+/// None - dogadjaj ne postoji; greska znaci da neki servis nije dostupan.
+pub async fn check(
+    state: &AppState,
+    event_id: Uuid,
+    user_id: Uuid,
+    email: &str,
+) -> Result<Option<EligibilityCheck>, String> {
+    let Some(data) = fetch_rules(state, event_id).await? else {
+        return Ok(None);
+    };
+    let rules = &data.rules;
+    let mut reasons = Vec::new();
+
+    if rules.is_empty() {
+        return Ok(Some(EligibilityCheck { eligible: true, reasons }));
+    }
+
+    if !rules.allowed_email_domains.is_empty() && !domain_allowed(email, &rules.allowed_email_domains) {
+        reasons.push(IneligibilityReason {
+            code: "email_domain",
+            message: format!("Prijava je dozvoljena samo za email adrese: {}", rules.allowed_email_domains.join(", ")),
+        });
+    }
+
+    if rules.min_age.is_some() || !rules.required_attributes.is_empty() {
+        let profile = fetch_profile(state, user_id).await?;
+
+        if let Some(min_age) = rules.min_age {
+            match profile.date_of_birth {
+                None => reasons.push(IneligibilityReason {
+                    code: "date_of_birth_missing",
+                    message: format!("Dogadjaj je za osobe od {}+ godina - unesite datum rodjenja u profil", min_age),
+                }),
+                Some(dob) => {
+                    let age = data.date_time.date().years_since(dob).unwrap_or(0);
+                    if (age as i32) < min_age {
+                        reasons.push(IneligibilityReason {
+                            code: "too_young",
+                            message: format!("Dogadjaj je za osobe od {}+ godina", min_age),
+                        });
+                    }
+                }
+            }
+        }
+
+        let missing: Vec<&str> = rules
+            .required_attributes
+            .iter()
+            .filter(|key| !attribute_filled(&profile, key))
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            reasons.push(IneligibilityReason {
+                code: "missing_attributes",
+                message: format!("U profilu nedostaju obavezni podaci: {}", missing.join(", ")),
+            });
+        }
+    }
+
+    if let (Some(max), false) = (rules.max_per_series, data.series_event_ids.is_empty()) {
+        let active = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM registrations
+             WHERE user_id = $1 AND event_id = ANY($2) AND status IN ('confirmed', 'waitlisted')",
+        )
+        .bind(user_id)
+        .bind(&data.series_event_ids)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        if active >= max as i64 {
+            reasons.push(IneligibilityReason {
+                code: "series_limit",
+                message: format!("Vec ste prijavljeni na {} termina ove serije (najvise {})", active, max),
+            });
+        }
+    }
+
+    Ok(Some(EligibilityCheck { eligible: reasons.is_empty(), reasons }))
+}
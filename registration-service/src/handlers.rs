@@ -1,12 +1,25 @@
-use axum::{extract::{Path, State}, http::{HeaderMap, StatusCode}, Json};
+use std::collections::HashMap;
+
+use axum::{extract::{Path, Query, State}, http::{HeaderMap, StatusCode}, Json};
 use serde_json::json;
 use uuid::Uuid;
 
 use axum::response::{IntoResponse, Response};
-use crate::models::{CountResult, EventServiceResponse, RegisterRequest, Registration};
+use crate::models::{
+    ActivityQuery, AnalyticsQuery, Attendee, BreakdownStats, CalendarToken,
+    CapacityChangeRequest, CheckInRequest, CountResult, CountsQuery, DailyStats,
+    EligibilityCheck, EligibilityQuery, EventAccessResponse, EventAnalytics, EventAnalyticsRow,
+    EventCounts, EventCountsRequest, EventServiceResponse, EventSummary, EventSummaryResponse,
+    OrganizerAnalytics, RegisterRequest, Registration, RegistrationActivity,
+    RestoreRegistrationsRequest, StatsTotals, TicketTypeData, TicketTypesResponse,
+    UsersResponse,
+};
+use crate::analytics;
+use crate::eligibility;
 use crate::AppState;
-use shared::auth::extract_claims;
+use shared::auth::{extract_claims, Claims, INTERNAL_TOKEN_HEADER};
 use shared::models::ApiResponse;
+use shared::staff::{role_allows, Permission};
 
 /// GET health
 pub async fn health_check() -> Json<serde_json::Value> {
@@ -16,6 +29,21 @@ pub async fn health_check() -> Json<serde_json::Value> {
     }))
 }
 
+/// Svi tipovi karata za dogadjaj (i skriveni) iz event servisa
+async fn fetch_ticket_types(state: &AppState, event_id: Uuid) -> Result<Vec<TicketTypeData>, String> {
+    let url = format!("{}/internal/events/{}/ticket-types", state.event_service_url, event_id);
+    let resp = reqwest::get(&url).await.map_err(|e| e.to_string())?;
+    let body = resp
+        .json::<TicketTypesResponse>()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    match body.data {
+        Some(list) if body.success => Ok(list),
+        _ => Err("Event servis vratio gresku".to_string()),
+    }
+}
+
 /// registration for event
 pub async fn register_for_event(
     headers: HeaderMap,
@@ -28,27 +56,35 @@ pub async fn register_for_event(
     };
     let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
 
-    // jel user vec prijavljen
+    // jel user vec prijavljen (ili na listi cekanja)
     let existing = sqlx::query_as::<_, Registration>(
-        "SELECT * FROM registrations WHERE event_id = $1 AND user_id = $2 AND status = 'confirmed'",
+        "SELECT * FROM registrations WHERE event_id = $1 AND user_id = $2 AND status IN ('confirmed', 'waitlisted')",
     )
     .bind(req.event_id)
     .bind(user_id)
     .fetch_optional(&state.db)
     .await;
 
-    if let Ok(Some(_)) = existing {
-        return (
-            StatusCode::CONFLICT,
-            Json(ApiResponse::error("Vec ste prijavljeni na ovaj dogadjaj")),
-        );
+    if let Ok(Some(reg)) = existing {
+        let msg = if reg.status == "waitlisted" {
+            "Vec ste na listi cekanja za ovaj dogadjaj"
+        } else {
+            "Vec ste prijavljeni na ovaj dogadjaj"
+        };
+        return (StatusCode::CONFLICT, Json(ApiResponse::error(msg)));
     }
 
-    // pitamo event servis za kapacitet
-    let event_url = format!("{}/events/{}", state.event_service_url, req.event_id);
-    tracing::info!("Pozivam event servis: {}", event_url);
+    // event servis proverava pristup (privatni dogadjaji: pozivnica ili pristupni kod) i vraca kapacitet
+    let admission_url = format!("{}/internal/events/{}/admission", state.event_service_url, req.event_id);
+    tracing::info!("Pozivam event servis: {}", admission_url);
 
-    let event_data = match reqwest::get(&event_url).await {
+    let resp = reqwest::Client::new()
+        .post(&admission_url)
+        .json(&json!({ "user_id": user_id, "email": claims.email, "access_code": req.access_code }))
+        .send()
+        .await;
+
+    let event_data = match resp {
         Ok(resp) => {
             let status = resp.status();
             let body = resp.text().await.unwrap_or_default();
@@ -56,6 +92,10 @@ pub async fn register_for_event(
 
             match serde_json::from_str::<EventServiceResponse>(&body) {
                 Ok(data) if data.success && data.data.is_some() => data.data.unwrap(),
+                Ok(data) if status == reqwest::StatusCode::FORBIDDEN => {
+                    let msg = data.message.unwrap_or_else(|| "Nemate pristup ovom dogadjaju".to_string());
+                    return (StatusCode::FORBIDDEN, Json(ApiResponse::error(&msg)));
+                }
                 Ok(data) => {
                     tracing::warn!("Event servis vratio success=false: {:?}", data);
                     return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Dogadjaj ne postoji")));
@@ -75,12 +115,106 @@ pub async fn register_for_event(
         }
     };
 
+    // uslovi dogadjaja (godine, domen emaila, profil, limit po seriji)
+    match eligibility::check(&state, req.event_id, user_id, &claims.email).await {
+        Ok(Some(check)) if !check.eligible => {
+            let msg = check.reasons.iter().map(|r| r.message.as_str()).collect::<Vec<_>>().join("; ");
+            return (StatusCode::FORBIDDEN, Json(ApiResponse::error(&msg)));
+        }
+        Ok(Some(_)) => {}
+        Ok(None) => return (StatusCode::NOT_FOUND, Json(ApiResponse::error("Dogadjaj ne postoji"))),
+        Err(e) => {
+            tracing::error!("Ne mogu da proverim uslove za prijavu: {}", e);
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(ApiResponse::error("Provera uslova za prijavu nije uspela")),
+            );
+        }
+    }
+
+    // tipovi karata - ako ih dogadjaj ima, prijava mora biti za jedan od njih
+    let ticket_types = match fetch_ticket_types(&state, req.event_id).await {
+        Ok(list) => list,
+        Err(e) => {
+            tracing::error!("Ne mogu da ucitam tipove karata: {}", e);
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(ApiResponse::error("Event servis nije dostupan")),
+            );
+        }
+    };
+
+    let ticket_type = match (req.ticket_type_id, ticket_types.is_empty()) {
+        (None, true) => None,
+        (None, false) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error("Izaberite tip karte")),
+            );
+        }
+        (Some(type_id), _) => match ticket_types.into_iter().find(|t| t.id == type_id) {
+            Some(tt) => Some(tt),
+            None => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(ApiResponse::error("Tip karte ne postoji za ovaj dogadjaj")),
+                );
+            }
+        },
+    };
+
+    if let Some(tt) = &ticket_type {
+        let now = chrono::Utc::now().naive_utc();
+        if tt.sales_start.is_some_and(|start| now < start) {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(&format!("Prodaja karata \"{}\" jos nije pocela", tt.name))),
+            );
+        }
+        if tt.sales_end.is_some_and(|end| now > end) {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(&format!("Prodaja karata \"{}\" je zavrsena", tt.name))),
+            );
+        }
+    }
+
+    // Brojanje i upis idu pod istim lock-om po dogadjaju, inace istovremeni
+    // kupci mogu da probiju kvotu ili kapacitet
+    let mut tx = match analytics::begin(&state.db, req.event_id).await {
+        Ok(tx) => tx,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(&format!("Greska: {}", e))),
+            );
+        }
+    };
+
+    if let Some(tt) = &ticket_type {
+        let sold = sqlx::query_as::<_, CountResult>(
+            "SELECT COUNT(*) as count FROM registrations WHERE event_id = $1 AND ticket_type_id = $2 AND status = 'confirmed'",
+        )
+        .bind(req.event_id)
+        .bind(tt.id)
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap_or(CountResult { count: Some(0) });
+
+        if sold.count.unwrap_or(0) >= tt.quota as i64 {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(&format!("Karte \"{}\" su rasprodate", tt.name))),
+            );
+        }
+    }
+
     // count registrations
     let count = sqlx::query_as::<_, CountResult>(
         "SELECT COUNT(*) as count FROM registrations WHERE event_id = $1 AND status = 'confirmed'",
     )
     .bind(req.event_id)
-    .fetch_one(&state.db)
+    .fetch_one(&mut *tx)
     .await
     .unwrap_or(CountResult { count: Some(0) });
 
@@ -98,16 +232,27 @@ pub async fn register_for_event(
 
     // Write registration
     let result = sqlx::query_as::<_, Registration>(
-        "INSERT INTO registrations (id, event_id, user_id, ticket_code, status)
-         VALUES (gen_random_uuid(), $1, $2, $3, 'confirmed')
+        "INSERT INTO registrations (id, event_id, user_id, ticket_code, status, ticket_type_id, source)
+         VALUES (gen_random_uuid(), $1, $2, $3, 'confirmed', $4, $5)
          RETURNING *",
     )
     .bind(req.event_id)
     .bind(user_id)
     .bind(&ticket_code)
-    .fetch_one(&state.db)
+    .bind(ticket_type.as_ref().map(|t| t.id))
+    .bind(analytics::normalize_source(req.source.as_deref()))
+    .fetch_one(&mut *tx)
     .await;
 
+    let result = match result {
+        Ok(reg) => async {
+            analytics::record(&mut tx, reg.event_id, &HashMap::new(), &[reg.id]).await?;
+            tx.commit().await.map(|_| reg)
+        }
+        .await,
+        Err(e) => Err(e),
+    };
+
     match result {
         Ok(reg) => (
             StatusCode::CREATED,
@@ -149,13 +294,27 @@ pub async fn cancel_registration(
                 );
             }
 
-            let result = sqlx::query_as::<_, Registration>(
-                "UPDATE registrations SET status = 'cancelled' WHERE id = $1 RETURNING *",
-            )
-            .bind(id)
-            .fetch_one(&state.db)
+            let result = async {
+                let mut tx = analytics::begin(&state.db, reg.event_id).await?;
+                let before = analytics::snapshot(&mut tx, &[id]).await?;
+                let cancelled = sqlx::query_as::<_, Registration>(
+                    "UPDATE registrations SET status = 'cancelled', status_note = NULL, cancelled_at = NOW()
+                     WHERE id = $1 RETURNING *",
+                )
+                .bind(id)
+                .fetch_one(&mut *tx)
+                .await?;
+                analytics::record(&mut tx, reg.event_id, &before, &[id]).await?;
+                tx.commit().await?;
+                Ok::<_, sqlx::Error>(cancelled)
+            }
             .await;
 
+            // oslobodjeno mesto dobija prvi sa liste cekanja
+            if result.is_ok() && reg.status == "confirmed" {
+                promote_waitlisted(&state, reg.event_id, 1).await;
+            }
+
             match result {
                 Ok(cancelled) => (
                     StatusCode::OK,
@@ -178,15 +337,33 @@ pub async fn cancel_registration(
     }
 }
 
-/// Internal: otkazuje sve prijave za dogadjaj (poziva event-service pri brisanju dogadjaja)
+/// Internal: otkazuje sve prijave za dogadjaj (poziva event-service pri brisanju dogadjaja).
+/// Oznacavaju se da bi se vratile ako se dogadjaj vrati iz kante.
 pub async fn cancel_registrations_for_event(
     State(state): State<AppState>,
     Path(event_id): Path<Uuid>,
 ) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
-    let result = sqlx::query("UPDATE registrations SET status = 'cancelled' WHERE event_id = $1")
+    let result = async {
+        let mut tx = analytics::begin(&state.db, event_id).await?;
+        let ids = sqlx::query_scalar::<_, Uuid>(
+            "SELECT id FROM registrations WHERE event_id = $1 AND status IN ('confirmed', 'waitlisted')",
+        )
         .bind(event_id)
-        .execute(&state.db)
-        .await;
+        .fetch_all(&mut *tx)
+        .await?;
+        let before = analytics::snapshot(&mut tx, &ids).await?;
+        let rows = sqlx::query(
+            "UPDATE registrations SET status = 'cancelled', cancelled_with_event = TRUE, cancelled_at = NOW()
+             WHERE id = ANY($1)",
+        )
+        .bind(&ids)
+        .execute(&mut *tx)
+        .await?;
+        analytics::record(&mut tx, event_id, &before, &ids).await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(rows)
+    }
+    .await;
 
     match result {
         Ok(rows) => (
@@ -203,6 +380,374 @@ pub async fn cancel_registrations_for_event(
     }
 }
 
+/// Prebacuje najranije prijave sa liste cekanja u potvrdjene (najvise `limit`, u okviru kvota tipova karata)
+async fn promote_waitlisted(state: &AppState, event_id: Uuid, limit: i64) -> u64 {
+    let quotas: HashMap<Uuid, i64> = match fetch_ticket_types(state, event_id).await {
+        Ok(list) => list.into_iter().map(|t| (t.id, t.quota as i64)).collect(),
+        Err(e) => {
+            tracing::warn!("Ne mogu da ucitam tipove karata za {}, lista cekanja ostaje: {}", event_id, e);
+            return 0;
+        }
+    };
+
+    let result = async {
+        let mut tx = analytics::begin(&state.db, event_id).await?;
+        let promoted = promote_within_quotas(&mut tx, event_id, limit, &quotas).await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(promoted)
+    }
+    .await;
+
+    match result {
+        Ok(promoted) => promoted,
+        Err(e) => {
+            tracing::warn!("Nisam uspeo da prebacim listu cekanja za {}: {}", event_id, e);
+            0
+        }
+    }
+}
+
+/// Redom sa liste cekanja, preskacuci prijave ciji je tip karte rasprodat.
+/// Poziva se u transakciji iz analytics::begin (isti lock kao u register_for_event),
+/// da nova prijava i prelazak ne probiju kvotu zajedno.
+async fn promote_within_quotas(
+    conn: &mut sqlx::PgConnection,
+    event_id: Uuid,
+    limit: i64,
+    quotas: &HashMap<Uuid, i64>,
+) -> Result<u64, sqlx::Error> {
+    let mut per_type: HashMap<Uuid, i64> = sqlx::query_as::<_, (Uuid, i64)>(
+        "SELECT ticket_type_id, COUNT(*) FROM registrations
+         WHERE event_id = $1 AND status = 'confirmed' AND ticket_type_id IS NOT NULL GROUP BY ticket_type_id",
+    )
+    .bind(event_id)
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .collect();
+
+    let waitlisted = sqlx::query_as::<_, (Uuid, Option<Uuid>)>(
+        "SELECT id, ticket_type_id FROM registrations WHERE event_id = $1 AND status = 'waitlisted' ORDER BY created_at",
+    )
+    .bind(event_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut promote = Vec::new();
+    for (id, ticket_type_id) in waitlisted {
+        if promote.len() as i64 >= limit {
+            break;
+        }
+        if let Some(tt) = ticket_type_id {
+            let sold = per_type.entry(tt).or_insert(0);
+            if quotas.get(&tt).is_some_and(|quota| *sold >= *quota) {
+                continue;
+            }
+            *sold += 1;
+        }
+        promote.push(id);
+    }
+
+    let before = analytics::snapshot(conn, &promote).await?;
+    let result = sqlx::query(
+        "UPDATE registrations SET status = 'confirmed', status_note = 'Presli ste sa liste cekanja' WHERE id = ANY($1)",
+    )
+    .bind(&promote)
+    .execute(&mut *conn)
+    .await?;
+    analytics::record(conn, event_id, &before, &promote).await?;
+    Ok(result.rows_affected())
+}
+
+/// Internal: broj potvrdjenih prijava i onih na listi cekanja (event-service pre promene kapaciteta
+/// ili kvote - tada samo za jedan tip karte)
+pub async fn registration_counts(
+    State(state): State<AppState>,
+    Path(event_id): Path<Uuid>,
+    Query(query): Query<CountsQuery>,
+) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
+    let result = sqlx::query_as::<_, (i64, i64)>(
+        "SELECT COUNT(*) FILTER (WHERE status = 'confirmed'), COUNT(*) FILTER (WHERE status = 'waitlisted')
+         FROM registrations WHERE event_id = $1 AND ($2::uuid IS NULL OR ticket_type_id = $2)",
+    )
+    .bind(event_id)
+    .bind(query.ticket_type_id)
+    .fetch_one(&state.db)
+    .await;
+
+    match result {
+        Ok((confirmed, waitlisted)) => (
+            StatusCode::OK,
+            Json(ApiResponse::success("Broj prijava", json!({ "confirmed": confirmed, "waitlisted": waitlisted }))),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!("Greska: {}", e))),
+        ),
+    }
+}
+
+/// Internal: broj prijava za vise dogadjaja (event-service racuna dostupnost za listu i facete).
+/// Dogadjaji bez prijava se ne vracaju.
+pub async fn registration_counts_bulk(
+    State(state): State<AppState>,
+    Json(req): Json<EventCountsRequest>,
+) -> (StatusCode, Json<ApiResponse<Vec<EventCounts>>>) {
+    let result = sqlx::query_as::<_, EventCounts>(
+        "SELECT event_id,
+                COUNT(*) FILTER (WHERE status = 'confirmed') AS confirmed,
+                COUNT(*) FILTER (WHERE status = 'waitlisted') AS waitlisted
+         FROM registrations WHERE event_id = ANY($1) GROUP BY event_id",
+    )
+    .bind(&req.event_ids)
+    .fetch_all(&state.db)
+    .await;
+
+    match result {
+        Ok(list) => (StatusCode::OK, Json(ApiResponse::success("Broj prijava", list))),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!("Greska: {}", e))),
+        ),
+    }
+}
+
+/// Internal: potvrdjene prijave i lista cekanja kao parovi korisnik-dogadjaj (za preporuke)
+pub async fn registration_activity(
+    State(state): State<AppState>,
+    Query(query): Query<ActivityQuery>,
+) -> (StatusCode, Json<ApiResponse<Vec<RegistrationActivity>>>) {
+    let result = sqlx::query_as::<_, RegistrationActivity>(
+        "SELECT user_id, event_id FROM registrations
+         WHERE status IN ('confirmed', 'waitlisted')
+           AND ($1::uuid IS NULL OR user_id = $1)
+           AND created_at > NOW() - $2 * INTERVAL '1 day'",
+    )
+    .bind(query.user_id)
+    .bind(query.since_days.unwrap_or(365) as f64)
+    .fetch_all(&state.db)
+    .await;
+
+    match result {
+        Ok(list) => (StatusCode::OK, Json(ApiResponse::success("Aktivnost", list))),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!("Greska: {}", e))),
+        ),
+    }
+}
+
+/// Internal: uskladjuje prijave sa novim kapacitetom (event-service posle izmene dogadjaja).
+/// Visak (poslednji prijavljeni) ide na listu cekanja ili se otkazuje uz napomenu;
+/// ako je kapacitet povecan, lista cekanja popunjava mesta.
+pub async fn apply_capacity_change(
+    State(state): State<AppState>,
+    Path(event_id): Path<Uuid>,
+    Json(req): Json<CapacityChangeRequest>,
+) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
+    let strategy = match req.strategy.as_deref() {
+        Some("waitlist") => Some(("waitlisted", "Kapacitet dogadjaja je smanjen - premesteni ste na listu cekanja")),
+        Some("cancel") => Some(("cancelled", "Kapacitet dogadjaja je smanjen - prijava je otkazana")),
+        _ => None,
+    };
+    // kvote trebaju samo za popunjavanje sa liste cekanja; ucitavaju se pre zakljucavanja
+    let quotas: Option<HashMap<Uuid, i64>> = match fetch_ticket_types(&state, event_id).await {
+        Ok(list) => Some(list.into_iter().map(|t| (t.id, t.quota as i64)).collect()),
+        Err(e) => {
+            tracing::warn!("Ne mogu da ucitam tipove karata za {}, lista cekanja ostaje: {}", event_id, e);
+            None
+        }
+    };
+
+    // broj potvrdjenih se cita tek pod lock-om, da prijava koja stigne u medjuvremenu ne ostane preko kapaciteta
+    let result = async {
+        let mut tx = analytics::begin(&state.db, event_id).await?;
+        let confirmed = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM registrations WHERE event_id = $1 AND status = 'confirmed'",
+        )
+        .bind(event_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let excess = confirmed - req.capacity as i64;
+        if excess <= 0 {
+            let promoted = match &quotas {
+                Some(quotas) => promote_within_quotas(&mut tx, event_id, -excess, quotas).await?,
+                None => 0,
+            };
+            tx.commit().await?;
+            return Ok(Ok(json!({ "moved": 0, "promoted": promoted })));
+        }
+
+        let Some((status, default_notice)) = strategy else {
+            return Ok(Err(confirmed));
+        };
+        let notice = req
+            .notice
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .unwrap_or(default_notice);
+
+        let ids = sqlx::query_scalar::<_, Uuid>(
+            "SELECT id FROM registrations WHERE event_id = $1 AND status = 'confirmed'
+             ORDER BY created_at DESC LIMIT $2",
+        )
+        .bind(event_id)
+        .bind(excess)
+        .fetch_all(&mut *tx)
+        .await?;
+        let before = analytics::snapshot(&mut tx, &ids).await?;
+        let rows = sqlx::query(
+            "UPDATE registrations SET status = $2, status_note = $3,
+                 cancelled_at = CASE WHEN $2 = 'cancelled' THEN NOW() END
+             WHERE id = ANY($1)",
+        )
+        .bind(&ids)
+        .bind(status)
+        .bind(notice)
+        .execute(&mut *tx)
+        .await?;
+        analytics::record(&mut tx, event_id, &before, &ids).await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(Ok(json!({ "moved": rows.rows_affected(), "promoted": 0 })))
+    }
+    .await;
+
+    match result {
+        Ok(Ok(sync)) => (StatusCode::OK, Json(ApiResponse::success("Kapacitet uskladjen", sync))),
+        Ok(Err(confirmed)) => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(&format!(
+                "Kapacitet {} je manji od broja potvrdjenih prijava ({})",
+                req.capacity, confirmed
+            ))),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!("Greska: {}", e))),
+        ),
+    }
+}
+
+/// Internal: vraca prijave otkazane brisanjem dogadjaja (event-service pri vracanju iz kante).
+/// Redom prijavljivanja, dok ima mesta na dogadjaju i u kvoti tipa karte; ostale ostaju otkazane.
+pub async fn restore_registrations_for_event(
+    State(state): State<AppState>,
+    Path(event_id): Path<Uuid>,
+    Json(req): Json<RestoreRegistrationsRequest>,
+) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
+    let quotas: HashMap<Uuid, i64> = match fetch_ticket_types(&state, event_id).await {
+        Ok(list) => list.into_iter().map(|t| (t.id, t.quota as i64)).collect(),
+        Err(e) => {
+            tracing::error!("Ne mogu da ucitam tipove karata: {}", e);
+            return (StatusCode::SERVICE_UNAVAILABLE, Json(ApiResponse::error("Event servis nije dostupan")));
+        }
+    };
+
+    let result = async {
+        let mut tx = analytics::begin(&state.db, event_id).await?;
+        let candidates = sqlx::query_as::<_, Registration>(
+            "SELECT * FROM registrations WHERE event_id = $1 AND cancelled_with_event ORDER BY created_at",
+        )
+        .bind(event_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        // ko se u medjuvremenu prijavio (dogadjaj nije bio vidljiv, ali za svaki slucaj)
+        let confirmed = sqlx::query_as::<_, (Option<Uuid>, i64)>(
+            "SELECT ticket_type_id, COUNT(*) FROM registrations
+             WHERE event_id = $1 AND status = 'confirmed' GROUP BY ticket_type_id",
+        )
+        .bind(event_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut total: i64 = confirmed.iter().map(|(_, n)| n).sum();
+        let mut per_type: HashMap<Uuid, i64> = confirmed
+            .into_iter()
+            .filter_map(|(tt, n)| tt.map(|id| (id, n)))
+            .collect();
+
+        let mut restore = Vec::new();
+        for reg in &candidates {
+            if total >= req.capacity as i64 {
+                break;
+            }
+            // tip karte koji vise ne postoji nema kvotu
+            if let Some(tt) = reg.ticket_type_id {
+                let sold = per_type.entry(tt).or_insert(0);
+                if quotas.get(&tt).is_some_and(|quota| *sold >= *quota) {
+                    continue;
+                }
+                *sold += 1;
+            }
+            total += 1;
+            restore.push(reg.id);
+        }
+
+        let before = analytics::snapshot(&mut tx, &restore).await?;
+        sqlx::query(
+            "UPDATE registrations
+             SET status = CASE WHEN id = ANY($2) THEN 'confirmed' ELSE status END,
+                 cancelled_at = CASE WHEN id = ANY($2) THEN NULL ELSE cancelled_at END,
+                 cancelled_with_event = FALSE
+             WHERE event_id = $1 AND cancelled_with_event",
+        )
+        .bind(event_id)
+        .bind(&restore)
+        .execute(&mut *tx)
+        .await?;
+        analytics::record(&mut tx, event_id, &before, &restore).await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>((restore.len(), candidates.len() - restore.len()))
+    }
+    .await;
+
+    match result {
+        Ok((restored, not_restored)) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(
+                "Prijave vracene",
+                json!({ "restored": restored, "not_restored": not_restored }),
+            )),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!("Greska: {}", e))),
+        ),
+    }
+}
+
+/// Internal: prijava korisnika na dogadjaj (poziva event-service, npr. za prijavu na sesiju)
+pub async fn registration_for_user(
+    State(state): State<AppState>,
+    Path((event_id, user_id)): Path<(Uuid, Uuid)>,
+) -> (StatusCode, Json<ApiResponse<Registration>>) {
+    let result = sqlx::query_as::<_, Registration>(
+        "SELECT * FROM registrations WHERE event_id = $1 AND user_id = $2",
+    )
+    .bind(event_id)
+    .bind(user_id)
+    .fetch_optional(&state.db)
+    .await;
+
+    match result {
+        Ok(Some(reg)) => (
+            StatusCode::OK,
+            Json(ApiResponse::success("Prijava pronadjena", reg)),
+        ),
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error("Prijava ne postoji")),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!("Greska: {}", e))),
+        ),
+    }
+}
+
 /// get my registrations
 pub async fn my_registrations(
     headers: HeaderMap,
@@ -288,3 +833,682 @@ pub async fn get_ticket_qr(
         _ => (StatusCode::SERVICE_UNAVAILABLE, "QR servis nije dostupan").into_response(),
     }
 }
+
+// ---- Licni kalendar ----
+
+fn calendar_url(state: &AppState, token: &str) -> String {
+    format!("{}/calendar/{}.ics", state.public_api_url, token)
+}
+
+/// GET /registrations/calendar-token - postojeci token za pretplatu na kalendar
+pub async fn get_calendar_token(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+) -> (StatusCode, Json<ApiResponse<CalendarToken>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+
+    let result = sqlx::query_as::<_, CalendarToken>(
+        "SELECT token, created_at FROM calendar_tokens WHERE user_id = $1",
+    )
+    .bind(user_id)
+    .fetch_optional(&state.db)
+    .await;
+
+    match result {
+        Ok(Some(mut token)) => {
+            token.url = calendar_url(&state, &token.token);
+            (StatusCode::OK, Json(ApiResponse::success("Kalendar token", token)))
+        }
+        Ok(None) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Kalendar jos nije napravljen"))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// POST /registrations/calendar-token - pravi novi token (stari link prestaje da radi)
+pub async fn create_calendar_token(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+) -> (StatusCode, Json<ApiResponse<CalendarToken>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+
+    // 2 x 128 bita slucajnih - token je jedina zastita feed-a
+    let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+
+    let result = sqlx::query_as::<_, CalendarToken>(
+        "INSERT INTO calendar_tokens (user_id, token) VALUES ($1, $2)
+         ON CONFLICT (user_id) DO UPDATE SET token = EXCLUDED.token, created_at = NOW()
+         RETURNING token, created_at",
+    )
+    .bind(user_id)
+    .bind(&token)
+    .fetch_one(&state.db)
+    .await;
+
+    match result {
+        Ok(mut token) => {
+            token.url = calendar_url(&state, &token.token);
+            (StatusCode::CREATED, Json(ApiResponse::success("Kalendar token napravljen", token)))
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// DELETE /registrations/calendar-token - gasi pretplatu
+pub async fn revoke_calendar_token(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+
+    match sqlx::query("DELETE FROM calendar_tokens WHERE user_id = $1").bind(user_id).execute(&state.db).await {
+        Ok(_) => (StatusCode::OK, Json(ApiResponse::success("Kalendar ugasen", "ok".to_string()))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse::error(&format!("Greska: {}", e)))),
+    }
+}
+
+/// GET /calendar/:token(.ics) - javni .ics feed sa dogadjajima na koje je korisnik prijavljen.
+/// Pravi se pri svakom zahtevu, pa otkazane prijave odmah nestaju iz kalendara.
+pub async fn user_calendar(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+) -> Response {
+    let token = token.strip_suffix(".ics").unwrap_or(&token);
+
+    let user_id = sqlx::query_scalar::<_, Uuid>("SELECT user_id FROM calendar_tokens WHERE token = $1")
+        .bind(token)
+        .fetch_optional(&state.db)
+        .await;
+
+    let user_id = match user_id {
+        Ok(Some(id)) => id,
+        Ok(None) => return (StatusCode::NOT_FOUND, "Kalendar ne postoji").into_response(),
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Greska").into_response(),
+    };
+
+    let event_ids = sqlx::query_scalar::<_, Uuid>(
+        "SELECT event_id FROM registrations WHERE user_id = $1 AND status = 'confirmed'",
+    )
+    .bind(user_id)
+    .fetch_all(&state.db)
+    .await;
+
+    let event_ids = match event_ids {
+        Ok(ids) => ids,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Greska").into_response(),
+    };
+
+    let url = format!("{}/internal/events/calendar", state.event_service_url);
+    let resp = reqwest::Client::new()
+        .post(&url)
+        .json(&json!({ "ids": event_ids, "name": "Moji dogadjaji" }))
+        .send()
+        .await;
+
+    match resp {
+        Ok(resp) if resp.status().is_success() => {
+            let body = resp.text().await.unwrap_or_default();
+            (
+                StatusCode::OK,
+                [(axum::http::header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+                body,
+            )
+                .into_response()
+        }
+        _ => (StatusCode::SERVICE_UNAVAILABLE, "Event servis nije dostupan").into_response(),
+    }
+}
+
+// ---- Osoblje: spisak prijavljenih i check-in ----
+
+/// Proverava da li korisnik sme datu akciju na dogadjaju.
+/// Admin uvek sme, ostalima ulogu (organizator ili osoblje) daje event servis.
+async fn require_event_permission(
+    state: &AppState,
+    claims: &Claims,
+    event_id: Uuid,
+    permission: Permission,
+) -> Result<(), (StatusCode, String)> {
+    if claims.role == "Admin" {
+        return Ok(());
+    }
+
+    let url = format!("{}/internal/events/{}/access/{}", state.event_service_url, event_id, claims.sub);
+    let access = match reqwest::get(&url).await {
+        Ok(resp) => resp.json::<EventAccessResponse>().await.ok(),
+        Err(_) => return Err((StatusCode::SERVICE_UNAVAILABLE, "Event servis nije dostupan".to_string())),
+    };
+
+    let role = access.filter(|a| a.success).and_then(|a| a.data).and_then(|a| a.role);
+    match role {
+        Some(role) if role_allows(&role, permission) => Ok(()),
+        _ => Err((StatusCode::FORBIDDEN, "Nemate dozvolu za ovaj dogadjaj".to_string())),
+    }
+}
+
+/// Email-ovi korisnika iz auth servisa (ako nije dostupan, spisak ide bez email-ova)
+async fn fetch_emails(state: &AppState, ids: Vec<Uuid>) -> HashMap<Uuid, String> {
+    let url = format!("{}/internal/users/batch", state.auth_service_url);
+    let resp = reqwest::Client::new()
+        .post(&url)
+        .header(INTERNAL_TOKEN_HEADER, &state.internal_token)
+        .json(&json!({ "ids": ids }))
+        .send()
+        .await;
+
+    let users = match resp {
+        Ok(resp) => resp
+            .json::<UsersResponse>()
+            .await
+            .ok()
+            .filter(|r| r.success)
+            .and_then(|r| r.data),
+        Err(e) => {
+            tracing::warn!("Auth servis nije dostupan: {}", e);
+            None
+        }
+    };
+    users
+        .unwrap_or_default()
+        .into_iter()
+        .map(|u| (u.id, u.email))
+        .collect()
+}
+
+/// GET /events/:event_id/attendees - svi prijavljeni (organizator, Admin i osoblje)
+pub async fn event_attendees(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(event_id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<Vec<Attendee>>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    if let Err((status, msg)) = require_event_permission(&state, &claims, event_id, Permission::View).await {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+
+    let result = sqlx::query_as::<_, Registration>(
+        "SELECT * FROM registrations WHERE event_id = $1 ORDER BY created_at",
+    )
+    .bind(event_id)
+    .fetch_all(&state.db)
+    .await;
+
+    let regs = match result {
+        Ok(r) => r,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(&format!("Greska: {}", e))),
+            )
+        }
+    };
+
+    let mut emails = fetch_emails(&state, regs.iter().map(|r| r.user_id).collect()).await;
+    let attendees = regs
+        .into_iter()
+        .map(|registration| Attendee {
+            email: emails.remove(&registration.user_id),
+            registration,
+        })
+        .collect();
+
+    (StatusCode::OK, Json(ApiResponse::success("Prijavljeni", attendees)))
+}
+
+/// POST /events/:event_id/check-in - oznacava ulaznicu kao iskoriscenu (check-in osoblje)
+pub async fn check_in(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(event_id): Path<Uuid>,
+    Json(req): Json<CheckInRequest>,
+) -> (StatusCode, Json<ApiResponse<Registration>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    if let Err((status, msg)) = require_event_permission(&state, &claims, event_id, Permission::CheckIn).await {
+        return (status, Json(ApiResponse::error(&msg)));
+    }
+
+    let existing = sqlx::query_as::<_, Registration>(
+        "SELECT * FROM registrations WHERE event_id = $1 AND ticket_code = $2",
+    )
+    .bind(event_id)
+    .bind(req.ticket_code.trim())
+    .fetch_optional(&state.db)
+    .await;
+
+    let reg = match existing {
+        Ok(Some(r)) => r,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error("Ulaznica ne postoji za ovaj dogadjaj")),
+            )
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(&format!("Greska: {}", e))),
+            )
+        }
+    };
+
+    match reg.status.as_str() {
+        "confirmed" => {}
+        "waitlisted" => {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error("Prijava je na listi cekanja - ulaznica jos nije vazeca")),
+            )
+        }
+        _ => return (StatusCode::CONFLICT, Json(ApiResponse::error("Prijava je otkazana"))),
+    }
+    if let Some(at) = reg.checked_in_at {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(&format!("Ulaznica je vec iskoriscena ({})", at.format("%d.%m.%Y %H:%M")))),
+        );
+    }
+
+    // uslov u WHERE sprecava dupli check-in kad dvoje skenira istovremeno
+    let result = async {
+        let mut tx = analytics::begin(&state.db, event_id).await?;
+        let before = analytics::snapshot(&mut tx, &[reg.id]).await?;
+        let updated = sqlx::query_as::<_, Registration>(
+            "UPDATE registrations SET checked_in_at = NOW(), checked_in_by = $2
+             WHERE id = $1 AND checked_in_at IS NULL RETURNING *",
+        )
+        .bind(reg.id)
+        .bind(Uuid::parse_str(&claims.sub).unwrap_or_default())
+        .fetch_optional(&mut *tx)
+        .await?;
+        analytics::record(&mut tx, event_id, &before, &[reg.id]).await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(updated)
+    }
+    .await;
+
+    match result {
+        Ok(Some(updated)) => (StatusCode::OK, Json(ApiResponse::success("Check-in uspesan", updated))),
+        Ok(None) => (StatusCode::CONFLICT, Json(ApiResponse::error("Ulaznica je vec iskoriscena"))),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!("Greska: {}", e))),
+        ),
+    }
+}
+
+// ---- Analitika ----
+
+fn analytics_error(status: StatusCode, msg: &str) -> Response {
+    (status, Json(ApiResponse::<()>::error(msg))).into_response()
+}
+
+fn db_error(e: sqlx::Error) -> Response {
+    analytics_error(StatusCode::INTERNAL_SERVER_ERROR, &format!("Greska: {}", e))
+}
+
+/// Naslov, kapacitet i pocetak dogadjaja iz event servisa (filter je id= ili organizer_id=)
+async fn fetch_event_summaries(state: &AppState, filter: &str) -> Result<Vec<EventSummary>, String> {
+    let url = format!("{}/internal/events/summary?{}", state.event_service_url, filter);
+    let resp = reqwest::get(&url).await.map_err(|e| e.to_string())?;
+    let body = resp
+        .json::<EventSummaryResponse>()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    match body.data {
+        Some(list) if body.success => Ok(list),
+        _ => Err("Event servis vratio gresku".to_string()),
+    }
+}
+
+/// Zbirni brojevi iz event_stats; dogadjaji bez prijava nemaju red
+async fn load_totals(state: &AppState, ids: &[Uuid]) -> Result<HashMap<Uuid, StatsTotals>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (Uuid, i64, i64, i64, i64, i64)>(
+        "SELECT event_id, registrations::int8, confirmed::int8, waitlisted::int8, cancelled::int8, checked_in::int8
+         FROM event_stats WHERE event_id = ANY($1)",
+    )
+    .bind(ids)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(id, registrations, confirmed, waitlisted, cancelled, checked_in)| {
+            (id, StatsTotals { registrations, confirmed, waitlisted, cancelled, checked_in })
+        })
+        .collect())
+}
+
+async fn load_timeline(state: &AppState, ids: &[Uuid]) -> Result<Vec<DailyStats>, sqlx::Error> {
+    sqlx::query_as::<_, DailyStats>(
+        "SELECT day, SUM(registrations)::int8 AS registrations, SUM(cancellations)::int8 AS cancellations,
+                SUM(check_ins)::int8 AS check_ins
+         FROM event_daily_stats WHERE event_id = ANY($1)
+         GROUP BY day ORDER BY day",
+    )
+    .bind(ids)
+    .fetch_all(&state.db)
+    .await
+}
+
+/// Podela po tipu karte ("ticket_type") ili izvoru prijave ("source")
+async fn load_breakdown(state: &AppState, ids: &[Uuid], dimension: &str) -> Result<Vec<BreakdownStats>, sqlx::Error> {
+    let mut rows = sqlx::query_as::<_, BreakdownStats>(
+        "SELECT value AS key, SUM(registrations)::int8 AS registrations, SUM(confirmed)::int8 AS confirmed,
+                SUM(cancelled)::int8 AS cancelled, SUM(checked_in)::int8 AS checked_in
+         FROM event_breakdown_stats WHERE event_id = ANY($1) AND dimension = $2
+         GROUP BY value ORDER BY registrations DESC, value",
+    )
+    .bind(ids)
+    .bind(dimension)
+    .fetch_all(&state.db)
+    .await?;
+
+    for row in &mut rows {
+        row.label = row.key.clone();
+    }
+    Ok(rows)
+}
+
+fn timeline_csv(rows: &[DailyStats]) -> Result<String, String> {
+    analytics::to_csv(
+        &["day", "registrations", "cancellations", "check_ins"],
+        rows.iter()
+            .map(|d| vec![d.day.to_string(), d.registrations.to_string(), d.cancellations.to_string(), d.check_ins.to_string()])
+            .collect(),
+    )
+}
+
+fn breakdown_csv(rows: &[BreakdownStats]) -> Result<String, String> {
+    analytics::to_csv(
+        &["key", "label", "registrations", "confirmed", "cancelled", "checked_in"],
+        rows.iter()
+            .map(|b| {
+                vec![
+                    b.key.clone(),
+                    b.label.clone(),
+                    b.registrations.to_string(),
+                    b.confirmed.to_string(),
+                    b.cancelled.to_string(),
+                    b.checked_in.to_string(),
+                ]
+            })
+            .collect(),
+    )
+}
+
+fn csv_response(filename: &str, body: Result<String, String>) -> Response {
+    let body = match body {
+        Ok(b) => b,
+        Err(e) => return analytics_error(StatusCode::INTERNAL_SERVER_ERROR, &format!("Greska: {}", e)),
+    };
+    (
+        StatusCode::OK,
+        [
+            (axum::http::header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (axum::http::header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename)),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+/// Da li je trazen CSV; None za nepoznat format
+fn wants_csv(query: &AnalyticsQuery) -> Option<bool> {
+    match query.format.as_deref() {
+        None | Some("json") => Some(false),
+        Some("csv") => Some(true),
+        Some(_) => None,
+    }
+}
+
+/// GET /events/:event_id/analytics - prijave kroz vreme, stope otkazivanja, popunjenosti
+/// i dolaska, podela po tipu karte i izvoru. ?format=csv&table=timeline|ticket_types|sources
+pub async fn event_analytics(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(event_id): Path<Uuid>,
+    Query(query): Query<AnalyticsQuery>,
+) -> Response {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return analytics_error(status, &msg),
+    };
+    if let Err((status, msg)) = require_event_permission(&state, &claims, event_id, Permission::View).await {
+        return analytics_error(status, &msg);
+    }
+    let Some(csv) = wants_csv(&query) else {
+        return analytics_error(StatusCode::BAD_REQUEST, "Format mora biti json ili csv");
+    };
+
+    let summary = match fetch_event_summaries(&state, &format!("id={}", event_id)).await {
+        Ok(list) => match list.into_iter().next() {
+            Some(s) => s,
+            None => return analytics_error(StatusCode::NOT_FOUND, "Dogadjaj ne postoji"),
+        },
+        Err(e) => {
+            tracing::error!("Ne mogu da ucitam dogadjaj za analitiku: {}", e);
+            return analytics_error(StatusCode::SERVICE_UNAVAILABLE, "Event servis nije dostupan");
+        }
+    };
+
+    let ids = [event_id];
+    let totals = match load_totals(&state, &ids).await {
+        Ok(mut map) => map.remove(&event_id).unwrap_or_default(),
+        Err(e) => return db_error(e),
+    };
+    let timeline = match load_timeline(&state, &ids).await {
+        Ok(t) => t,
+        Err(e) => return db_error(e),
+    };
+    let mut ticket_types = match load_breakdown(&state, &ids, "ticket_type").await {
+        Ok(t) => t,
+        Err(e) => return db_error(e),
+    };
+    let sources = match load_breakdown(&state, &ids, "source").await {
+        Ok(s) => s,
+        Err(e) => return db_error(e),
+    };
+
+    // nazivi tipova karata; obrisan tip ostaje pod svojim id-em
+    let names: HashMap<String, String> = fetch_ticket_types(&state, event_id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|t| (t.id.to_string(), t.name))
+        .collect();
+    for row in &mut ticket_types {
+        if row.key.is_empty() {
+            row.label = "Bez tipa karte".to_string();
+        } else if let Some(name) = names.get(&row.key) {
+            row.label = name.clone();
+        }
+    }
+
+    if csv {
+        let table = query.table.as_deref().unwrap_or("timeline");
+        let body = match table {
+            "timeline" => timeline_csv(&timeline),
+            "ticket_types" => breakdown_csv(&ticket_types),
+            "sources" => breakdown_csv(&sources),
+            _ => return analytics_error(StatusCode::BAD_REQUEST, "Tabela mora biti timeline, ticket_types ili sources"),
+        };
+        return csv_response(&format!("analytics-{}-{}.csv", event_id, table), body);
+    }
+
+    let attended = summary.started.then_some((totals.confirmed, totals.checked_in));
+    let analytics = EventAnalytics {
+        event_id,
+        title: summary.title,
+        date_time: summary.date_time,
+        capacity: summary.capacity,
+        rates: analytics::rates(&totals, summary.capacity as i64, attended),
+        totals,
+        timeline,
+        ticket_types,
+        sources,
+    };
+    (StatusCode::OK, Json(ApiResponse::success("Analitika dogadjaja", analytics))).into_response()
+}
+
+/// GET /analytics/organizer - zbir za sve dogadjaje organizatora (Admin moze ?organizer_id=).
+/// ?format=csv&table=events|timeline|sources
+pub async fn organizer_analytics(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Query(query): Query<AnalyticsQuery>,
+) -> Response {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return analytics_error(status, &msg),
+    };
+    let own_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+    let organizer_id = match query.organizer_id {
+        Some(id) if id != own_id && claims.role != "Admin" => {
+            return analytics_error(StatusCode::FORBIDDEN, "Samo Admin moze da vidi analitiku drugih organizatora")
+        }
+        Some(id) => id,
+        None => own_id,
+    };
+    let Some(csv) = wants_csv(&query) else {
+        return analytics_error(StatusCode::BAD_REQUEST, "Format mora biti json ili csv");
+    };
+
+    let summaries = match fetch_event_summaries(&state, &format!("organizer_id={}", organizer_id)).await {
+        Ok(list) => list,
+        Err(e) => {
+            tracing::error!("Ne mogu da ucitam dogadjaje za analitiku: {}", e);
+            return analytics_error(StatusCode::SERVICE_UNAVAILABLE, "Event servis nije dostupan");
+        }
+    };
+    let ids: Vec<Uuid> = summaries.iter().map(|s| s.id).collect();
+
+    let mut totals_by_event = match load_totals(&state, &ids).await {
+        Ok(map) => map,
+        Err(e) => return db_error(e),
+    };
+    let timeline = match load_timeline(&state, &ids).await {
+        Ok(t) => t,
+        Err(e) => return db_error(e),
+    };
+    let sources = match load_breakdown(&state, &ids, "source").await {
+        Ok(s) => s,
+        Err(e) => return db_error(e),
+    };
+
+    let mut totals = StatsTotals::default();
+    let mut capacity = 0i64;
+    let mut attended: Option<(i64, i64)> = None;
+    let mut events = Vec::with_capacity(summaries.len());
+    for summary in summaries {
+        let t = totals_by_event.remove(&summary.id).unwrap_or_default();
+        totals.registrations += t.registrations;
+        totals.confirmed += t.confirmed;
+        totals.waitlisted += t.waitlisted;
+        totals.cancelled += t.cancelled;
+        totals.checked_in += t.checked_in;
+        capacity += summary.capacity as i64;
+        if summary.started {
+            let (confirmed, checked_in) = attended.unwrap_or_default();
+            attended = Some((confirmed + t.confirmed, checked_in + t.checked_in));
+        }
+
+        let event_attended = summary.started.then_some((t.confirmed, t.checked_in));
+        events.push(EventAnalyticsRow {
+            event_id: summary.id,
+            title: summary.title,
+            date_time: summary.date_time,
+            capacity: summary.capacity,
+            rates: analytics::rates(&t, summary.capacity as i64, event_attended),
+            totals: t,
+        });
+    }
+
+    if csv {
+        let table = query.table.as_deref().unwrap_or("events");
+        let body = match table {
+            "events" => analytics::to_csv(
+                &[
+                    "event_id", "title", "date_time", "capacity", "registrations", "confirmed", "waitlisted",
+                    "cancelled", "checked_in", "cancellation_rate", "fill_rate", "check_in_rate", "no_show_rate",
+                ],
+                events
+                    .iter()
+                    .map(|e| {
+                        vec![
+                            e.event_id.to_string(),
+                            e.title.clone(),
+                            e.date_time.format("%Y-%m-%d %H:%M").to_string(),
+                            e.capacity.to_string(),
+                            e.totals.registrations.to_string(),
+                            e.totals.confirmed.to_string(),
+                            e.totals.waitlisted.to_string(),
+                            e.totals.cancelled.to_string(),
+                            e.totals.checked_in.to_string(),
+                            e.rates.cancellation_rate.to_string(),
+                            analytics::csv_rate(e.rates.fill_rate),
+                            analytics::csv_rate(e.rates.check_in_rate),
+                            analytics::csv_rate(e.rates.no_show_rate),
+                        ]
+                    })
+                    .collect(),
+            ),
+            "timeline" => timeline_csv(&timeline),
+            "sources" => breakdown_csv(&sources),
+            _ => return analytics_error(StatusCode::BAD_REQUEST, "Tabela mora biti events, timeline ili sources"),
+        };
+        return csv_response(&format!("analytics-organizer-{}.csv", table), body);
+    }
+
+    let analytics = OrganizerAnalytics {
+        organizer_id,
+        capacity,
+        rates: analytics::rates(&totals, capacity, attended),
+        totals,
+        events,
+        timeline,
+        sources,
+    };
+    (StatusCode::OK, Json(ApiResponse::success("Analitika organizatora", analytics))).into_response()
+}
+
+// ---- Uslovi za prijavu ----
+
+/// GET /registrations/eligibility?event_id= - da li ulogovani korisnik moze da se prijavi i zasto ne
+pub async fn check_eligibility(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Query(query): Query<EligibilityQuery>,
+) -> (StatusCode, Json<ApiResponse<EligibilityCheck>>) {
+    let claims = match extract_claims(&headers, &state.jwt_secret) {
+        Ok(c) => c,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(&msg))),
+    };
+    let user_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
+
+    match eligibility::check(&state, query.event_id, user_id, &claims.email).await {
+        Ok(Some(check)) => (StatusCode::OK, Json(ApiResponse::success("Provera uslova", check))),
+        Ok(None) => (StatusCode::NOT_FOUND, Json(ApiResponse::error("Dogadjaj ne postoji"))),
+        Err(e) => {
+            tracing::error!("Ne mogu da proverim uslove za prijavu: {}", e);
+            (StatusCode::SERVICE_UNAVAILABLE, Json(ApiResponse::error("Provera uslova za prijavu nije uspela")))
+        }
+    }
+}
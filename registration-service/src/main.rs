@@ -1,5 +1,7 @@
 // Registration servis - prijave na dogadjaje, karte, QR kodovi, analitike
 
+mod analytics;
+mod eligibility;
 mod db;
 mod handlers;
 mod models;
@@ -12,7 +14,13 @@ pub struct AppState {
     pub db: PgPool,
     pub jwt_secret: String,
     pub event_service_url: String,
+    /// email-ovi prijavljenih za spisak osoblja
+    pub auth_service_url: String,
+    /// servisni token za /internal/* rute auth servisa
+    pub internal_token: String,
     pub qr_service_url: String,
+    /// javna adresa API-ja (za link ka licnom kalendaru)
+    pub public_api_url: String,
 }
 
 #[tokio::main]
@@ -29,23 +37,53 @@ async fn main() {
     let event_service_url = std::env::var("EVENT_SERVICE_URL")
         .unwrap_or_else(|_| "http://localhost:3003".to_string());
 
+    let auth_service_url = std::env::var("AUTH_SERVICE_URL")
+        .unwrap_or_else(|_| "http://localhost:3001".to_string());
+
+    let internal_token = std::env::var("INTERNAL_API_TOKEN")
+        .expect("INTERNAL_API_TOKEN mora biti postavljen u .env");
+
     let qr_service_url = std::env::var("QR_SERVICE_URL")
         .unwrap_or_else(|_| "http://localhost:3005".to_string());
 
+    let public_api_url = std::env::var("PUBLIC_API_URL")
+        .unwrap_or_else(|_| "http://localhost:3000/api".to_string())
+        .trim_end_matches('/')
+        .to_string();
+
     let pool = db::create_pool(&database_url).await;
 
+    // stare prijave bez zbirne statistike
+    tokio::spawn(analytics::backfill(pool.clone()));
+
     let state = AppState {
         db: pool,
         jwt_secret,
         event_service_url,
+        auth_service_url,
+        internal_token,
         qr_service_url,
+        public_api_url,
     };
 
     let app = Router::new()
         .route("/health", get(handlers::health_check))
         .route("/registrations", post(handlers::register_for_event))
         .route("/registrations/my", get(handlers::my_registrations))
+        .route("/registrations/eligibility", get(handlers::check_eligibility))
+        .route("/registrations/calendar-token", get(handlers::get_calendar_token).post(handlers::create_calendar_token).delete(handlers::revoke_calendar_token))
+        .route("/calendar/:token", get(handlers::user_calendar))
+        .route("/events/:event_id/attendees", get(handlers::event_attendees))
+        .route("/events/:event_id/check-in", post(handlers::check_in))
+        .route("/events/:event_id/analytics", get(handlers::event_analytics))
+        .route("/analytics/organizer", get(handlers::organizer_analytics))
         .route("/internal/event/:event_id/cancel-registrations", post(handlers::cancel_registrations_for_event))
+        .route("/internal/event/:event_id/restore-registrations", post(handlers::restore_registrations_for_event))
+        .route("/internal/events/counts", post(handlers::registration_counts_bulk))
+        .route("/internal/registrations/activity", get(handlers::registration_activity))
+        .route("/internal/event/:event_id/counts", get(handlers::registration_counts))
+        .route("/internal/event/:event_id/capacity", post(handlers::apply_capacity_change))
+        .route("/internal/event/:event_id/user/:user_id", get(handlers::registration_for_user))
         .route("/registrations/:id", delete(handlers::cancel_registration))
         .route("/registrations/:id/qr", get(handlers::get_ticket_qr))
         .with_state(state);
@@ -2,7 +2,10 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime};
+use std::collections::BTreeMap;
+use shared::eligibility::EligibilityRules;
+use shared::staff::EventAccess;
 
 /// register to event
 #[derive(Debug, FromRow, Serialize)]
@@ -11,14 +14,51 @@ pub struct Registration {
     pub event_id: Uuid,
     pub user_id: Uuid,
     pub ticket_code: String,
-    pub status: String,        // confirmed ili cancelled
+    pub status: String,        // confirmed, waitlisted ili cancelled
     pub created_at: NaiveDateTime,
+    pub ticket_type_id: Option<Uuid>,
+    pub checked_in_at: Option<NaiveDateTime>,
+    pub checked_in_by: Option<Uuid>,
+    /// zasto je prijava premestena na listu cekanja ili otkazana
+    pub status_note: Option<String>,
+    /// odakle je prijava dosla (ref sa linka), podrazumevano "direct"
+    pub source: String,
+    pub cancelled_at: Option<NaiveDateTime>,
+}
+
+/// Prijava sa email-om korisnika (spisak prijavljenih za osoblje)
+#[derive(Debug, Serialize)]
+pub struct Attendee {
+    #[serde(flatten)]
+    pub registration: Registration,
+    pub email: Option<String>,
+}
+
+/// Check-in po kodu sa ulaznice
+#[derive(Debug, Deserialize)]
+pub struct CheckInRequest {
+    pub ticket_code: String,
 }
 
 /// req for registration
 #[derive(Debug, Deserialize)]
 pub struct RegisterRequest {
     pub event_id: Uuid,
+    /// obavezan ako dogadjaj ima tipove karata
+    pub ticket_type_id: Option<Uuid>,
+    /// za privatne dogadjaje bez pozivnice
+    pub access_code: Option<String>,
+    /// izvor prijave za analitiku (?ref= ili utm_source sa linka)
+    pub source: Option<String>,
+}
+
+/// token za licni kalendar; url je adresa za pretplatu u kalendar aplikaciji
+#[derive(Debug, FromRow, Serialize)]
+pub struct CalendarToken {
+    pub token: String,
+    #[sqlx(default)]
+    pub url: String,
+    pub created_at: NaiveDateTime,
 }
 
 /// Podaci o eventu koje dobijamo od event servisa
@@ -34,12 +74,261 @@ pub struct EventData {
 #[derive(Debug, Deserialize)]
 pub struct EventServiceResponse {
     pub success: bool,
+    pub message: Option<String>,
     pub data: Option<EventData>,
 }
 
+/// Tip karte koji dobijamo od event servisa
+#[derive(Debug, Deserialize)]
+pub struct TicketTypeData {
+    pub id: Uuid,
+    pub name: String,
+    pub quota: i32,
+    pub sales_start: Option<NaiveDateTime>,
+    pub sales_end: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TicketTypesResponse {
+    pub success: bool,
+    pub data: Option<Vec<TicketTypeData>>,
+}
+
 /// Broj registracija 
 #[derive(Debug, FromRow)]
 pub struct CountResult {
     pub count: Option<i64>,
 }
 
+/// Filter za broj prijava: sa ticket_type_id broje se samo prijave tog tipa karte
+#[derive(Debug, Deserialize)]
+pub struct CountsQuery {
+    pub ticket_type_id: Option<Uuid>,
+}
+
+/// Korisnik iz auth servisa
+#[derive(Debug, Deserialize)]
+pub struct UserData {
+    pub id: Uuid,
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UsersResponse {
+    pub success: bool,
+    pub data: Option<Vec<UserData>>,
+}
+
+/// Uloga korisnika na dogadjaju iz event servisa
+#[derive(Debug, Deserialize)]
+pub struct EventAccessResponse {
+    pub success: bool,
+    pub data: Option<EventAccess>,
+}
+
+/// Vracanje prijava posle vracanja dogadjaja iz kante (kapacitet salje event-service)
+#[derive(Debug, Deserialize)]
+pub struct RestoreRegistrationsRequest {
+    pub capacity: i32,
+}
+
+/// Broj prijava za vise dogadjaja odjednom (event-service, za dostupnost u listi)
+#[derive(Debug, Deserialize)]
+pub struct EventCountsRequest {
+    pub event_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct EventCounts {
+    pub event_id: Uuid,
+    pub confirmed: i64,
+    pub waitlisted: i64,
+}
+
+/// Filter za aktivnost: bez user_id vraca prijave svih korisnika (preporuke u event-service-u)
+#[derive(Debug, Deserialize)]
+pub struct ActivityQuery {
+    pub user_id: Option<Uuid>,
+    /// koliko dana unazad (po datumu prijave), podrazumevano 365
+    pub since_days: Option<i64>,
+}
+
+/// Par korisnik-dogadjaj iz aktivnih prijava
+#[derive(Debug, Serialize, FromRow)]
+pub struct RegistrationActivity {
+    pub user_id: Uuid,
+    pub event_id: Uuid,
+}
+
+/// Novi kapacitet dogadjaja - visak prijava ide na listu cekanja ili se otkazuje,
+/// a ako ima mesta, lista cekanja se prebacuje u potvrdjene
+#[derive(Debug, Deserialize)]
+pub struct CapacityChangeRequest {
+    pub capacity: i32,
+    /// "waitlist" ili "cancel"; bez toga se smanjenje ispod broja prijava odbija
+    pub strategy: Option<String>,
+    pub notice: Option<String>,
+}
+
+// ---- Analitika ----
+
+/// Query za analitiku: format=csv vraca tabelu (table=timeline|ticket_types|sources,
+/// za organizatora jos i events), organizer_id samo za Admin-a
+#[derive(Debug, Deserialize)]
+pub struct AnalyticsQuery {
+    pub format: Option<String>,
+    pub table: Option<String>,
+    pub organizer_id: Option<Uuid>,
+}
+
+/// Osnovni podaci o dogadjaju iz event servisa (naslov, kapacitet, da li je poceo)
+#[derive(Debug, Deserialize)]
+pub struct EventSummary {
+    pub id: Uuid,
+    pub title: String,
+    pub date_time: NaiveDateTime,
+    pub capacity: i32,
+    pub started: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EventSummaryResponse {
+    pub success: bool,
+    pub data: Option<Vec<EventSummary>>,
+}
+
+/// Zbirni brojevi prijava (iz event_stats ili zbir za organizatora)
+#[derive(Debug, Default, Clone, Serialize, FromRow)]
+pub struct StatsTotals {
+    pub registrations: i64,
+    pub confirmed: i64,
+    pub waitlisted: i64,
+    pub cancelled: i64,
+    pub checked_in: i64,
+}
+
+/// Stope u odnosu na prijave i kapacitet; no_show_rate tek kad dogadjaj pocne
+#[derive(Debug, Serialize)]
+pub struct StatsRates {
+    pub cancellation_rate: f64,
+    pub fill_rate: Option<f64>,
+    pub check_in_rate: Option<f64>,
+    pub no_show_rate: Option<f64>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct DailyStats {
+    pub day: NaiveDate,
+    pub registrations: i64,
+    pub cancellations: i64,
+    pub check_ins: i64,
+}
+
+/// Red podele po tipu karte ili izvoru; label je naziv tipa karte
+#[derive(Debug, Serialize, FromRow)]
+pub struct BreakdownStats {
+    pub key: String,
+    #[sqlx(default)]
+    pub label: String,
+    pub registrations: i64,
+    pub confirmed: i64,
+    pub cancelled: i64,
+    pub checked_in: i64,
+}
+
+/// GET /events/:event_id/analytics
+#[derive(Debug, Serialize)]
+pub struct EventAnalytics {
+    pub event_id: Uuid,
+    pub title: String,
+    pub date_time: NaiveDateTime,
+    pub capacity: i32,
+    #[serde(flatten)]
+    pub totals: StatsTotals,
+    #[serde(flatten)]
+    pub rates: StatsRates,
+    pub timeline: Vec<DailyStats>,
+    pub ticket_types: Vec<BreakdownStats>,
+    pub sources: Vec<BreakdownStats>,
+}
+
+/// Jedan dogadjaj u pregledu organizatora
+#[derive(Debug, Serialize)]
+pub struct EventAnalyticsRow {
+    pub event_id: Uuid,
+    pub title: String,
+    pub date_time: NaiveDateTime,
+    pub capacity: i32,
+    #[serde(flatten)]
+    pub totals: StatsTotals,
+    #[serde(flatten)]
+    pub rates: StatsRates,
+}
+
+/// GET /analytics/organizer - zbir za sve dogadjaje organizatora
+#[derive(Debug, Serialize)]
+pub struct OrganizerAnalytics {
+    pub organizer_id: Uuid,
+    pub capacity: i64,
+    #[serde(flatten)]
+    pub totals: StatsTotals,
+    #[serde(flatten)]
+    pub rates: StatsRates,
+    pub events: Vec<EventAnalyticsRow>,
+    pub timeline: Vec<DailyStats>,
+    pub sources: Vec<BreakdownStats>,
+}
+
+// ---- Uslovi za prijavu ----
+
+/// Uslovi iz event servisa (/internal/events/:id/eligibility)
+#[derive(Debug, Deserialize)]
+pub struct EventEligibilityData {
+    pub rules: EligibilityRules,
+    pub date_time: NaiveDateTime,
+    #[serde(default)]
+    pub series_event_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EventEligibilityResponse {
+    pub success: bool,
+    pub data: Option<EventEligibilityData>,
+}
+
+/// Profil iz auth servisa (/internal/users/:id/profile)
+#[derive(Debug, Deserialize)]
+pub struct ProfileData {
+    pub full_name: Option<String>,
+    pub date_of_birth: Option<NaiveDate>,
+    pub organization: Option<String>,
+    pub phone: Option<String>,
+    #[serde(default)]
+    pub attributes: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProfileResponse {
+    pub success: bool,
+    pub data: Option<ProfileData>,
+}
+
+/// Razlog zbog kog korisnik ne moze da se prijavi
+#[derive(Debug, Serialize)]
+pub struct IneligibilityReason {
+    /// date_of_birth_missing, too_young, email_domain, missing_attributes, series_limit
+    pub code: &'static str,
+    pub message: String,
+}
+
+/// GET /registrations/eligibility?event_id=
+#[derive(Debug, Deserialize)]
+pub struct EligibilityQuery {
+    pub event_id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EligibilityCheck {
+    pub eligible: bool,
+    pub reasons: Vec<IneligibilityReason>,
+}
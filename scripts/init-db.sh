@@ -19,26 +19,339 @@ psql -v ON_ERROR_STOP=1 --username "$POSTGRES_USER" --dbname auth_db <<-EOSQL
         email VARCHAR(255) UNIQUE NOT NULL,
         password_hash TEXT NOT NULL,
         role VARCHAR(50) NOT NULL DEFAULT 'User',
-        created_at TIMESTAMP NOT NULL DEFAULT NOW()
+        created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        full_name VARCHAR(255),
+        date_of_birth DATE,
+        organization VARCHAR(255),
+        phone VARCHAR(50),
+        attributes JSONB NOT NULL DEFAULT '{}'
     );
 EOSQL
 
 echo "Pokrecem migracije za event_db..."
 psql -v ON_ERROR_STOP=1 --username "$POSTGRES_USER" --dbname event_db <<-EOSQL
+    CREATE TABLE IF NOT EXISTS event_series (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+        organizer_id UUID NOT NULL,
+        title VARCHAR(255) NOT NULL,
+        description TEXT NOT NULL,
+        location VARCHAR(255) NOT NULL,
+        capacity INT NOT NULL DEFAULT 100,
+        category VARCHAR(100) NOT NULL DEFAULT 'Ostalo',
+        image_url TEXT,
+        latitude DOUBLE PRECISION,
+        longitude DOUBLE PRECISION,
+        dtstart TIMESTAMP NOT NULL,
+        rrule TEXT NOT NULL,
+        exdates TIMESTAMP[] NOT NULL DEFAULT '{}',
+        eligibility JSONB,
+        created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+    );
+
     CREATE TABLE IF NOT EXISTS events (
         id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
         organizer_id UUID NOT NULL,
         title VARCHAR(255) NOT NULL,
         description TEXT NOT NULL,
+        description_html TEXT NOT NULL DEFAULT '',
         location VARCHAR(255) NOT NULL,
         date_time TIMESTAMP NOT NULL,
         capacity INT NOT NULL DEFAULT 100,
         category VARCHAR(100) NOT NULL DEFAULT 'Ostalo',
         image_url TEXT,
+        image_thumbnails JSONB,
+        image_keys TEXT[] NOT NULL DEFAULT '{}',
+        latitude DOUBLE PRECISION,
+        longitude DOUBLE PRECISION,
+        series_id UUID REFERENCES event_series(id) ON DELETE SET NULL,
+        occurrence_date TIMESTAMP,
+        is_exception BOOLEAN NOT NULL DEFAULT FALSE,
+        status VARCHAR(20) NOT NULL DEFAULT 'published',
+        visibility VARCHAR(20) NOT NULL DEFAULT 'public',
+        slug VARCHAR(64) NOT NULL UNIQUE DEFAULT replace(gen_random_uuid()::text, '-', ''),
+        access_code VARCHAR(64),
+        version INT NOT NULL DEFAULT 1,
+        deleted_at TIMESTAMP,
+        deleted_by UUID,
+        rating_avg DOUBLE PRECISION,
+        rating_count INT NOT NULL DEFAULT 0,
+        featured BOOLEAN NOT NULL DEFAULT FALSE,
+        featured_priority INT NOT NULL DEFAULT 0,
         created_at TIMESTAMP NOT NULL DEFAULT NOW(),
-        updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+        updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        CONSTRAINT events_series_occurrence_key
+            UNIQUE (series_id, occurrence_date) DEFERRABLE INITIALLY IMMEDIATE
     );
     CREATE INDEX IF NOT EXISTS idx_events_category ON events(category);
+    CREATE INDEX IF NOT EXISTS idx_events_coordinates ON events(latitude, longitude);
+    CREATE INDEX IF NOT EXISTS idx_events_status ON events(status);
+    CREATE INDEX IF NOT EXISTS idx_events_deleted ON events(deleted_at) WHERE deleted_at IS NOT NULL;
+    CREATE INDEX IF NOT EXISTS idx_events_featured ON events(featured_priority DESC, date_time) WHERE featured;
+
+    CREATE TABLE IF NOT EXISTS event_templates (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+        organizer_id UUID NOT NULL,
+        name VARCHAR(100) NOT NULL,
+        title VARCHAR(255),
+        description TEXT,
+        location VARCHAR(255),
+        capacity INT,
+        category VARCHAR(100),
+        image_url TEXT,
+        image_thumbnails JSONB,
+        image_keys TEXT[] NOT NULL DEFAULT '{}',
+        latitude DOUBLE PRECISION,
+        longitude DOUBLE PRECISION,
+        created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        UNIQUE (organizer_id, name)
+    );
+
+    CREATE TABLE IF NOT EXISTS speakers (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+        event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+        name VARCHAR(255) NOT NULL,
+        bio TEXT NOT NULL DEFAULT '',
+        photo_url TEXT,
+        created_at TIMESTAMP NOT NULL DEFAULT NOW()
+    );
+    CREATE TABLE IF NOT EXISTS sessions (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+        event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+        title VARCHAR(255) NOT NULL,
+        abstract TEXT NOT NULL DEFAULT '',
+        room VARCHAR(100),
+        track VARCHAR(100),
+        start_time TIMESTAMP NOT NULL,
+        end_time TIMESTAMP NOT NULL,
+        capacity INT,
+        created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        CHECK (end_time > start_time)
+    );
+    CREATE TABLE IF NOT EXISTS session_speakers (
+        session_id UUID NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+        speaker_id UUID NOT NULL REFERENCES speakers(id) ON DELETE CASCADE,
+        PRIMARY KEY (session_id, speaker_id)
+    );
+    CREATE TABLE IF NOT EXISTS session_attendees (
+        session_id UUID NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+        user_id UUID NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        PRIMARY KEY (session_id, user_id)
+    );
+    CREATE INDEX IF NOT EXISTS idx_sessions_event ON sessions(event_id, start_time);
+    CREATE INDEX IF NOT EXISTS idx_speakers_event ON speakers(event_id);
+
+    CREATE TABLE IF NOT EXISTS ticket_types (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+        event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+        name VARCHAR(100) NOT NULL,
+        description TEXT NOT NULL DEFAULT '',
+        price_cents BIGINT NOT NULL DEFAULT 0 CHECK (price_cents >= 0),
+        currency VARCHAR(3) NOT NULL DEFAULT 'RSD',
+        quota INT NOT NULL CHECK (quota > 0),
+        sales_start TIMESTAMP,
+        sales_end TIMESTAMP,
+        visibility VARCHAR(20) NOT NULL DEFAULT 'public',
+        sort_order INT NOT NULL DEFAULT 0,
+        created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        UNIQUE (event_id, name)
+    );
+    CREATE INDEX IF NOT EXISTS idx_ticket_types_event ON ticket_types(event_id);
+
+    CREATE TABLE IF NOT EXISTS event_staff (
+        event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+        user_id UUID NOT NULL,
+        email VARCHAR(255) NOT NULL,
+        role VARCHAR(20) NOT NULL,
+        added_by UUID NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        PRIMARY KEY (event_id, user_id)
+    );
+    CREATE INDEX IF NOT EXISTS idx_event_staff_user ON event_staff(user_id);
+
+    CREATE TABLE IF NOT EXISTS event_revisions (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+        event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+        version INT NOT NULL,
+        author_id UUID,
+        changes JSONB,
+        snapshot JSONB NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        UNIQUE (event_id, version)
+    );
+
+    CREATE TABLE IF NOT EXISTS event_invitations (
+        event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+        email VARCHAR(255) NOT NULL,
+        invited_by UUID NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        PRIMARY KEY (event_id, email)
+    );
+
+    CREATE TABLE IF NOT EXISTS categories (
+        slug VARCHAR(100) PRIMARY KEY,
+        name VARCHAR(100) NOT NULL UNIQUE,
+        translations JSONB NOT NULL DEFAULT '{}',
+        sort_order INT NOT NULL DEFAULT 0,
+        created_at TIMESTAMP NOT NULL DEFAULT NOW()
+    );
+    INSERT INTO categories (slug, name, translations, sort_order) VALUES
+        ('konferencija', 'Konferencija', '{"en": "Conference"}', 10),
+        ('radionica', 'Radionica', '{"en": "Workshop"}', 20),
+        ('koncert', 'Koncert', '{"en": "Concert"}', 30),
+        ('meetup', 'Meetup', '{"en": "Meetup"}', 40),
+        ('ostalo', 'Ostalo', '{"en": "Other"}', 1000)
+    ON CONFLICT DO NOTHING;
+
+    CREATE TABLE IF NOT EXISTS tags (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+        slug VARCHAR(50) NOT NULL UNIQUE,
+        name VARCHAR(50) NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS event_tags (
+        event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+        tag_id UUID NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+        PRIMARY KEY (event_id, tag_id)
+    );
+    CREATE INDEX IF NOT EXISTS idx_event_tags_tag ON event_tags(tag_id);
+
+    CREATE TABLE IF NOT EXISTS event_reviews (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+        event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+        user_id UUID NOT NULL,
+        author_name VARCHAR(100) NOT NULL,
+        rating SMALLINT NOT NULL CHECK (rating BETWEEN 1 AND 5),
+        body TEXT NOT NULL DEFAULT '',
+        reply TEXT,
+        replied_by UUID,
+        replied_at TIMESTAMP,
+        hidden BOOLEAN NOT NULL DEFAULT FALSE,
+        hidden_reason TEXT,
+        report_count INT NOT NULL DEFAULT 0,
+        created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        UNIQUE (event_id, user_id)
+    );
+    CREATE INDEX IF NOT EXISTS idx_event_reviews_reported ON event_reviews(report_count) WHERE report_count > 0;
+
+    CREATE TABLE IF NOT EXISTS review_reports (
+        review_id UUID NOT NULL REFERENCES event_reviews(id) ON DELETE CASCADE,
+        user_id UUID NOT NULL,
+        reason TEXT NOT NULL DEFAULT '',
+        created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        PRIMARY KEY (review_id, user_id)
+    );
+
+    CREATE TABLE IF NOT EXISTS event_questions (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+        event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+        parent_id UUID REFERENCES event_questions(id) ON DELETE CASCADE,
+        user_id UUID NOT NULL,
+        author_name VARCHAR(100) NOT NULL,
+        body TEXT NOT NULL,
+        from_staff BOOLEAN NOT NULL DEFAULT FALSE,
+        answered_at TIMESTAMP,
+        pinned BOOLEAN NOT NULL DEFAULT FALSE,
+        hidden BOOLEAN NOT NULL DEFAULT FALSE,
+        upvotes INT NOT NULL DEFAULT 0,
+        deleted_at TIMESTAMP,
+        created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+    );
+
+    CREATE INDEX IF NOT EXISTS idx_event_questions_event ON event_questions(event_id, parent_id);
+    CREATE INDEX IF NOT EXISTS idx_event_questions_user ON event_questions(user_id, created_at);
+
+    CREATE TABLE IF NOT EXISTS question_votes (
+        question_id UUID NOT NULL REFERENCES event_questions(id) ON DELETE CASCADE,
+        user_id UUID NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        PRIMARY KEY (question_id, user_id)
+    );
+
+    CREATE TABLE IF NOT EXISTS question_posts (
+        user_id UUID NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT NOW()
+    );
+
+    CREATE INDEX IF NOT EXISTS idx_question_posts_user ON question_posts(user_id, created_at);
+
+    CREATE TABLE IF NOT EXISTS event_bookmarks (
+        user_id UUID NOT NULL,
+        event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+        created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        PRIMARY KEY (user_id, event_id)
+    );
+
+    CREATE TABLE IF NOT EXISTS organizer_follows (
+        user_id UUID NOT NULL,
+        organizer_id UUID NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        PRIMARY KEY (user_id, organizer_id)
+    );
+
+    CREATE TABLE IF NOT EXISTS category_follows (
+        user_id UUID NOT NULL,
+        category_slug VARCHAR(100) NOT NULL REFERENCES categories(slug) ON UPDATE CASCADE ON DELETE CASCADE,
+        created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        PRIMARY KEY (user_id, category_slug)
+    );
+
+    CREATE TABLE IF NOT EXISTS event_similarity (
+        event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+        similar_event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+        score DOUBLE PRECISION NOT NULL,
+        PRIMARY KEY (event_id, similar_event_id)
+    );
+
+    CREATE TABLE IF NOT EXISTS event_popularity (
+        event_id UUID PRIMARY KEY REFERENCES events(id) ON DELETE CASCADE,
+        registrations INT NOT NULL DEFAULT 0,
+        bookmarks INT NOT NULL DEFAULT 0,
+        score DOUBLE PRECISION NOT NULL DEFAULT 0,
+        computed_at TIMESTAMP NOT NULL DEFAULT NOW()
+    );
+
+    CREATE TABLE IF NOT EXISTS event_translations (
+        event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+        locale VARCHAR(10) NOT NULL,
+        title VARCHAR(255) NOT NULL,
+        description TEXT,
+        description_html TEXT,
+        location VARCHAR(255),
+        updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        PRIMARY KEY (event_id, locale)
+    );
+
+    CREATE TABLE IF NOT EXISTS event_eligibility (
+        event_id UUID PRIMARY KEY REFERENCES events(id) ON DELETE CASCADE,
+        rules JSONB NOT NULL,
+        updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+    );
+
+    CREATE TABLE IF NOT EXISTS collections (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+        slug VARCHAR(100) NOT NULL UNIQUE,
+        title VARCHAR(255) NOT NULL,
+        description TEXT NOT NULL DEFAULT '',
+        active_from TIMESTAMP,
+        active_until TIMESTAMP,
+        created_by UUID NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+    );
+
+    CREATE TABLE IF NOT EXISTS collection_events (
+        collection_id UUID NOT NULL REFERENCES collections(id) ON DELETE CASCADE,
+        event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+        position INT NOT NULL,
+        PRIMARY KEY (collection_id, event_id)
+    );
 EOSQL
 
 echo "Pokrecem migracije za registration_db..."
@@ -50,10 +363,54 @@ psql -v ON_ERROR_STOP=1 --username "$POSTGRES_USER" --dbname registration_db <<-
         ticket_code VARCHAR(50) UNIQUE NOT NULL,
         status VARCHAR(20) NOT NULL DEFAULT 'confirmed',
         created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+        ticket_type_id UUID,
+        checked_in_at TIMESTAMP,
+        checked_in_by UUID,
+        cancelled_with_event BOOLEAN NOT NULL DEFAULT FALSE,
+        status_note TEXT,
+        source VARCHAR(50) NOT NULL DEFAULT 'direct',
+        cancelled_at TIMESTAMP,
         UNIQUE(event_id, user_id)
     );
     CREATE INDEX IF NOT EXISTS idx_registrations_event ON registrations(event_id);
     CREATE INDEX IF NOT EXISTS idx_registrations_user ON registrations(user_id);
+    CREATE INDEX IF NOT EXISTS idx_registrations_ticket_type ON registrations(event_id, ticket_type_id);
+
+    CREATE TABLE IF NOT EXISTS calendar_tokens (
+        user_id UUID PRIMARY KEY,
+        token VARCHAR(64) NOT NULL UNIQUE,
+        created_at TIMESTAMP NOT NULL DEFAULT NOW()
+    );
+
+    CREATE TABLE IF NOT EXISTS event_stats (
+        event_id UUID PRIMARY KEY,
+        registrations INT NOT NULL DEFAULT 0,
+        confirmed INT NOT NULL DEFAULT 0,
+        waitlisted INT NOT NULL DEFAULT 0,
+        cancelled INT NOT NULL DEFAULT 0,
+        checked_in INT NOT NULL DEFAULT 0,
+        updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+    );
+
+    CREATE TABLE IF NOT EXISTS event_daily_stats (
+        event_id UUID NOT NULL,
+        day DATE NOT NULL,
+        registrations INT NOT NULL DEFAULT 0,
+        cancellations INT NOT NULL DEFAULT 0,
+        check_ins INT NOT NULL DEFAULT 0,
+        PRIMARY KEY (event_id, day)
+    );
+
+    CREATE TABLE IF NOT EXISTS event_breakdown_stats (
+        event_id UUID NOT NULL,
+        dimension VARCHAR(20) NOT NULL,
+        value VARCHAR(50) NOT NULL,
+        registrations INT NOT NULL DEFAULT 0,
+        confirmed INT NOT NULL DEFAULT 0,
+        cancelled INT NOT NULL DEFAULT 0,
+        checked_in INT NOT NULL DEFAULT 0,
+        PRIMARY KEY (event_id, dimension, value)
+    );
 EOSQL
 
 echo "Sve baze su spremne!"
@@ -90,3 +90,17 @@ pub fn require_role(claims: &Claims, required: &str) -> Result<(), (StatusCode,
         ))
     }
 }
+
+/// Header kojim servisi medjusobno salju zajednicki token za /internal/* rute
+pub const INTERNAL_TOKEN_HEADER: &str = "x-internal-token";
+
+/// Proverava servisni token na internim rutama (nisu za klijente, vracaju licne podatke)
+pub fn check_internal_token(headers: &HeaderMap, expected: &str) -> Result<(), (StatusCode, String)> {
+    match headers.get(INTERNAL_TOKEN_HEADER).and_then(|v| v.to_str().ok()) {
+        Some(token) if !expected.is_empty() && token == expected => Ok(()),
+        _ => Err((
+            StatusCode::UNAUTHORIZED,
+            "Interna ruta - nedostaje ili nije ispravan servisni token".to_string(),
+        )),
+    }
+}
@@ -0,0 +1,91 @@
+// Uslovi za prijavu na dogadjaj - cuva ih event-service, proverava registration-service
+
+use serde::{Deserialize, Serialize};
+
+/// Polja profila (auth-service) koja se mogu traziti; ostali nazivi su dodatni atributi profila
+pub const PROFILE_FIELDS: &[&str] = &["full_name", "date_of_birth", "organization", "phone"];
+
+/// Naziv dodatnog atributa: mala slova, brojevi i _, najvise 50 znakova
+pub fn valid_attribute_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= 50
+        && key.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Uslovi dogadjaja; prazna lista ili None znaci da uslova nema
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EligibilityRules {
+    /// navrsene godine na dan dogadjaja
+    #[serde(default)]
+    pub min_age: Option<i32>,
+    /// "firma.rs" pusta i poddomene (ime@dev.firma.rs); email nije verifikovan, pa ovo nije kontrola pristupa
+    #[serde(default)]
+    pub allowed_email_domains: Vec<String>,
+    /// polja iz PROFILE_FIELDS ili dodatni atributi koji moraju biti popunjeni
+    #[serde(default)]
+    pub required_attributes: Vec<String>,
+    /// najvise aktivnih prijava po korisniku na termine iste serije
+    #[serde(default)]
+    pub max_per_series: Option<i32>,
+}
+
+impl EligibilityRules {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attribute_keys() {
+        assert!(valid_attribute_key("student_id"));
+        assert!(valid_attribute_key("broj_2"));
+        assert!(valid_attribute_key(&"a".repeat(50)));
+
+        assert!(!valid_attribute_key(""));
+        assert!(!valid_attribute_key(&"a".repeat(51)));
+        assert!(!valid_attribute_key("Student"));
+        assert!(!valid_attribute_key("student-id"));
+        assert!(!valid_attribute_key("godište"));
+    }
+
+    #[test]
+    fn missing_fields_default_to_no_rules() {
+        let rules: EligibilityRules = serde_json::from_str("{}").unwrap();
+        assert!(rules.is_empty());
+
+        let rules: EligibilityRules = serde_json::from_str(r#"{"min_age":18}"#).unwrap();
+        assert!(!rules.is_empty());
+        assert_eq!(rules.min_age, Some(18));
+        assert!(rules.allowed_email_domains.is_empty());
+        assert_eq!(rules.max_per_series, None);
+    }
+
+    #[test]
+    fn any_rule_makes_rules_non_empty() {
+        assert!(EligibilityRules::default().is_empty());
+        for rules in [
+            EligibilityRules { min_age: Some(0), ..Default::default() },
+            EligibilityRules { allowed_email_domains: vec!["firma.rs".to_string()], ..Default::default() },
+            EligibilityRules { required_attributes: vec!["phone".to_string()], ..Default::default() },
+            EligibilityRules { max_per_series: Some(1), ..Default::default() },
+        ] {
+            assert!(!rules.is_empty(), "{:?}", rules);
+        }
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let rules = EligibilityRules {
+            min_age: Some(18),
+            allowed_email_domains: vec!["firma.rs".to_string()],
+            required_attributes: vec!["organization".to_string(), "student_id".to_string()],
+            max_per_series: Some(2),
+        };
+        let json = serde_json::to_string(&rules).unwrap();
+        assert_eq!(serde_json::from_str::<EligibilityRules>(&json).unwrap(), rules);
+    }
+}
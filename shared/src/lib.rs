@@ -2,3 +2,5 @@
 
 pub mod models;
 pub mod auth;
+pub mod staff;
+pub mod eligibility;
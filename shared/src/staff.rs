@@ -0,0 +1,77 @@
+// Osoblje dogadjaja - uloge i sta koja uloga sme (isto pravilo u event i registration servisu)
+
+use serde::{Deserialize, Serialize};
+
+/// Organizator dogadjaja (i Admin) - ima sve dozvole, nije red u event_staff
+pub const OWNER: &str = "owner";
+
+/// Uloge koje se dodeljuju osoblju
+pub const STAFF_ROLES: &[&str] = &["co_organizer", "editor", "checkin", "viewer"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    /// draftovi, skriveni tipovi karata, spisak prijavljenih
+    View,
+    /// check-in ulaznica na ulazu
+    CheckIn,
+    /// izmena dogadjaja, slike, agende i tipova karata
+    Edit,
+    /// dodavanje i uklanjanje osoblja
+    ManageStaff,
+    Delete,
+}
+
+/// Da li uloga (OWNER ili neka iz STAFF_ROLES) ima dozvolu
+pub fn role_allows(role: &str, permission: Permission) -> bool {
+    use Permission::*;
+    match role {
+        OWNER => true,
+        "co_organizer" => permission != Delete,
+        "editor" => matches!(permission, View | Edit),
+        "checkin" => matches!(permission, View | CheckIn),
+        "viewer" => permission == View,
+        _ => false,
+    }
+}
+
+/// Uloga korisnika na dogadjaju, kako je vraca event-service (/internal/events/:id/access/:user_id)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EventAccess {
+    pub role: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Permission::*;
+
+    const ALL: [Permission; 5] = [View, CheckIn, Edit, ManageStaff, Delete];
+
+    fn allowed(role: &str) -> Vec<Permission> {
+        ALL.into_iter().filter(|p| role_allows(role, *p)).collect()
+    }
+
+    #[test]
+    fn permissions_per_role() {
+        assert_eq!(allowed(OWNER), ALL);
+        assert_eq!(allowed("co_organizer"), [View, CheckIn, Edit, ManageStaff]);
+        assert_eq!(allowed("editor"), [View, Edit]);
+        assert_eq!(allowed("checkin"), [View, CheckIn]);
+        assert_eq!(allowed("viewer"), [View]);
+    }
+
+    #[test]
+    fn unknown_roles_have_no_permissions() {
+        for role in ["", "admin", "Owner", "EDITOR"] {
+            assert!(allowed(role).is_empty(), "{}", role);
+        }
+    }
+
+    #[test]
+    fn every_staff_role_can_view() {
+        for role in STAFF_ROLES {
+            assert!(role_allows(role, View), "{}", role);
+        }
+        assert!(!STAFF_ROLES.contains(&OWNER));
+    }
+}